@@ -39,6 +39,9 @@ extern const cbindgen_private::ItemVTable PathVTable;
 extern const cbindgen_private::ItemVTable FlickableVTable;
 extern const cbindgen_private::ItemVTable WindowVTable;
 extern const cbindgen_private::ItemVTable TextInputVTable;
+extern const cbindgen_private::ItemVTable VisibilityVTable;
+extern const cbindgen_private::ItemVTable OpacityVTable;
+extern const cbindgen_private::ItemVTable ClipVTable;
 
 extern const cbindgen_private::ItemVTable NativeButtonVTable;
 extern const cbindgen_private::ItemVTable NativeCheckBoxVTable;
@@ -58,10 +61,14 @@ using ComponentRef = VRef<private_api::ComponentVTable>;
 using ItemVisitorRefMut = VRefMut<cbindgen_private::ItemVisitorVTable>;
 }
 using cbindgen_private::EasingCurve;
+using cbindgen_private::ImageFit;
 using cbindgen_private::PropertyAnimation;
 using cbindgen_private::Slice;
 using cbindgen_private::TextHorizontalAlignment;
 using cbindgen_private::TextVerticalAlignment;
+using cbindgen_private::TextDecoration;
+using cbindgen_private::TextDirection;
+using cbindgen_private::FontStyle;
 using cbindgen_private::TraversalOrder;
 
 namespace private_api {
@@ -108,6 +115,9 @@ using cbindgen_private::Rectangle;
 using cbindgen_private::Text;
 using cbindgen_private::TextInput;
 using cbindgen_private::TouchArea;
+using cbindgen_private::Visibility;
+using cbindgen_private::Opacity;
+using cbindgen_private::Clip;
 using cbindgen_private::Window;
 
 using cbindgen_private::NativeButton;
@@ -45,4 +45,49 @@ private:
     using Tuple = std::tuple<Arg...>;
     cbindgen_private::SignalOpaque inner;
 };
+
+/// A signal whose handler returns a value of type Ret.
+/// The value returned by the handler is returned by emit(), or a default constructed Ret
+/// if there is no handler.
+template<typename Ret, typename... Arg>
+struct Signal<Ret(Arg...)>
+{
+    Signal() { cbindgen_private::sixtyfps_signal_init(&inner); }
+    ~Signal() { cbindgen_private::sixtyfps_signal_drop(&inner); }
+    Signal(const Signal &) = delete;
+    Signal(Signal &&) = delete;
+    Signal &operator=(const Signal &) = delete;
+
+    template<typename F>
+    void set_handler(F binding)
+    {
+        cbindgen_private::sixtyfps_signal_set_handler(
+                &inner,
+                [](void *user_data, const void *arg) {
+                    auto *emission = reinterpret_cast<const Emission *>(arg);
+                    *emission->result =
+                            std::apply(*reinterpret_cast<F *>(user_data), emission->args);
+                },
+                new F(std::move(binding)),
+                [](void *user_data) { delete reinterpret_cast<F *>(user_data); });
+    }
+
+    Ret emit(const Arg &...arg) const
+    {
+        Ret result{};
+        Emission emission{Tuple{arg...}, &result};
+        cbindgen_private::sixtyfps_signal_emit(&inner, &emission);
+        return result;
+    }
+
+private:
+    using Tuple = std::tuple<Arg...>;
+    /// What is passed through the signal: the arguments, and where to store the result
+    struct Emission
+    {
+        Tuple args;
+        Ret *result;
+    };
+    cbindgen_private::SignalOpaque inner;
+};
 }
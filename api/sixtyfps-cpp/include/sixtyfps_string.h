@@ -93,6 +93,26 @@ struct SharedString
     /// \endcode
     static SharedString from_number(double n) { return SharedString(n); }
 
+    /// Returns a copy of this string converted to upper case. The conversion uses the default
+    /// case mapping of Unicode, independent of the locale.
+    SharedString to_uppercase() const
+    {
+        SharedString result;
+        cbindgen_private::sixtyfps_shared_string_drop(&result);
+        cbindgen_private::sixtyfps_shared_string_to_uppercase(&result, this);
+        return result;
+    }
+
+    /// Returns a copy of this string converted to lower case. The conversion uses the default
+    /// case mapping of Unicode, independent of the locale.
+    SharedString to_lowercase() const
+    {
+        SharedString result;
+        cbindgen_private::sixtyfps_shared_string_drop(&result);
+        cbindgen_private::sixtyfps_shared_string_to_lowercase(&result, this);
+        return result;
+    }
+
     /// Returns true if \a is equal to \b; otherwise returns false.
     friend bool operator==(const SharedString &a, const SharedString &b)
     {
@@ -103,6 +123,30 @@ struct SharedString
     {
         return std::string_view(a) != std::string_view(b);
     }
+    /// Returns true if \a a is lexicographically less than \a b; otherwise returns false.
+    /// The strings are compared by their UTF-8 bytes, which is the order of their Unicode
+    /// code points.
+    friend bool operator<(const SharedString &a, const SharedString &b)
+    {
+        return std::string_view(a) < std::string_view(b);
+    }
+    /// Returns true if \a a is lexicographically less than or equal to \a b; otherwise returns
+    /// false.
+    friend bool operator<=(const SharedString &a, const SharedString &b)
+    {
+        return std::string_view(a) <= std::string_view(b);
+    }
+    /// Returns true if \a a is lexicographically greater than \a b; otherwise returns false.
+    friend bool operator>(const SharedString &a, const SharedString &b)
+    {
+        return std::string_view(a) > std::string_view(b);
+    }
+    /// Returns true if \a a is lexicographically greater than or equal to \a b; otherwise
+    /// returns false.
+    friend bool operator>=(const SharedString &a, const SharedString &b)
+    {
+        return std::string_view(a) >= std::string_view(b);
+    }
 
     /// Writes the \a shared_string to the specified \a stream and returns a reference to the
     /// stream.
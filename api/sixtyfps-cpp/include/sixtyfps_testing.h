@@ -10,6 +10,7 @@ LICENSE END */
 #pragma once
 #include "sixtyfps.h"
 #include <iostream>
+#include <initializer_list>
 
 namespace sixtyfps::testing {
 inline void mock_elapsed_time(int64_t time_in_ms)
@@ -23,6 +24,45 @@ inline void send_mouse_click(Component &component, float x, float y)
                                                 &component.window);
 }
 
+template<typename Component>
+inline void send_mouse_wheel(Component &component, float x, float y, float delta_x, float delta_y)
+{
+    cbindgen_private::sixtyfps_send_mouse_wheel({ &Component::component_type, &component }, x, y,
+                                                delta_x, delta_y, &component.window);
+}
+
+template<typename Component>
+inline void send_pinch_gesture(Component &component, float x, float y)
+{
+    cbindgen_private::sixtyfps_send_pinch_gesture({ &Component::component_type, &component }, x, y,
+                                                  &component.window);
+}
+
+template<typename Component>
+inline void send_resize_event(Component &component, float width, float height)
+{
+    cbindgen_private::sixtyfps_send_resize_event({ &Component::component_type, &component }, width,
+                                                 height, &component.window);
+}
+
+template<typename Component>
+inline void send_show_event(Component &component)
+{
+    cbindgen_private::sixtyfps_send_show_event({ &Component::component_type, &component },
+                                               &component.window);
+}
+
+template<typename Component>
+inline void send_dropped_files(Component &component,
+                               std::initializer_list<sixtyfps::SharedString> paths)
+{
+    cbindgen_private::Slice<sixtyfps::SharedString> slice {
+        const_cast<sixtyfps::SharedString *>(paths.begin()), paths.size()
+    };
+    cbindgen_private::sixtyfps_send_dropped_files({ &Component::component_type, &component },
+                                                  &slice, &component.window);
+}
+
 template<typename Component>
 inline void send_keyboard_string_sequence(Component &component, const sixtyfps::SharedString &str)
 {
@@ -20,31 +20,90 @@ function load_native_lib() {
 
 const native = !process.env.SIXTYFPS_NODE_NATIVE_LIB ? require('../native/index.node') : load_native_lib();
 
+// Add to `exports` the constructor of the component type `c`
+function export_component(exports, c) {
+    exports[c.name()] = function (init_properties) {
+        let comp = c.create(init_properties);
+        let ret = {
+            show() { comp.show() },
+            send_mouse_click(x, y) { comp.send_mouse_click(x, y) },
+            send_mouse_wheel(x, y, delta_x, delta_y) { comp.send_mouse_wheel(x, y, delta_x, delta_y) },
+            send_pinch_gesture(x, y) { comp.send_pinch_gesture(x, y) },
+            send_resize_event(width, height) { comp.send_resize_event(width, height) },
+            send_show_event() { comp.send_show_event() },
+            send_dropped_files(paths) { comp.send_dropped_files(paths) },
+            send_keyboard_string_sequence(s) { comp.send_keyboard_string_sequence(s) },
+            send_key_event(key, pressed) { comp.send_key_event(key, pressed) },
+            set_property(name, value, animation) { comp.set_property(name, value, animation) },
+            set_properties(values) { comp.set_properties(values) },
+            set_property_binding(name, binding) { comp.set_property_binding(name, binding) },
+            on_property_changed(name, callback) {
+                let id = comp.on_property_changed(name, callback);
+                // The id is reused by the next listener once removed, so only remove it once
+                return () => {
+                    if (id !== undefined) {
+                        comp.remove_property_listener(id);
+                        id = undefined;
+                    }
+                };
+            },
+            process_property_changes() { comp.process_property_changes() },
+            notify() { comp.notify() },
+            render_now() { comp.render_now() },
+            get_min_size() { return comp.get_min_size() },
+            item_geometry(id) { return comp.item_geometry(id) },
+            element_geometry_in(ancestor_id, id) { return comp.element_geometry_in(ancestor_id, id) },
+            logical_to_physical(x, y) { return comp.logical_to_physical(x, y) },
+            physical_to_logical(x, y) { return comp.physical_to_logical(x, y) },
+            set_scale_factor(factor) { comp.set_scale_factor(factor) },
+            get_model(id) { return comp.get_model(id) },
+            set_model(id, model) { comp.set_model(id, model) },
+            get_property(name) { return comp.get_property(name) },
+            has_property(name) { return comp.has_property(name) },
+            has_signal(name) { return comp.has_signal(name) },
+            set_focus_item(id) { comp.set_focus_item(id) }
+        };
+        c.properties().forEach(x => {
+            Object.defineProperty(ret, x, {
+                get() { return comp.get_property(x); },
+                set(newValue) { comp.set_property(x, newValue); },
+                enumerable: true,
+            })
+        });
+        c.signals().forEach(x => {
+            Object.defineProperty(ret, x, {
+                get() { return function () { return comp.emit_signal(x, [...arguments], native.strict_signal_arguments === true); } },
+                enumerable: true,
+            })
+        });
+        return ret;
+    }
+    exports[c.name()].signal_arguments = name => c.signal_arguments(name);
+    exports[c.name()].resources = () => c.resources();
+    exports[c.name()].element_tree = () => c.element_tree();
+    exports[c.name()].property_descriptors = () => c.property_descriptors();
+    exports[c.name()].has_property = name => c.has_property(name);
+    exports[c.name()].has_signal = name => c.has_signal(name);
+}
+
 require.extensions['.60'] =
     function (module, filename) {
-        var c = native.load(filename);
-        module.exports[c.name()] = function (init_properties) {
-            let comp = c.create(init_properties);
-            let ret = {
-                show() { comp.show() },
-                send_mouse_click(x, y) { comp.send_mouse_click(x, y) },
-                send_keyboard_string_sequence(s) { comp.send_keyboard_string_sequence(s) }
-            };
-            c.properties().forEach(x => {
-                Object.defineProperty(ret, x, {
-                    get() { return comp.get_property(x); },
-                    set(newValue) { comp.set_property(x, newValue); },
-                    enumerable: true,
-                })
-            });
-            c.signals().forEach(x => {
-                Object.defineProperty(ret, x, {
-                    get() { return function () { comp.emit_signal(x, [...arguments]); } },
-                    enumerable: true,
-                })
-            });
-            return ret;
-        }
+        export_component(module.exports, native.load(filename));
     }
 
+// Same as `require` of a .60 file, but the compiled component is re-used as long as the file
+// and the files it imports are unchanged. The syntax tree of the file is also saved on disk, so
+// that other processes don't parse the file again.
+native.loadCached = function (filename, options) {
+    let exports = {};
+    export_component(exports, native.load_cached(filename, options));
+    return exports;
+}
+
+// Compile the source of a .60 file to the Rust code that the `sixtyfps::sixtyfps!` macro would
+// generate for it, and return that code as a string.
+native.compileToRust = function (source, options) {
+    return native.compile_to_rust(source, options);
+}
+
 module.exports = native;
@@ -10,14 +10,22 @@ LICENSE END */
 use core::cell::RefCell;
 use neon::prelude::*;
 use sixtyfps_compilerlib::typeregister::Type;
-use sixtyfps_corelib::Resource;
+use sixtyfps_corelib::{Resource, SharedArray};
 
 use std::rc::Rc;
 
 mod persistent_context;
 
 struct WrappedComponentType(Option<Rc<sixtyfps_interpreter::ComponentDescription>>);
-struct WrappedComponentBox(Option<Rc<sixtyfps_interpreter::ComponentBox>>);
+struct WrappedComponentBox(
+    Option<Rc<sixtyfps_interpreter::ComponentBox>>,
+    /// Dependency of all the bindings set from JS, so they can be invalidated with `notify()`
+    std::pin::Pin<Rc<sixtyfps_corelib::Property<i32>>>,
+    /// The listeners installed with `on_property_changed()`, indexed by the id returned to JS,
+    /// with the index of their callback in the persistent context. They are set to None when
+    /// removed, and the id and the index are then reused by the next listener.
+    Rc<RefCell<Vec<(u32, Option<sixtyfps_interpreter::PropertyChangeListener>)>>>,
+);
 
 /// We need to do some gymnastic with closures to pass the ExecuteContext with the right lifetime
 type GlobalContextCallback =
@@ -49,35 +57,337 @@ fn run_scoped<'cx, T>(
 /// The first argument of this finction is a string to the .60 file
 ///
 /// The return value is a SixtyFpsComponentType
-fn load(mut cx: FunctionContext) -> JsResult<JsValue> {
+fn load(cx: FunctionContext) -> JsResult<JsValue> {
+    load_impl(cx, |path, compiler_config| {
+        let source = match std::fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(e) => return Err(e.to_string()),
+        };
+        Ok(sixtyfps_interpreter::load(source, &path, compiler_config))
+    })
+}
+
+/// Same as `load`, but re-use the component of a previous call if the .60 file, the files it
+/// imports and the include paths did not change.
+fn load_cached(cx: FunctionContext) -> JsResult<JsValue> {
+    load_impl(cx, |path, compiler_config| {
+        Ok(sixtyfps_interpreter::load_cached(path, compiler_config))
+    })
+}
+
+fn load_impl(
+    mut cx: FunctionContext,
+    load_fn: impl FnOnce(
+        &std::path::Path,
+        &sixtyfps_compilerlib::CompilerConfiguration,
+    ) -> Result<
+        (
+            Result<Rc<sixtyfps_interpreter::ComponentDescription>, ()>,
+            sixtyfps_compilerlib::diagnostics::BuildDiagnostics,
+        ),
+        String,
+    >,
+) -> JsResult<JsValue> {
     let path = cx.argument::<JsString>(0)?.value();
     let path = std::path::Path::new(path.as_str());
-    let include_paths = match std::env::var_os("SIXTYFPS_INCLUDE_PATH") {
-        Some(paths) => {
-            std::env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()).collect()
-        }
-        None => vec![],
-    };
+    let include_paths = include_paths_from_options(&mut cx, 1)?;
+    let features = features_from_options(&mut cx, 1)?;
     let compiler_config = sixtyfps_compilerlib::CompilerConfiguration {
         include_paths: &include_paths,
+        features: &features,
         ..Default::default()
     };
-    let source = std::fs::read_to_string(&path).or_else(|e| cx.throw_error(e.to_string()))?;
-    let (c, warnings) = match sixtyfps_interpreter::load(source, &path, &compiler_config) {
+    let result = load_fn(&path, &compiler_config).or_else(|e| cx.throw_error(e))?;
+    let (c, warnings) = match result {
         (Ok(c), warnings) => (c, warnings),
         (Err(()), errors) => {
+            let diagnostics = diagnostics_to_js(&mut cx, &errors)?;
             errors.print();
-            return cx.throw_error("Compilation error");
+            let error = JsError::error(&mut cx, "Compilation error")?;
+            error.set(&mut cx, "diagnostics", diagnostics)?;
+            return cx.throw(error);
         }
     };
 
+    let warnings_array = diagnostics_to_js(&mut cx, &warnings)?;
     warnings.print();
 
     let mut obj = SixtyFpsComponentType::new::<_, JsValue, _>(&mut cx, std::iter::empty())?;
     cx.borrow_mut(&mut obj, |mut obj| obj.0 = Some(c));
+    obj.set(&mut cx, "warnings", warnings_array)?;
     Ok(obj.as_value(&mut cx))
 }
 
+/// Convert a point to an object with the `x` and `y` properties
+fn point_to_js<'cx>(
+    cx: &mut impl Context<'cx>,
+    point: sixtyfps_corelib::graphics::Point,
+) -> JsResult<'cx, JsValue> {
+    let object = JsObject::new(cx);
+    let x = JsNumber::new(cx, point.x);
+    object.set(cx, "x", x)?;
+    let y = JsNumber::new(cx, point.y);
+    object.set(cx, "y", y)?;
+    Ok(object.as_value(cx))
+}
+
+/// Convert a rectangle to an object with the `x`, `y`, `width` and `height` properties
+fn rect_to_js<'cx>(
+    cx: &mut impl Context<'cx>,
+    rect: sixtyfps_corelib::graphics::Rect,
+) -> JsResult<'cx, JsValue> {
+    let geometry = JsObject::new(cx);
+    let x = JsNumber::new(cx, rect.origin.x);
+    geometry.set(cx, "x", x)?;
+    let y = JsNumber::new(cx, rect.origin.y);
+    geometry.set(cx, "y", y)?;
+    let width = JsNumber::new(cx, rect.size.width);
+    geometry.set(cx, "width", width)?;
+    let height = JsNumber::new(cx, rect.size.height);
+    geometry.set(cx, "height", height)?;
+    Ok(geometry.as_value(cx))
+}
+
+/// Converts the options of an animation, `{ duration, easing, loop_count }`, to a PropertyAnimation.
+/// The duration is in milliseconds, and the easing is the name of an easing curve of the .60 language.
+fn to_property_animation<'cx>(
+    options: Handle<'cx, JsObject>,
+    cx: &mut impl Context<'cx>,
+) -> NeonResult<sixtyfps_corelib::items::PropertyAnimation> {
+    let mut animation = sixtyfps_corelib::items::PropertyAnimation::default();
+    let duration = options.get(cx, "duration")?;
+    if !duration.is_a::<JsUndefined>() {
+        animation.duration = duration.downcast_or_throw::<JsNumber, _>(cx)?.value() as i32;
+    }
+    let loop_count = options.get(cx, "loop_count")?;
+    if !loop_count.is_a::<JsUndefined>() {
+        animation.loop_count = loop_count.downcast_or_throw::<JsNumber, _>(cx)?.value() as i32;
+    }
+    let easing = options.get(cx, "easing")?;
+    if !easing.is_a::<JsUndefined>() {
+        let easing = easing.downcast_or_throw::<JsString, _>(cx)?.value();
+        use sixtyfps_compilerlib::expression_tree::EasingCurve;
+        animation.easing = match EasingCurve::from_name(&easing) {
+            Some(EasingCurve::Linear) => sixtyfps_corelib::animations::EasingCurve::Linear,
+            Some(EasingCurve::CubicBezier(a, b, c, d)) => {
+                sixtyfps_corelib::animations::EasingCurve::CubicBezier([a, b, c, d])
+            }
+            None => return cx.throw_error(format!("Unknown easing curve {}", easing)),
+        };
+    }
+    Ok(animation)
+}
+
+/// Type-check the source of a .60 file without loading it.
+///
+/// The first argument is the source. The optional second argument is an object with the `path`
+/// of the file, used to resolve the relative imports, and the `includePaths`.
+///
+/// The return value is the array of the diagnostics, in the same format as the `diagnostics`
+/// of the error thrown by `load`.
+fn check(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let source = cx.argument::<JsString>(0)?.value();
+    let path = path_from_options(&mut cx, 1)?;
+    let include_paths = include_paths_from_options(&mut cx, 1)?;
+    let features = features_from_options(&mut cx, 1)?;
+    let compiler_config = sixtyfps_compilerlib::CompilerConfiguration {
+        include_paths: &include_paths,
+        features: &features,
+        ..Default::default()
+    };
+    let (syntax_node, diagnostics) = sixtyfps_compilerlib::parser::parse(source, Some(&path));
+    let diagnostics = if diagnostics.has_error() {
+        let mut d = sixtyfps_compilerlib::diagnostics::BuildDiagnostics::default();
+        d.add(diagnostics);
+        d
+    } else {
+        sixtyfps_compilerlib::check_syntax_node(syntax_node, diagnostics, &compiler_config)
+    };
+    Ok(diagnostics_to_js(&mut cx, &diagnostics)?.as_value(&mut cx))
+}
+
+/// Compile the source of a .60 file to Rust code, like the `sixtyfps::sixtyfps!` macro does.
+///
+/// The arguments are the same as for `check`. The return value is the generated Rust code as a
+/// string. A compilation error is thrown like in `load`.
+fn compile_to_rust(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let source = cx.argument::<JsString>(0)?.value();
+    let path = path_from_options(&mut cx, 1)?;
+    let include_paths = include_paths_from_options(&mut cx, 1)?;
+    let features = features_from_options(&mut cx, 1)?;
+    let compiler_config = sixtyfps_compilerlib::CompilerConfiguration {
+        include_paths: &include_paths,
+        features: &features,
+        ..Default::default()
+    };
+    let (syntax_node, diagnostics) = sixtyfps_compilerlib::parser::parse(source, Some(&path));
+    let (doc, mut diagnostics) =
+        sixtyfps_compilerlib::compile_syntax_node(syntax_node, diagnostics, &compiler_config);
+    let mut output = Vec::new();
+    if !diagnostics.has_error() {
+        sixtyfps_compilerlib::generator::generate(
+            sixtyfps_compilerlib::generator::OutputFormat::Rust,
+            &mut output,
+            &doc,
+            &mut diagnostics,
+        )
+        .or_else(|e| cx.throw_error(e.to_string()))?;
+    }
+    if diagnostics.has_error() {
+        let js_diagnostics = diagnostics_to_js(&mut cx, &diagnostics)?;
+        let error = JsError::error(&mut cx, "Compilation error")?;
+        error.set(&mut cx, "diagnostics", js_diagnostics)?;
+        return cx.throw(error);
+    }
+    let output = String::from_utf8(output).or_else(|e| cx.throw_error(e.to_string()))?;
+    Ok(JsString::new(&mut cx, output).as_value(&mut cx))
+}
+
+/// Read the `path` of the options object passed as the argument `arg`, or `<source>` if there is none.
+fn path_from_options(cx: &mut FunctionContext, arg: i32) -> NeonResult<std::path::PathBuf> {
+    let path = match cx.argument_opt(arg) {
+        Some(options) => {
+            let options = options.downcast_or_throw::<JsObject, _>(cx)?;
+            let path = options.get(cx, "path")?;
+            if path.is_a::<JsUndefined>() {
+                None
+            } else {
+                Some(path.downcast_or_throw::<JsString, _>(cx)?.value())
+            }
+        }
+        None => None,
+    };
+    Ok(std::path::PathBuf::from(path.unwrap_or_else(|| "<source>".into())))
+}
+
+/// Read the `includePaths` of the options object passed as the argument `arg`, and fall back to
+/// the `SIXTYFPS_INCLUDE_PATH` environment variable.
+fn include_paths_from_options(
+    cx: &mut FunctionContext,
+    arg: i32,
+) -> NeonResult<Vec<std::path::PathBuf>> {
+    let include_paths_option = match cx.argument_opt(arg) {
+        Some(options) => {
+            let options = options.downcast_or_throw::<JsObject, _>(cx)?;
+            let include_paths = options.get(cx, "includePaths")?;
+            if include_paths.is_a::<JsUndefined>() {
+                None
+            } else {
+                let include_paths = include_paths.downcast_or_throw::<JsArray, _>(cx)?;
+                let mut paths = vec![];
+                for include_path in include_paths.to_vec(cx)? {
+                    let include_path = std::path::PathBuf::from(
+                        include_path.downcast_or_throw::<JsString, _>(cx)?.value(),
+                    );
+                    if !include_path.exists() {
+                        return cx.throw_error(format!(
+                            "Include path {} does not exist",
+                            include_path.display()
+                        ));
+                    }
+                    paths.push(include_path);
+                }
+                Some(paths)
+            }
+        }
+        None => None,
+    };
+    Ok(match include_paths_option {
+        Some(paths) => paths,
+        None => match std::env::var_os("SIXTYFPS_INCLUDE_PATH") {
+            Some(paths) => {
+                std::env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()).collect()
+            }
+            None => vec![],
+        },
+    })
+}
+
+/// Read the `features` of the options object passed as the argument `arg`: the names of the
+/// features for which `feature("name")` is true in the .60 files.
+fn features_from_options(cx: &mut FunctionContext, arg: i32) -> NeonResult<Vec<String>> {
+    let options = match cx.argument_opt(arg) {
+        Some(options) => options.downcast_or_throw::<JsObject, _>(cx)?,
+        None => return Ok(vec![]),
+    };
+    let features = options.get(cx, "features")?;
+    if features.is_a::<JsUndefined>() {
+        return Ok(vec![]);
+    }
+    let features = features.downcast_or_throw::<JsArray, _>(cx)?;
+    let mut result = vec![];
+    for feature in features.to_vec(cx)? {
+        result.push(feature.downcast_or_throw::<JsString, _>(cx)?.value());
+    }
+    Ok(result)
+}
+
+/// Convert the diagnostics to an array of `{ message, file, line, column, level, category }` objects
+fn diagnostics_to_js<'cx>(
+    cx: &mut impl Context<'cx>,
+    diagnostics: &sixtyfps_compilerlib::diagnostics::BuildDiagnostics,
+) -> JsResult<'cx, JsArray> {
+    use sixtyfps_compilerlib::diagnostics::{Diagnostic, DiagnosticCategory, Level};
+    let array = JsArray::new(cx, 0);
+    let mut len: u32 = 0;
+    for file_diagnostics in diagnostics.iter() {
+        for diagnostic in &file_diagnostics.inner {
+            let (message, (line, column), level, category) = match diagnostic {
+                Diagnostic::CompilerDiagnostic(d) => (
+                    d.message.clone(),
+                    file_diagnostics.line_column(d.span.offset),
+                    match d.level {
+                        Level::Error => "error",
+                        Level::Warning => "warning",
+                    },
+                    d.category,
+                ),
+                Diagnostic::FileLoadError(err) => {
+                    (err.to_string(), (0, 0), "error", DiagnosticCategory::Correctness)
+                }
+            };
+            let obj = JsObject::new(cx);
+            let message = JsString::new(cx, message);
+            obj.set(cx, "message", message)?;
+            let file = JsString::new(cx, file_diagnostics.current_path.to_string_lossy());
+            obj.set(cx, "file", file)?;
+            let line = JsNumber::new(cx, line as f64);
+            obj.set(cx, "line", line)?;
+            let column = JsNumber::new(cx, column as f64);
+            obj.set(cx, "column", column)?;
+            let level = JsString::new(cx, level);
+            obj.set(cx, "level", level)?;
+            let category = JsString::new(cx, category.as_str());
+            obj.set(cx, "category", category)?;
+            array.set(cx, len, obj)?;
+            len = len + 1;
+        }
+    }
+    Ok(array)
+}
+
+/// Convert an element of the tree returned by `ComponentDescription::element_tree` to an
+/// `{ id, type, repeated, children }` object
+fn element_info_to_js<'cx>(
+    cx: &mut impl Context<'cx>,
+    info: &sixtyfps_interpreter::ElementInfo,
+) -> JsResult<'cx, JsObject> {
+    let obj = JsObject::new(cx);
+    let id = JsString::new(cx, &info.id);
+    obj.set(cx, "id", id)?;
+    let type_name = JsString::new(cx, &info.type_name);
+    obj.set(cx, "type", type_name)?;
+    let repeated = JsBoolean::new(cx, info.repeated);
+    obj.set(cx, "repeated", repeated)?;
+    let children = JsArray::new(cx, info.children.len() as u32);
+    for (i, child) in info.children.iter().enumerate() {
+        let child = element_info_to_js(cx, child)?;
+        children.set(cx, i as u32, child)?;
+    }
+    obj.set(cx, "children", children)?;
+    Ok(obj)
+}
+
 fn create<'cx>(
     cx: &mut CallContext<'cx, impl neon::object::This>,
     component_type: Rc<sixtyfps_interpreter::ComponentDescription>,
@@ -97,7 +407,7 @@ fn create<'cx>(
                     cx.throw_error(format!("Property {} not found in the component", prop_name))
                 })?
                 .clone();
-            if let Type::Signal { .. } = ty {
+            if let Type::Signal { return_type, .. } = ty {
                 let _fun = value.downcast_or_throw::<JsFunction, _>(cx)?;
                 let fun_idx = persistent_context.allocate(cx, value);
                 component_type
@@ -106,21 +416,29 @@ fn create<'cx>(
                         prop_name.as_str(),
                         Box::new(move |args| {
                             let args = args.iter().cloned().collect::<Vec<_>>();
-                            GLOBAL_CONTEXT.with(move |cx_fn| {
-                                cx_fn(&move |cx, presistent_context| {
+                            let return_type = return_type.clone();
+                            let result = RefCell::new(sixtyfps_interpreter::Value::Void);
+                            GLOBAL_CONTEXT.with(|cx_fn| {
+                                cx_fn(&|cx, presistent_context| {
                                     let args = args
                                         .iter()
                                         .map(|a| to_js_value(a.clone(), cx).unwrap())
                                         .collect::<Vec<_>>();
-                                    presistent_context
+                                    let ret = presistent_context
                                         .get(cx, fun_idx)
                                         .unwrap()
                                         .downcast::<JsFunction>()
                                         .unwrap()
                                         .call::<_, _, JsValue, _>(cx, JsUndefined::new(), args)
                                         .unwrap();
+                                    if let Some(return_type) = &return_type {
+                                        *result.borrow_mut() =
+                                            to_eval_value(ret, (**return_type).clone(), cx)
+                                                .unwrap();
+                                    }
                                 })
-                            })
+                            });
+                            result.into_inner()
                         }),
                     )
                     .or_else(|_| cx.throw_error(format!("Cannot set signal")))?;
@@ -150,8 +468,66 @@ fn to_eval_value<'cx>(
             Ok(Value::Number(val.downcast_or_throw::<JsNumber, _>(cx)?.value()))
         }
         Type::String => Ok(Value::String(val.to_string(cx)?.value().into())),
-        Type::Color | Type::Array(_) | Type::Object(_) => todo!(),
-        Type::Resource => Ok(Value::String(val.to_string(cx)?.value().into())),
+        Type::Array(a) => {
+            let array = val.downcast_or_throw::<JsArray, _>(cx)?;
+            Ok(Value::Array(
+                array
+                    .to_vec(cx)?
+                    .into_iter()
+                    .map(|item| to_eval_value(item, (*a).clone(), cx))
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+        Type::Object(o) => {
+            let obj = val.downcast_or_throw::<JsObject, _>(cx)?;
+            Ok(Value::Object(
+                o.iter()
+                    .map(|(pro_name, pro_ty)| {
+                        let field = obj.get(cx, pro_name.as_str())?;
+                        let value = if field.is_a::<JsUndefined>() {
+                            // Missing fields get the default value of their type
+                            match sixtyfps_interpreter::default_value_for_type(pro_ty) {
+                                Value::Void => {
+                                    return cx.throw_error(format!(
+                                        "A value of type {} must be provided",
+                                        pro_ty
+                                    ))
+                                }
+                                value => value,
+                            }
+                        } else {
+                            to_eval_value(field, pro_ty.clone(), cx)?
+                        };
+                        Ok((pro_name.clone(), value))
+                    })
+                    .collect::<Result<_, _>>()?,
+            ))
+        }
+        Type::Color => todo!(),
+        Type::Resource => {
+            if val.is_a::<JsString>() {
+                return Ok(Value::Resource(Resource::AbsoluteFilePath(
+                    val.to_string(cx)?.value().into(),
+                )));
+            }
+            let obj = val.downcast_or_throw::<JsObject, _>(cx)?;
+            let width = obj.get(cx, "width")?.downcast_or_throw::<JsNumber, _>(cx)?.value() as u32;
+            let height =
+                obj.get(cx, "height")?.downcast_or_throw::<JsNumber, _>(cx)?.value() as u32;
+            let buffer = obj.get(cx, "data")?.downcast_or_throw::<JsBuffer, _>(cx)?;
+            let data = cx.borrow(&buffer, |data| SharedArray::from_slice(data.as_slice::<u8>()));
+            let expected_len = width as usize * height as usize * 4;
+            if data.len() != expected_len {
+                return cx.throw_error(format!(
+                    "The image data has {} bytes, but a {}x{} RGBA image needs {}",
+                    data.len(),
+                    width,
+                    height,
+                    expected_len
+                ));
+            }
+            Ok(Value::Resource(Resource::EmbeddedRgbaImage { width, height, data }))
+        }
         Type::Bool => Ok(Value::Bool(val.downcast_or_throw::<JsBoolean, _>(cx)?.value())),
         Type::Component(c) if c.root_element.borrow().base_type == Type::Void => {
             let obj = val.downcast_or_throw::<JsObject, _>(cx)?;
@@ -273,11 +649,83 @@ declare_types! {
             }
             Ok(array.as_value(&mut cx))
         }
+        method has_property(mut cx) {
+            let name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let found = ct.properties().get(&name).map_or(false, |ty| ty.is_property_type());
+            Ok(JsBoolean::new(&mut cx, found).as_value(&mut cx))
+        }
+        method has_signal(mut cx) {
+            let name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let found = matches!(ct.properties().get(&name), Some(Type::Signal { .. }));
+            Ok(JsBoolean::new(&mut cx, found).as_value(&mut cx))
+        }
+        method signal_arguments(mut cx) {
+            let signal_name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let args = match ct.properties().remove(&signal_name) {
+                Some(Type::Signal { args, .. }) => args,
+                Some(_) => return cx.throw_error(format!("{} is not a signal", signal_name)),
+                None => {
+                    return cx.throw_error(format!("Signal {} not found in the component", signal_name))
+                }
+            };
+            let array = JsArray::new(&mut cx, args.len() as u32);
+            for (i, arg) in args.iter().enumerate() {
+                let arg_type = JsString::new(&mut cx, arg.to_string());
+                array.set(&mut cx, i as u32, arg_type)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
+        method resources(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let resources = ct.referenced_file_resources();
+            let array = JsArray::new(&mut cx, resources.len() as u32);
+            for (i, path) in resources.iter().enumerate() {
+                let path = JsString::new(&mut cx, path);
+                array.set(&mut cx, i as u32, path)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
+        method property_descriptors(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let descriptors = ct.property_descriptors();
+            let array = JsArray::new(&mut cx, descriptors.len() as u32);
+            for (i, descriptor) in descriptors.iter().enumerate() {
+                let obj = JsObject::new(&mut cx);
+                let name = JsString::new(&mut cx, &descriptor.name);
+                obj.set(&mut cx, "name", name)?;
+                let ty = JsString::new(&mut cx, descriptor.ty.to_string());
+                obj.set(&mut cx, "type", ty)?;
+                let read_only = JsBoolean::new(&mut cx, descriptor.read_only);
+                obj.set(&mut cx, "readonly", read_only)?;
+                array.set(&mut cx, i as u32, obj)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
+        method element_tree(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let tree = ct.element_tree();
+            Ok(element_info_to_js(&mut cx, &tree)?.as_value(&mut cx))
+        }
     }
 
     class SixtyFpsComponent for WrappedComponentBox {
         init(_) {
-            Ok(WrappedComponentBox(None))
+            Ok(WrappedComponentBox(None, Rc::pin(Default::default()), Default::default()))
         }
         method show(mut cx) {
             let mut this = cx.this();
@@ -289,15 +737,43 @@ declare_types! {
             })?;
             Ok(JsUndefined::new().as_value(&mut cx))
         }
+        method has_property(mut cx) {
+            let name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let x = this.borrow(&lock).0.clone();
+            let component = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let found = component
+                .description()
+                .properties()
+                .get(&name)
+                .map_or(false, |ty| ty.is_property_type());
+            Ok(JsBoolean::new(&mut cx, found).as_value(&mut cx))
+        }
+        method has_signal(mut cx) {
+            let name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let x = this.borrow(&lock).0.clone();
+            let component = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let found = matches!(
+                component.description().properties().get(&name),
+                Some(Type::Signal { .. })
+            );
+            Ok(JsBoolean::new(&mut cx, found).as_value(&mut cx))
+        }
         method get_property(mut cx) {
             let prop_name = cx.argument::<JsString>(0)?.value();
             let this = cx.this();
             let lock = cx.lock();
             let x = this.borrow(&lock).0.clone();
             let component = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
-            let value = component.description()
-                .get_property(component.borrow(), prop_name.as_str())
-                .or_else(|_| cx.throw_error(format!("Cannot read property")))?;
+            // Reading the property may evaluate bindings set from JS
+            let value = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .get_property(component.borrow(), prop_name.as_str())
+                    .map_err(|()| format!("Property {} not found in the component", prop_name))
+            })?;
             to_js_value(value, &mut cx)
         }
         method set_property(mut cx) {
@@ -315,15 +791,199 @@ declare_types! {
                 .clone();
 
             let value = to_eval_value(cx.argument::<JsValue>(1)?, ty, &mut cx)?;
+            let animation = match cx.argument_opt(2) {
+                Some(options) if !options.is_a::<JsUndefined>() => {
+                    let options = options.downcast_or_throw::<JsObject, _>(&mut cx)?;
+                    Some(to_property_animation(options, &mut cx)?)
+                }
+                _ => None,
+            };
+            match animation {
+                Some(animation) => component.description()
+                    .set_property_with_animation(component.borrow(), prop_name.as_str(), value, animation),
+                None => component.description()
+                    .set_property(component.borrow(), prop_name.as_str(), value),
+            }
+            .or_else(|_| cx.throw_error(format!("Cannot assign property")))?;
+
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method set_properties(mut cx) {
+            let obj = cx.argument::<JsObject>(0)?;
+            let this = cx.this();
+            let lock = cx.lock();
+            let x = this.borrow(&lock).0.clone();
+            let component  = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let properties = component.description().properties();
+            let mut values = Vec::new();
+            for x in obj.get_own_property_names(&mut cx)?.to_vec(&mut cx)? {
+                let prop_name = x.to_string(&mut cx)?.value();
+                let ty = properties
+                    .get(&prop_name)
+                    .ok_or(())
+                    .or_else(|()| {
+                        cx.throw_error(format!("Property {} not found in the component", prop_name))
+                    })?
+                    .clone();
+                let value = obj.get(&mut cx, x)?;
+                values.push((prop_name, to_eval_value(value, ty, &mut cx)?));
+            }
+            component.description()
+                .set_properties(component.borrow(), values)
+                .or_else(|_| cx.throw_error(format!("Cannot assign properties")))?;
+
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method set_property_binding(mut cx) {
+            let prop_name = cx.argument::<JsString>(0)?.value();
+            let fun = cx.argument::<JsFunction>(1)?;
+            let this = cx.this();
+            let lock = cx.lock();
+            let (x, notifier) = {
+                let wrapped = this.borrow(&lock);
+                (wrapped.0.clone(), wrapped.1.clone())
+            };
+            let component = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.description().properties()
+                .get(&prop_name)
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Property {} not found in the component", prop_name))
+                })?
+                .clone();
+            if !ty.is_property_type() {
+                return cx.throw_error(format!("{} is not a property", prop_name));
+            }
+
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let fun_idx = persistent_context.allocate(&mut cx, fun.as_value(&mut cx));
             component.description()
-                .set_property(component.borrow(), prop_name.as_str(), value)
-                .or_else(|_| cx.throw_error(format!("Cannot assign property")))?;
+                .set_binding(
+                    component.borrow(),
+                    prop_name.as_str(),
+                    Box::new(move || {
+                        // Register the dependency so that notify() re-evaluates the binding
+                        notifier.as_ref().get();
+                        let result = RefCell::new(sixtyfps_interpreter::Value::Void);
+                        GLOBAL_CONTEXT.with(|cx_fn| {
+                            cx_fn(&|cx, presistent_context| {
+                                let ret = presistent_context
+                                    .get(cx, fun_idx)
+                                    .unwrap()
+                                    .downcast::<JsFunction>()
+                                    .unwrap()
+                                    .call::<_, _, JsValue, _>(cx, JsUndefined::new(), std::iter::empty::<Handle<JsValue>>())
+                                    .unwrap();
+                                *result.borrow_mut() = to_eval_value(ret, ty.clone(), cx).unwrap();
+                            })
+                        });
+                        result.into_inner()
+                    }),
+                )
+                .or_else(|_| cx.throw_error(format!("Cannot set binding")))?;
+
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method notify(mut cx) {
+            let this = cx.this();
+            let lock = cx.lock();
+            let notifier = this.borrow(&lock).1.clone();
+            notifier.as_ref().set(notifier.as_ref().get_untracked() + 1);
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method on_property_changed(mut cx) {
+            let prop_name = cx.argument::<JsString>(0)?.value();
+            let fun = cx.argument::<JsFunction>(1)?;
+            let this = cx.this();
+            let lock = cx.lock();
+            let (x, listeners) = {
+                let wrapped = this.borrow(&lock);
+                (wrapped.0.clone(), wrapped.2.clone())
+            };
+            let component = x.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let free_id = listeners.borrow().iter().position(|(_, listener)| listener.is_none());
+            let (id, fun_idx) = match free_id {
+                Some(id) => {
+                    let fun_idx = listeners.borrow()[id].0;
+                    persistent_context.set(&mut cx, fun_idx, fun.as_value(&mut cx));
+                    (id, fun_idx)
+                }
+                None => {
+                    let fun_idx = persistent_context.allocate(&mut cx, fun.as_value(&mut cx));
+                    let mut listeners = listeners.borrow_mut();
+                    listeners.push((fun_idx, None));
+                    (listeners.len() - 1, fun_idx)
+                }
+            };
+            let listener = match sixtyfps_interpreter::PropertyChangeListener::new(
+                &component,
+                prop_name.as_str(),
+                move |value| {
+                    GLOBAL_CONTEXT.with(|cx_fn| {
+                        cx_fn(&|cx, presistent_context| {
+                            let value = to_js_value(value.clone(), cx).unwrap();
+                            presistent_context
+                                .get(cx, fun_idx)
+                                .unwrap()
+                                .downcast::<JsFunction>()
+                                .unwrap()
+                                .call::<_, _, JsValue, _>(cx, JsUndefined::new(), vec![value])
+                                .unwrap();
+                        })
+                    })
+                },
+            ) {
+                Ok(listener) => listener,
+                Err(()) => {
+                    persistent_context.free(&mut cx, fun_idx);
+                    return cx.throw_error(format!("Property {} not found in the component", prop_name));
+                }
+            };
 
+            listeners.borrow_mut()[id].1 = Some(listener);
+            Ok(JsNumber::new(&mut cx, id as f64).as_value(&mut cx))
+        }
+        method remove_property_listener(mut cx) {
+            let id = cx.argument::<JsNumber>(0)?.value() as usize;
+            let this = cx.this();
+            let lock = cx.lock();
+            let listeners = this.borrow(&lock).2.clone();
+            let fun_idx = match listeners.borrow_mut().get_mut(id) {
+                Some((fun_idx, listener)) if listener.is_some() => {
+                    *listener = None;
+                    *fun_idx
+                }
+                _ => return Ok(JsUndefined::new().as_value(&mut cx)),
+            };
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            persistent_context.free(&mut cx, fun_idx);
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method process_property_changes(mut cx) {
+            let this = cx.this();
+            let lock = cx.lock();
+            let listeners = this.borrow(&lock).2.clone();
+            // The callbacks may add or remove listeners
+            let listeners =
+                listeners.borrow().iter().filter_map(|(_, listener)| listener.clone()).collect::<Vec<_>>();
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                listeners.iter().for_each(|listener| listener.process());
+                Ok(())
+            })?;
             Ok(JsUndefined::new().as_value(&mut cx))
         }
         method emit_signal(mut cx) {
             let signal_name = cx.argument::<JsString>(0)?.value();
             let arguments = cx.argument::<JsArray>(1)?.to_vec(&mut cx)?;
+            let strict = match cx.argument_opt(2) {
+                Some(strict) => strict.downcast_or_throw::<JsBoolean, _>(&mut cx)?.value(),
+                None => false,
+            };
             let this = cx.this();
             let lock = cx.lock();
             let x = this.borrow(&lock).0.clone();
@@ -334,27 +994,181 @@ declare_types! {
                     cx.throw_error(format!("Signal {} not found in the component", signal_name))
                 })?
                 .clone();
-            let args = if let Type::Signal {args} = ty {
+            let (args, return_type) = if let Type::Signal {args, return_type} = ty {
                 let count = args.len();
-                let args = arguments.into_iter().zip(args.into_iter()).map(|(a, ty)| to_eval_value(a, ty, &mut cx)).collect::<Result<Vec<_>, _>>()?;
-                if args.len() != count {
-                    cx.throw_error(format!("{} expect {} arguments, but {} where provided", signal_name, count, args.len()))?;
+                if arguments.len() > count || (strict && arguments.len() != count) {
+                    cx.throw_error(format!("{} expect {} arguments, but {} where provided", signal_name, count, arguments.len()))?;
+                }
+                let provided = arguments.len();
+                let mut values = arguments.into_iter().zip(args.iter()).map(|(a, ty)| to_eval_value(a, ty.clone(), &mut cx)).collect::<Result<Vec<_>, _>>()?;
+                // The arguments that were omitted get the default value of their type
+                for ty in &args[provided..] {
+                    match sixtyfps_interpreter::default_value_for_type(ty) {
+                        Value::Void => cx.throw_error(format!("Cannot use a default value for an argument of type {}", ty))?,
+                        value => values.push(value),
+                    }
                 }
-                args
+                (values, return_type)
 
             } else {
                 cx.throw_error(format!("{} is not a signal", signal_name))?;
                 unreachable!()
             };
 
-            run_scoped(&mut cx,this.downcast().unwrap(), || {
+            let result = run_scoped(&mut cx,this.downcast().unwrap(), || {
                 component.description()
                     .emit_signal(component.borrow(), signal_name.as_str(), args.as_slice())
                     .map_err(|()| "Cannot emit signal".to_string())
             })?;
+            if return_type.is_some() {
+                to_js_value(result, &mut cx)
+            } else {
+                Ok(JsUndefined::new().as_value(&mut cx))
+            }
+        }
+
+        method get_min_size(mut cx) {
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let (width, height) = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                Ok(component.min_size())
+            })?;
+            let size = JsObject::new(&mut cx);
+            let width = JsNumber::new(&mut cx, width);
+            size.set(&mut cx, "width", width)?;
+            let height = JsNumber::new(&mut cx, height);
+            size.set(&mut cx, "height", height)?;
+            Ok(size.as_value(&mut cx))
+        }
+
+        method item_geometry(mut cx) {
+            let id = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let rect = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .item_geometry(component.borrow(), id.as_str())
+                    .map_err(|()| format!("Element {} not found in the component", id))
+            })?;
+            rect_to_js(&mut cx, rect)
+        }
+
+        method element_geometry_in(mut cx) {
+            let ancestor_id = cx.argument::<JsString>(0)?.value();
+            let id = cx.argument::<JsString>(1)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let rect = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .element_geometry_in(component.borrow(), ancestor_id.as_str(), id.as_str())
+                    .map_err(|()| format!("Element {} not found within the element {} of the component", id, ancestor_id))
+            })?;
+            rect_to_js(&mut cx, rect)
+        }
+
+        method logical_to_physical(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let point = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                Ok(component.window().logical_to_physical(sixtyfps_corelib::graphics::Point::new(x, y)))
+            })?;
+            point_to_js(&mut cx, point)
+        }
+
+        method physical_to_logical(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let point = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                Ok(component.window().physical_to_logical(sixtyfps_corelib::graphics::Point::new(x, y)))
+            })?;
+            point_to_js(&mut cx, point)
+        }
+
+        method set_scale_factor(mut cx) {
+            let factor = cx.argument::<JsNumber>(0)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.window().set_scale_factor(factor);
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method get_model(mut cx) {
+            let id = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let model = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .get_model(component.borrow(), id.as_str())
+                    .map_err(|()| format!("Repeated element {} not found in the component", id))
+            })?;
+            to_js_value(model, &mut cx)
+        }
+
+        method set_model(mut cx) {
+            let id = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.description().model_type(id.as_str())
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Repeated element {} not found in the component", id))
+                })?;
+            let model = to_eval_value(cx.argument::<JsValue>(1)?, ty, &mut cx)?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .set_model(component.borrow(), id.as_str(), model)
+                    .map_err(|()| format!("Cannot set the model of the repeated element {}", id))
+            })?;
             Ok(JsUndefined::new().as_value(&mut cx))
         }
 
+        method set_focus_item(mut cx) {
+            let id = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.description()
+                    .set_focus_item(component.borrow(), id.as_str())
+                    .map_err(|()| format!("Element {} not found in the component, or it cannot have the focus", id))
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method render_now(mut cx) {
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.window().render_now(component.borrow());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
         method send_mouse_click(mut cx) {
             let x = cx.argument::<JsNumber>(0)?.value() as f32;
             let y = cx.argument::<JsNumber>(1)?.value() as f32;
@@ -369,6 +1183,96 @@ declare_types! {
             Ok(JsUndefined::new().as_value(&mut cx))
         }
 
+        method send_mouse_wheel(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let delta_x = cx.argument::<JsNumber>(2)?.value() as f32;
+            let delta_y = cx.argument::<JsNumber>(3)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_mouse_wheel(component.borrow(), x, y, delta_x, delta_y, &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_pinch_gesture(mut cx) {
+            let x = cx.argument::<JsNumber>(0)?.value() as f32;
+            let y = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_pinch_gesture(component.borrow(), x, y, &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_resize_event(mut cx) {
+            let width = cx.argument::<JsNumber>(0)?.value() as f32;
+            let height = cx.argument::<JsNumber>(1)?.value() as f32;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_resize_event(component.borrow(), width, height, &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_dropped_files(mut cx) {
+            let paths = cx.argument::<JsArray>(0)?.to_vec(&mut cx)?;
+            let paths = paths
+                .into_iter()
+                .map(|p| Ok(p.to_string(&mut cx)?.value().into()))
+                .collect::<NeonResult<Vec<sixtyfps_corelib::SharedString>>>()?;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_dropped_files(component.borrow(), &paths.as_slice().into(), &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_show_event(mut cx) {
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_show_event(component.borrow(), &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
+        method send_key_event(mut cx) {
+            let key = cx.argument::<JsString>(0)?.value();
+            let pressed = cx.argument::<JsBoolean>(1)?.value();
+            let key_code = key
+                .parse::<sixtyfps_corelib::input::KeyCode>()
+                .or_else(|_| cx.throw_error(format!("Unknown key code {}", key)))?;
+            let this = cx.this();
+            let lock = cx.lock();
+            let comp = this.borrow(&lock).0.clone();
+            let component = comp.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            run_scoped(&mut cx,this.downcast().unwrap(), || {
+                sixtyfps_corelib::tests::sixtyfps_send_key_event(component.borrow(), key_code, pressed, &component.window());
+                Ok(())
+            })?;
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+
         method send_keyboard_string_sequence(mut cx) {
             let sequence = cx.argument::<JsString>(0)?.value();
             let this = cx.this();
@@ -386,7 +1290,12 @@ declare_types! {
 
 register_module!(mut m, {
     m.export_function("load", load)?;
+    m.export_function("load_cached", load_cached)?;
+    m.export_function("check", check)?;
+    m.export_function("compile_to_rust", compile_to_rust)?;
     m.export_function("mock_elapsed_time", mock_elapsed_time)?;
+    m.export_function("set_animations_paused", set_animations_paused)?;
+    m.export_function("trace_property_changes", trace_property_changes)?;
     Ok(())
 });
 
@@ -396,3 +1305,17 @@ fn mock_elapsed_time(mut cx: FunctionContext) -> JsResult<JsValue> {
     sixtyfps_corelib::tests::sixtyfps_mock_elapsed_time(ms as _);
     Ok(JsUndefined::new().as_value(&mut cx))
 }
+
+/// Pause or resume all the animations
+fn set_animations_paused(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let paused = cx.argument::<JsBoolean>(0)?.value();
+    sixtyfps_corelib::animations::set_animations_paused(paused);
+    Ok(JsUndefined::new().as_value(&mut cx))
+}
+
+/// Enable or disable the printing of every property change, for debugging purposes
+fn trace_property_changes(mut cx: FunctionContext) -> JsResult<JsValue> {
+    let enabled = cx.argument::<JsBoolean>(0)?.value();
+    sixtyfps_interpreter::set_trace_property_changes(enabled);
+    Ok(JsUndefined::new().as_value(&mut cx))
+}
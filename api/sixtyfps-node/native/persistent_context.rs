@@ -29,6 +29,18 @@ impl<'a> PersistentContext<'a> {
         idx
     }
 
+    /// Replace the value at an index previously returned by `allocate`.
+    pub fn set(&self, cx: &mut impl Context<'a>, idx: u32, value: Handle<'a, JsValue>) {
+        self.0.set(cx, idx, value).unwrap();
+    }
+
+    /// Release the value at the given index so it can be garbage collected. The index can
+    /// then be reused with `set`.
+    pub fn free(&self, cx: &mut impl Context<'a>, idx: u32) {
+        let undefined = JsUndefined::new().upcast();
+        self.0.set(cx, idx, undefined).unwrap();
+    }
+
     pub fn get(&self, cx: &mut impl Context<'a>, idx: u32) -> JsResult<'a, JsValue> {
         self.0.get(cx, idx)
     }
@@ -113,6 +113,7 @@ pub use sixtyfps_rs_macro::sixtyfps;
 pub(crate) mod repeater;
 
 pub use crate::repeater::{Model, ModelHandle, ModelNotify, ModelPeer, VecModel};
+pub use sixtyfps_corelib::animations::set_animations_paused;
 pub use sixtyfps_corelib::sharedarray::SharedArray;
 pub use sixtyfps_corelib::string::SharedString;
 pub use sixtyfps_corelib::{ARGBColor, Color};
@@ -126,7 +127,7 @@ pub mod re_exports {
     pub use once_cell::sync::Lazy;
     pub use once_cell::unsync::OnceCell;
     pub use pin_weak::rc::*;
-    pub use sixtyfps_corelib::animations::EasingCurve;
+    pub use sixtyfps_corelib::animations::{animation_tick, EasingCurve};
     pub use sixtyfps_corelib::component::{Component, ComponentRefPin, ComponentVTable};
     pub use sixtyfps_corelib::eventloop::ComponentWindow;
     pub use sixtyfps_corelib::graphics::{
@@ -160,6 +161,33 @@ pub mod re_exports {
     pub use vtable::{self, *};
 }
 
+/// Registers the font in the TrueType or OpenType file at `path` under the `family` name. Text
+/// elements with that `font_family` use it instead of looking for a system font.
+///
+/// If the file cannot be loaded, a warning is printed, an error is returned and the family
+/// keeps on resolving to the system fonts.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn register_font_from_path(
+    family: &str,
+    path: impl AsRef<std::path::Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sixtyfps_corelib::font::FONT_CACHE
+        .with(|fc| fc.register_font_from_path(family, path.as_ref()))
+        .map_err(|err| err.into())
+}
+
+/// Registers the font in `data`, the contents of a TrueType or OpenType file, under the
+/// `family` name. This is useful for fonts embedded in the application with `include_bytes!`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn register_font_from_memory(
+    family: &str,
+    data: impl Into<Vec<u8>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    sixtyfps_corelib::font::FONT_CACHE
+        .with(|fc| fc.register_font_from_memory(family, data.into()))
+        .map_err(|err| err.into())
+}
+
 /// Creates a new window to render components in.
 #[doc(hidden)]
 pub fn create_window() -> re_exports::ComponentWindow {
@@ -175,6 +203,9 @@ pub mod testing {
         fn component_window(&self) -> &super::re_exports::ComponentWindow;
     }
 
+    pub use sixtyfps_corelib::eventloop::LayoutTracker;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use sixtyfps_corelib::headless::create_headless_window;
     pub use sixtyfps_corelib::tests::sixtyfps_mock_elapsed_time as mock_elapsed_time;
     /// Simulate a mouse click
     pub fn send_mouse_click<
@@ -192,6 +223,90 @@ pub mod testing {
         );
     }
 
+    /// Simulate the mouse wheel being turned while the cursor is at `x`, `y`. Positive deltas
+    /// scroll towards the top left of the content.
+    pub fn send_mouse_wheel<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + HasWindow,
+    >(
+        component: core::pin::Pin<&X>,
+        x: f32,
+        y: f32,
+        delta_x: f32,
+        delta_y: f32,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_mouse_wheel(
+            vtable::VRef::new_pin(component),
+            x,
+            y,
+            delta_x,
+            delta_y,
+            component.component_window(),
+        );
+    }
+
+    /// Simulate a pinch gesture with two fingers around `x`, `y`. The first finger is lifted first.
+    pub fn send_pinch_gesture<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + HasWindow,
+    >(
+        component: core::pin::Pin<&X>,
+        x: f32,
+        y: f32,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_pinch_gesture(
+            vtable::VRef::new_pin(component),
+            x,
+            y,
+            component.component_window(),
+        );
+    }
+
+    /// Simulate the window of the component being resized by the windowing system. The layout
+    /// is recomputed and the `resized` signal of the root `Window` element is emitted with the
+    /// new size.
+    pub fn send_resize_event<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + HasWindow,
+    >(
+        component: core::pin::Pin<&X>,
+        width: f32,
+        height: f32,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_resize_event(
+            vtable::VRef::new_pin(component),
+            width,
+            height,
+            component.component_window(),
+        );
+    }
+
+    /// Simulate the window of the component being shown. The first frame is rendered and the `shown`
+    /// signal of the root `Window` element is emitted, as the event loop does after the first frame.
+    pub fn send_show_event<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + HasWindow,
+    >(
+        component: core::pin::Pin<&X>,
+    ) {
+        sixtyfps_corelib::tests::sixtyfps_send_show_event(
+            vtable::VRef::new_pin(component),
+            component.component_window(),
+        );
+    }
+
+    /// Simulate files being dropped on the window of the component. The `file_dropped` signal of
+    /// the root `Window` element is emitted with the absolute path of each file, then `files_dropped`.
+    pub fn send_dropped_files<
+        X: vtable::HasStaticVTable<sixtyfps_corelib::component::ComponentVTable> + HasWindow,
+    >(
+        component: core::pin::Pin<&X>,
+        paths: &[&str],
+    ) {
+        let paths = paths.iter().map(|p| super::SharedString::from(*p)).collect::<Vec<_>>();
+        sixtyfps_corelib::tests::sixtyfps_send_dropped_files(
+            vtable::VRef::new_pin(component),
+            &crate::re_exports::Slice::from_slice(&paths),
+            component.component_window(),
+        );
+    }
+
     /// Simulate a change in keyboard modifiers being pressed
     pub fn set_current_keyboard_modifiers<X: HasWindow>(
         component: core::pin::Pin<&X>,
@@ -60,7 +60,22 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                     }
                     ';' => SyntaxKind::Semicolon,
                     '!' => SyntaxKind::Bang,
-                    '.' => SyntaxKind::Dot,
+                    '.' => {
+                        // `...` is made of three joint dots, accumulate them in the last token
+                        if let Some(last) = vec.last_mut() {
+                            if last.kind == SyntaxKind::Dot && prev_spacing == Spacing::Joint {
+                                if last.text == "." {
+                                    last.text = "..".into();
+                                    continue;
+                                } else if last.text == ".." {
+                                    last.kind = SyntaxKind::DotDotDot;
+                                    last.text = "...".into();
+                                    continue;
+                                }
+                            }
+                        }
+                        SyntaxKind::Dot
+                    }
                     '+' => SyntaxKind::Plus,
                     '-' => SyntaxKind::Minus,
                     '*' => SyntaxKind::Star,
@@ -79,6 +94,12 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                                 last.kind = SyntaxKind::FatArrow;
                                 last.text = "=>".into();
                                 continue;
+                            } else if last.kind == SyntaxKind::Minus
+                                && prev_spacing == Spacing::Joint
+                            {
+                                last.kind = SyntaxKind::Arrow;
+                                last.text = "->".into();
+                                continue;
                             }
                         }
                         SyntaxKind::RAngle
@@ -131,6 +152,21 @@ fn fill_token_vec(stream: impl Iterator<Item = TokenTree>, vec: &mut Vec<parser:
                 let s = l.to_string();
                 // Why can't the rust API give me the type of the literal
                 let f = s.chars().next().unwrap();
+                if f == 'r' {
+                    // The Rust tokenizer rejects the `\{` of the string interpolations, so such
+                    // strings are written as raw strings, whose content is lexed as a .60 string.
+                    let content = s[1..].trim_matches('#');
+                    let content = &content[1..content.len() - 1];
+                    vec.extend(
+                        lexer::lex(&format!("\"{}\"", content))
+                            .into_iter()
+                            .filter(|t| {
+                                !matches!(t.kind, SyntaxKind::Whitespace | SyntaxKind::Comment)
+                            })
+                            .map(|t| parser::Token { span: Some(l.span()), ..t }),
+                    );
+                    continue;
+                }
                 let kind = if f == '"' {
                     SyntaxKind::StringLiteral
                 } else if f.is_digit(10) {
@@ -222,6 +258,10 @@ fn extract_include_paths(
 
 /// This macro allows you to use the `.60` design markup language inline in Rust code. Within the braces of the macro
 /// you can use place `.60` code and the named exported components will be available for instantiation.
+///
+/// String literals that interpolate expressions with `\{...}` must be written as raw strings, such as
+/// `r"Hello \{name}!"` or `r#"\{to_uppercase("\{name}")}"#`, because the Rust tokenizer does
+/// not accept this escape in regular string literals.
 #[proc_macro]
 pub fn sixtyfps(stream: TokenStream) -> TokenStream {
     let token_iter = stream.into_iter();
@@ -134,7 +134,7 @@ Hello := Rectangle {
     Path {
         x: 100px;
         y: 300px;
-        fill_color: green;
+        fill: green;
         LineTo {
             x: 100;
             y: 50;
@@ -7,7 +7,7 @@
     This file is also available under commercial licensing terms.
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::rc::Rc;
 
@@ -70,12 +70,48 @@ impl From<Level> for codemap_diagnostic::Level {
     }
 }
 
+/// The kind of problem reported by a diagnostic, so that tools can treat some categories of
+/// warnings differently (for example, fail the build on them)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiagnosticCategory {
+    /// The code is likely not doing what is intended. Also used for all the errors
+    Correctness,
+    /// Some code has no effect
+    Unused,
+    /// The code is correct but could be written in a better way
+    Style,
+}
+
+impl Default for DiagnosticCategory {
+    fn default() -> Self {
+        Self::Correctness
+    }
+}
+
+impl DiagnosticCategory {
+    /// The name of the category, in lower case
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCategory::Correctness => "correctness",
+            DiagnosticCategory::Unused => "unused",
+            DiagnosticCategory::Style => "style",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(thiserror::Error, Default, Debug)]
 #[error("{message}")]
 pub struct CompilerDiagnostic {
     pub message: String,
     pub span: Span,
     pub level: Level,
+    pub category: DiagnosticCategory,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -103,7 +139,16 @@ impl IntoIterator for FileDiagnostics {
 
 impl FileDiagnostics {
     pub fn push_diagnostic_with_span(&mut self, message: String, span: Span, level: Level) {
-        self.inner.push(CompilerDiagnostic { message, span, level }.into());
+        self.inner.push(CompilerDiagnostic { message, span, level, ..Default::default() }.into());
+    }
+    pub fn push_warning_with_span(
+        &mut self,
+        message: String,
+        span: Span,
+        category: DiagnosticCategory,
+    ) {
+        self.inner
+            .push(CompilerDiagnostic { message, span, level: Level::Warning, category }.into());
     }
     pub fn push_error_with_span(&mut self, message: String, span: Span) {
         self.push_diagnostic_with_span(message, span, Level::Error)
@@ -143,7 +188,9 @@ impl FileDiagnostics {
             .inner
             .into_iter()
             .map(|diagnostic| match diagnostic {
-                Diagnostic::CompilerDiagnostic(CompilerDiagnostic { message, span, level }) => {
+                Diagnostic::CompilerDiagnostic(CompilerDiagnostic {
+                    message, span, level, ..
+                }) => {
                     let spans = if !internal_errors {
                         let s = codemap_diagnostic::SpanLabel {
                             span: file_span.subspan(span.offset as u64, span.offset as u64),
@@ -232,6 +279,19 @@ impl FileDiagnostics {
         self.inner.iter().map(|d| d.to_string()).collect()
     }
 
+    /// Returns the line and column, both starting at 1, of the given byte offset within the source
+    /// of this file. Returns (0, 0) if the source is not known.
+    pub fn line_column(&self, offset: usize) -> (usize, usize) {
+        let source = match &self.source {
+            Some(source) => source,
+            None => return (0, 0),
+        };
+        let before = source.get(..offset).unwrap_or(source);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or_default().chars().count() + 1;
+        (line, column)
+    }
+
     pub fn new_from_error(path: std::path::PathBuf, err: std::io::Error) -> Self {
         Self { inner: vec![err.into()], current_path: Rc::new(path), source: None }
     }
@@ -251,6 +311,7 @@ impl quote::ToTokens for FileDiagnostics {
                     level,
                     message,
                     span,
+                    ..
                 }) => {
                     match level {
                         Level::Error => {
@@ -304,6 +365,33 @@ impl BuildDiagnostics {
         message: String,
         source: &dyn SpannedWithSourceFile,
         level: Level,
+    ) {
+        self.push_compiler_diagnostic(
+            CompilerDiagnostic { message, span: source.span(), level, ..Default::default() },
+            source,
+        )
+    }
+
+    pub fn push_error(&mut self, message: String, source: &dyn SpannedWithSourceFile) {
+        self.push_diagnostic(message, source, Level::Error)
+    }
+
+    pub fn push_warning(
+        &mut self,
+        message: String,
+        source: &dyn SpannedWithSourceFile,
+        category: DiagnosticCategory,
+    ) {
+        self.push_compiler_diagnostic(
+            CompilerDiagnostic { message, span: source.span(), level: Level::Warning, category },
+            source,
+        )
+    }
+
+    fn push_compiler_diagnostic(
+        &mut self,
+        diagnostic: CompilerDiagnostic,
+        source: &dyn SpannedWithSourceFile,
     ) {
         match source.source_file() {
             Some(source_file) => self
@@ -313,17 +401,11 @@ impl BuildDiagnostics {
                     current_path: source_file.clone(),
                     ..Default::default()
                 })
-                .push_diagnostic_with_span(message, source.span(), level),
-            None => self.push_internal_error(
-                CompilerDiagnostic { message, span: source.span(), level }.into(),
-            ),
+                .push_compiler_error(diagnostic),
+            None => self.push_internal_error(diagnostic.into()),
         }
     }
 
-    pub fn push_error(&mut self, message: String, source: &dyn SpannedWithSourceFile) {
-        self.push_diagnostic(message, source, Level::Error)
-    }
-
     pub fn push_internal_error(&mut self, err: Diagnostic) {
         self.internal_errors
             .get_or_insert_with(|| FileDiagnostics {
@@ -334,7 +416,7 @@ impl BuildDiagnostics {
             .push(err)
     }
 
-    fn iter(&self) -> impl Iterator<Item = &FileDiagnostics> {
+    pub fn iter(&self) -> impl Iterator<Item = &FileDiagnostics> {
         self.per_input_file_diagnostics.values().chain(self.internal_errors.iter())
     }
 
@@ -354,6 +436,23 @@ impl BuildDiagnostics {
         self.iter().any(|diag| diag.has_error())
     }
 
+    /// Returns the number of warnings in each category. Categories without warnings are not
+    /// in the map.
+    pub fn warning_count_by_category(&self) -> BTreeMap<DiagnosticCategory, usize> {
+        let mut counts = BTreeMap::new();
+        for diag in self.iter().flat_map(|diag| diag.inner.iter()) {
+            if let Diagnostic::CompilerDiagnostic(CompilerDiagnostic {
+                level: Level::Warning,
+                category,
+                ..
+            }) = diag
+            {
+                *counts.entry(*category).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
     pub fn to_string_vec(&self) -> Vec<String> {
         self.iter()
             .flat_map(|diag| {
@@ -408,3 +507,16 @@ impl Extend<FileDiagnostics> for BuildDiagnostics {
         }
     }
 }
+
+#[test]
+fn test_line_column() {
+    let diag = FileDiagnostics {
+        source: Some("Foo := Rectangle {\n    colour: red;\n}\n".into()),
+        ..Default::default()
+    };
+    assert_eq!(diag.line_column(0), (1, 1));
+    assert_eq!(diag.line_column(7), (1, 8));
+    assert_eq!(diag.line_column(23), (2, 5));
+    assert_eq!(diag.line_column(1000), (4, 1));
+    assert_eq!(FileDiagnostics::default().line_column(5), (0, 0));
+}
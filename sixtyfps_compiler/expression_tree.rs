@@ -13,7 +13,7 @@ use crate::parser::SyntaxNodeWithSourceFile;
 use crate::typeregister::BuiltinElement;
 use crate::typeregister::{EnumerationValue, Type};
 use core::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::rc::{Rc, Weak};
 
@@ -44,6 +44,17 @@ impl Hash for NamedReference {
 pub enum BuiltinFunction {
     GetWindowScaleFactor,
     Debug,
+    /// Converts a string to upper case, using the Unicode default case mapping
+    StringToUppercase,
+    /// Converts a string to lower case, using the Unicode default case mapping
+    StringToLowercase,
+    /// The number of rows of an array or a model
+    ArrayLength,
+    /// `Math.clamp(value, min, max)`: the value bounded by min and max, or min if min > max
+    /// or if the value is NaN
+    Clamp,
+    /// `animation_tick()`: the time of the animations, as a duration since the start of the program
+    AnimationTick,
 }
 
 impl BuiltinFunction {
@@ -55,6 +66,19 @@ impl BuiltinFunction {
             BuiltinFunction::Debug => {
                 Type::Function { return_type: Box::new(Type::Void), args: vec![Type::String] }
             }
+            BuiltinFunction::StringToUppercase | BuiltinFunction::StringToLowercase => {
+                Type::Function { return_type: Box::new(Type::String), args: vec![Type::String] }
+            }
+            BuiltinFunction::ArrayLength => {
+                Type::Function { return_type: Box::new(Type::Int32), args: vec![Type::Model] }
+            }
+            BuiltinFunction::Clamp => Type::Function {
+                return_type: Box::new(Type::Float32),
+                args: vec![Type::Float32, Type::Float32, Type::Float32],
+            },
+            BuiltinFunction::AnimationTick => {
+                Type::Function { return_type: Box::new(Type::Duration), args: vec![] }
+            }
         }
     }
 }
@@ -167,6 +191,11 @@ pub enum Expression {
 
     /// A string literal. The .0 is the content of the string, without the quotes
     StringLiteral(String),
+    /// A string with interpolated expressions, such as `"Hello \{name}!"`.
+    /// All the parts have the type `Type::String`, and the result is their concatenation.
+    StringInterpolation {
+        parts: Vec<Expression>,
+    },
     /// Number
     NumberLiteral(f64, Unit),
     ///
@@ -277,7 +306,8 @@ pub enum Expression {
     },
     Object {
         ty: Type,
-        values: HashMap<String, Expression>,
+        /// Sorted by name, like the fields of `Type::Object`, so that the output is deterministic
+        values: BTreeMap<String, Expression>,
     },
 
     PathElements {
@@ -302,6 +332,7 @@ impl Expression {
             Expression::Invalid => Type::Invalid,
             Expression::Uncompiled(_) => Type::Invalid,
             Expression::StringLiteral(_) => Type::String,
+            Expression::StringInterpolation { .. } => Type::String,
             Expression::NumberLiteral(_, unit) => unit.ty(),
             Expression::BoolLiteral(_) => Type::Bool,
             Expression::TwoWayBinding(NamedReference { element, name }) => {
@@ -341,7 +372,11 @@ impl Expression {
             },
             Expression::Cast { to, .. } => to.clone(),
             Expression::CodeBlock(sub) => sub.last().map_or(Type::Void, |e| e.ty()),
-            Expression::FunctionCall { function, .. } => function.ty(),
+            Expression::FunctionCall { function, .. } => match function.ty() {
+                Type::Function { return_type, .. } => *return_type,
+                Type::Signal { return_type, .. } => return_type.map_or(Type::Void, |t| *t),
+                _ => Type::Invalid,
+            },
             Expression::SelfAssignment { .. } => Type::Void,
             Expression::ResourceReference { .. } => Type::Resource,
             Expression::Condition { condition: _, true_expr, false_expr } => {
@@ -393,6 +428,7 @@ impl Expression {
             Expression::Uncompiled(_) => {}
             Expression::TwoWayBinding(_) => {}
             Expression::StringLiteral(_) => {}
+            Expression::StringInterpolation { parts } => parts.iter().for_each(visitor),
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
             Expression::SignalReference { .. } => {}
@@ -455,6 +491,7 @@ impl Expression {
             Expression::Uncompiled(_) => {}
             Expression::TwoWayBinding(_) => {}
             Expression::StringLiteral(_) => {}
+            Expression::StringInterpolation { parts } => parts.iter_mut().for_each(visitor),
             Expression::NumberLiteral(_, _) => {}
             Expression::BoolLiteral(_) => {}
             Expression::SignalReference { .. } => {}
@@ -517,6 +554,7 @@ impl Expression {
             Expression::Uncompiled(_) => false,
             Expression::TwoWayBinding(_) => false,
             Expression::StringLiteral(_) => true,
+            Expression::StringInterpolation { parts } => parts.iter().all(Expression::is_constant),
             Expression::NumberLiteral(_, _) => true,
             Expression::BoolLiteral(_) => true,
             Expression::SignalReference { .. } => false,
@@ -564,6 +602,33 @@ impl Expression {
         let ty = self.ty();
         if ty == target_type {
             self
+        } else if !ty.can_convert(&target_type) && self.is_partial_literal_of(&target_type) {
+            // Object literals may omit fields, which then get their default value
+            match (self, target_type) {
+                (Expression::Object { mut values, .. }, Type::Object(target_fields)) => {
+                    Expression::Object {
+                        values: target_fields
+                            .iter()
+                            .map(|(name, t)| {
+                                let value = match values.remove(name) {
+                                    Some(value) => value.maybe_convert_to(t.clone(), node, diag),
+                                    None => Expression::default_value_for_type(t),
+                                };
+                                (name.clone(), value)
+                            })
+                            .collect(),
+                        ty: Type::Object(target_fields),
+                    }
+                }
+                (Expression::Array { values, .. }, Type::Array(element_ty)) => Expression::Array {
+                    values: values
+                        .into_iter()
+                        .map(|e| e.maybe_convert_to((*element_ty).clone(), node, diag))
+                        .collect(),
+                    element_ty: *element_ty,
+                },
+                _ => unreachable!(),
+            }
         } else if ty.can_convert(&target_type) {
             let from =
                 match (ty, &target_type) {
@@ -669,6 +734,25 @@ impl Expression {
         }
     }
 
+    /// Return true if this is an object literal whose fields all exist in the `target_type`
+    /// object, possibly nested in other object literals or array literals, so that it can be
+    /// converted to that type by giving the default value to the missing fields.
+    fn is_partial_literal_of(&self, target_type: &Type) -> bool {
+        let converts_to =
+            |e: &Expression, t: &Type| e.ty().can_convert(t) || e.is_partial_literal_of(t);
+        match (self, target_type) {
+            (Expression::Object { values, .. }, Type::Object(target_fields)) => {
+                values.iter().all(|(name, value)| {
+                    target_fields.get(name).map_or(false, |t| converts_to(value, t))
+                })
+            }
+            (Expression::Array { values, .. }, Type::Array(element_ty)) => {
+                values.iter().all(|value| converts_to(value, element_ty))
+            }
+            _ => false,
+        }
+    }
+
     /// Return the default value for the given type
     pub fn default_value_for_type(ty: &Type) -> Expression {
         match ty {
@@ -773,3 +857,18 @@ impl Default for EasingCurve {
         Self::Linear
     }
 }
+
+impl EasingCurve {
+    /// Returns the easing curve with that name in the `.60` language, such as `ease_in_out`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        // These value are coming from CSSn with - replaced by _
+        match name {
+            "linear" => Some(EasingCurve::Linear),
+            "ease" => Some(EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0)),
+            "ease_in" => Some(EasingCurve::CubicBezier(0.42, 0.0, 1.0, 1.0)),
+            "ease_in_out" => Some(EasingCurve::CubicBezier(0.42, 0.0, 0.58, 1.0)),
+            "ease_out" => Some(EasingCurve::CubicBezier(0.0, 0.0, 0.58, 1.0)),
+            _ => None,
+        }
+    }
+}
@@ -227,6 +227,7 @@ fn get_cpp_type(ty: &Type, type_node: &dyn Spanned, diag: &mut BuildDiagnostics)
             message: "Cannot map property type to C++".into(),
             span: type_node.span(),
             level: Level::Error,
+            category: Default::default(),
         };
         diag.push_internal_error(err.into());
         "".into()
@@ -322,7 +323,7 @@ fn handle_item(elem: &ElementRc, main_struct: &mut Struct, init: &mut Vec<String
     let id = &item.id;
     init.extend(item.bindings.iter().map(|(prop_name, binding_expression)| {
         let prop_ty = item.lookup_property(prop_name.as_str());
-        if let Type::Signal { args } = &prop_ty {
+        if let Type::Signal { args, return_type } = &prop_ty {
             let signal_accessor_prefix = if item.property_declarations.contains_key(prop_name) {
                 String::new()
             } else {
@@ -336,11 +337,12 @@ fn handle_item(elem: &ElementRc, main_struct: &mut Struct, init: &mut Vec<String
                 "{signal_accessor_prefix}{prop}.set_handler(
                     [this]({params}) {{
                         [[maybe_unused]] auto self = this;
-                        {code};
+                        {return_statement}{code};
                     }});",
                 signal_accessor_prefix = signal_accessor_prefix,
                 prop = prop_name,
                 params = params.join(", "),
+                return_statement = if return_type.is_some() { "return " } else { "" },
                 code = compile_expression(binding_expression, &component)
             )
         } else if let Expression::TwoWayBinding(nr) = &binding_expression.expression {
@@ -532,14 +534,17 @@ fn generate_component(
     let mut init = vec!["[[maybe_unused]] auto self = this;".into()];
 
     for (cpp_name, property_decl) in component.root_element.borrow().property_declarations.iter() {
-        let ty = if let Type::Signal { args } = &property_decl.property_type {
+        let ty = if let Type::Signal { args, return_type } = &property_decl.property_type {
             let param_types = args
                 .iter()
                 .map(|t| get_cpp_type(t, &property_decl.type_node, diag))
                 .collect::<Vec<_>>();
+            let return_type =
+                return_type.as_ref().map(|rt| get_cpp_type(rt, &property_decl.type_node, diag));
             if property_decl.expose_in_public_api && is_root {
                 let signal_emitter = vec![format!(
-                    "{}.emit({});",
+                    "{}{}.emit({});",
+                    if return_type.is_some() { "return " } else { "" },
                     cpp_name,
                     (0..args.len()).map(|i| format!("arg_{}", i)).join(", ")
                 )];
@@ -548,12 +553,13 @@ fn generate_component(
                     Declaration::Function(Function {
                         name: format!("emit_{}", cpp_name),
                         signature: format!(
-                            "({})",
+                            "({}){}",
                             param_types
                                 .iter()
                                 .enumerate()
                                 .map(|(i, ty)| format!("{} arg_{}", ty, i))
-                                .join(", ")
+                                .join(", "),
+                            return_type.as_ref().map_or(String::new(), |rt| format!(" -> {}", rt))
                         ),
                         statements: Some(signal_emitter),
                         ..Default::default()
@@ -573,7 +579,10 @@ fn generate_component(
                     }),
                 ));
             }
-            format!("sixtyfps::Signal<{}>", param_types.join(", "))
+            match return_type {
+                Some(rt) => format!("sixtyfps::Signal<{}({})>", rt, param_types.join(", ")),
+                None => format!("sixtyfps::Signal<{}>", param_types.join(", ")),
+            }
         } else {
             let cpp_type =
                 get_cpp_type(&property_decl.property_type, &property_decl.type_node, diag);
@@ -911,6 +920,18 @@ fn generate_component(
         }),
     ));
 
+    component_struct.members.push((
+        Access::Public,
+        Declaration::Function(Function {
+            name: "layout_info".into(),
+            signature: "(sixtyfps::private_api::ComponentRef component) -> sixtyfps::LayoutInfo"
+                .into(),
+            is_static: true,
+            statements: Some(layout_info(component)),
+            ..Default::default()
+        }),
+    ));
+
     component_struct.members.push((
         Access::Public, // FIXME: we call this function from tests
         Declaration::Function(Function {
@@ -939,7 +960,7 @@ fn generate_component(
         ty: "const sixtyfps::private_api::ComponentVTable".to_owned(),
         name: format!("{}::component_type", component_id),
         init: Some(
-            "{ visit_children, nullptr, compute_layout, input_event, key_event, focus_event }"
+            "{ visit_children, layout_info, compute_layout, input_event, key_event, focus_event }"
                 .to_owned(),
         ),
     }));
@@ -979,6 +1000,7 @@ fn model_data_type(parent_element: &ElementRc, diag: &mut BuildDiagnostics) -> S
                         .map(|n| n.span())
                         .unwrap_or_default(),
                     level: Level::Error,
+                    category: Default::default(),
                 }
                 .into(),
             );
@@ -1044,6 +1066,13 @@ fn compile_expression(e: &crate::expression_tree::Expression, component: &Rc<Com
         Expression::StringLiteral(s) => {
             format!(r#"sixtyfps::SharedString("{}")"#, s.escape_debug())
         }
+        Expression::StringInterpolation { parts } => {
+            let parts = parts
+                .iter()
+                .map(|p| format!("s += std::string_view({});", compile_expression(p, component)))
+                .collect::<Vec<_>>();
+            format!("[&]{{ std::string s; {} return sixtyfps::SharedString(s); }}()", parts.join(" "))
+        }
         Expression::NumberLiteral(n, unit) => unit.normalize(*n).to_string(),
         Expression::BoolLiteral(b) => b.to_string(),
         Expression::PropertyReference(NamedReference { element, name }) => {
@@ -1063,6 +1092,21 @@ fn compile_expression(e: &crate::expression_tree::Expression, component: &Rc<Com
                 "[](auto... args){ (std::cout << ... << args) << std::endl; return nullptr; }"
                     .into()
             }
+            BuiltinFunction::StringToUppercase => {
+                "[](const sixtyfps::SharedString &s){ return s.to_uppercase(); }".into()
+            }
+            BuiltinFunction::StringToLowercase => {
+                "[](const sixtyfps::SharedString &s){ return s.to_lowercase(); }".into()
+            }
+            BuiltinFunction::ArrayLength => {
+                "[](const auto &model) -> int { return model ? model->row_count() : 0; }".into()
+            }
+            BuiltinFunction::Clamp => {
+                "[](double v, double min, double max) { return !(v >= min) || min > max ? min : (v > max ? max : v); }".into()
+            }
+            BuiltinFunction::AnimationTick => {
+                "[]() -> std::int64_t { return sixtyfps::cbindgen_private::sixtyfps_animation_tick(); }".into()
+            }
         },
         Expression::RepeaterIndexReference { element } => {
             let access = access_member(
@@ -1110,9 +1154,10 @@ fn compile_expression(e: &crate::expression_tree::Expression, component: &Rc<Com
                     format!("std::make_shared<sixtyfps::IntModel>({})", f)
                 }
                 (Type::Array(_), Type::Model) => f,
-                (Type::Float32, Type::Color) => {
+                (Type::Float32, Type::Color) | (Type::Int32, Type::Color) => {
                     format!("sixtyfps::Color::from_argb_encoded({})", f)
                 }
+                (Type::Color, Type::Int32) => format!("int({}.as_argb_encoded())", f),
                 (Type::Object(_), Type::Component(c))
                     if c.root_element.borrow().base_type == Type::Void =>
                 {
@@ -1336,8 +1381,12 @@ fn collect_layouts_recursively<'a, 'b>(
                 }
 
                 let get_property_ref = LayoutItemCodeGen::<CppLanguageLayoutGen>::get_property_ref;
+                let collapsed = cell
+                    .collapsed
+                    .as_ref()
+                    .map_or_else(|| "false".to_owned(), |e| compile_expression(e, component));
                 creation_code.push(format!(
-                    "        {{ {c}, {r}, {cs}, {rs}, {li}, {x}, {y}, {w}, {h} }},",
+                    "        {{ {c}, {r}, {cs}, {rs}, {li}, {x}, {y}, {w}, {h}, {collapsed} }},",
                     c = cell.col,
                     r = cell.row,
                     cs = cell.colspan,
@@ -1346,7 +1395,8 @@ fn collect_layouts_recursively<'a, 'b>(
                     x = get_property_ref(&cell.item, "x"),
                     y = get_property_ref(&cell.item, "y"),
                     w = get_property_ref(&cell.item, "width"),
-                    h = get_property_ref(&cell.item, "height")
+                    h = get_property_ref(&cell.item, "height"),
+                    collapsed = collapsed,
                 ));
             }
             let cell_ref_variable = format!("cells_{}", layout_tree.len()).to_owned();
@@ -1543,6 +1593,58 @@ impl<'a> LayoutTreeItem<'a> {
     }
 }
 
+fn layout_info(component: &Rc<Component>) -> Vec<String> {
+    let mut res = vec![];
+
+    let root_element = component.root_element.borrow();
+    res.push(format!(
+        "[[maybe_unused]] auto self = reinterpret_cast<const {ty}*>(component.instance);",
+        ty = component_id(component)
+    ));
+    res.push(format!(
+        "auto layout_info = sixtyfps::private_api::{vt}.layouting_info({{&sixtyfps::private_api::{vt}, const_cast<sixtyfps::{ty}*>(&self->{id})}}, &{window});",
+        vt = root_element.base_type.as_native().vtable_symbol,
+        ty = root_element.base_type.as_native().class_name,
+        id = root_element.id,
+        window = window_ref_expression(component)
+    ));
+    // Only the layouts that fill the root element constrain the size of the component
+    component
+        .layout_constraints
+        .borrow()
+        .iter()
+        .filter(|layout| {
+            matches!(layout, Layout::GridLayout(_))
+                && layout.rect().fills_element(&component.root_element)
+        })
+        .for_each(|layout| {
+            let mut layout_tree = Vec::new();
+            let root_layout_info = layout.get_layout_info_ref(&mut layout_tree, component);
+            res.push("    {".into());
+            res.extend(layout_tree.iter().filter_map(|layout| match layout {
+                LayoutTreeItem::GridLayout { var_creation_code, .. } => {
+                    Some(var_creation_code.clone())
+                }
+                LayoutTreeItem::PathLayout(_) => None,
+            }));
+            // Note: This "logic" is manually inlined from LayoutInfo::merge in layout.rs.
+            res.push(format!(
+                r#"        auto root_layout_info = {};
+        layout_info = sixtyfps::LayoutInfo{{
+            std::max(layout_info.min_width, root_layout_info.min_width),
+            std::min(layout_info.max_width, root_layout_info.max_width),
+            std::max(layout_info.min_height, root_layout_info.min_height),
+            std::min(layout_info.max_height, root_layout_info.max_height),
+        }};"#,
+                root_layout_info
+            ));
+            res.push("    }".into());
+        });
+    res.push("return layout_info;".into());
+
+    res
+}
+
 fn compute_layout(
     component: &Rc<Component>,
     repeater_layout_code: &mut Vec<String>,
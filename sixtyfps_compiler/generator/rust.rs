@@ -52,6 +52,7 @@ fn rust_type(
             message: format!("Cannot map property type {} to Rust", ty),
             span: span.clone(),
             level: Level::Error,
+            category: Default::default(),
         }),
     }
 }
@@ -130,10 +131,11 @@ fn generate_component(
     let mut declared_property_types = vec![];
     let mut declared_signals = vec![];
     let mut declared_signals_types = vec![];
+    let mut declared_signals_return_types = vec![];
     let mut property_and_signal_accessors: Vec<TokenStream> = vec![];
     for (prop_name, property_decl) in component.root_element.borrow().property_declarations.iter() {
         let prop_ident = format_ident!("{}", prop_name);
-        if let Type::Signal { args } = &property_decl.property_type {
+        if let Type::Signal { args, return_type } = &property_decl.property_type {
             declared_signals.push(prop_ident.clone());
             let signal_args = args
                 .iter()
@@ -143,6 +145,13 @@ fn generate_component(
                     diag.push_internal_error(err.into());
                     vec![]
                 });
+            let return_type = return_type.as_ref().map(|rt| {
+                rust_type(rt, &property_decl.type_node.span()).unwrap_or_else(|err| {
+                    diag.push_internal_error(err.into());
+                    quote!(())
+                })
+            });
+            let return_annotation = return_type.as_ref().map(|rt| quote!(-> #rt));
 
             if property_decl.expose_in_public_api {
                 let args_name =
@@ -151,7 +160,7 @@ fn generate_component(
                 property_and_signal_accessors.push(
                     quote!(
                         #[allow(dead_code)]
-                        pub fn #emitter_ident(self: ::core::pin::Pin<&Self>, #(#args_name : #signal_args,)*) {
+                        pub fn #emitter_ident(self: ::core::pin::Pin<&Self>, #(#args_name : #signal_args,)*) #return_annotation {
                             Self::FIELD_OFFSETS.#prop_ident.apply_pin(self).emit(&(#(#args_name,)*))
                         }
                     )
@@ -162,7 +171,7 @@ fn generate_component(
                 property_and_signal_accessors.push(
                     quote!(
                         #[allow(dead_code)]
-                        pub fn #on_ident(self: ::core::pin::Pin<&Self>, f: impl Fn(#(#signal_args),*) + 'static) {
+                        pub fn #on_ident(self: ::core::pin::Pin<&Self>, f: impl Fn(#(#signal_args),*) #return_annotation + 'static) {
                             #[allow(unused)]
                             Self::FIELD_OFFSETS.#prop_ident.apply_pin(self).set_handler(
                                 // FIXME: why do i need to clone here?
@@ -174,6 +183,7 @@ fn generate_component(
                 );
             }
             declared_signals_types.push(signal_args);
+            declared_signals_return_types.push(return_type.unwrap_or_else(|| quote!(())));
         } else {
             let rust_property_type =
                 rust_type(&property_decl.property_type, &property_decl.type_node.span())
@@ -365,15 +375,22 @@ fn generate_component(
             for (k, binding_expression) in &item.bindings {
                 let rust_property =
                     access_member(item_rc, k, component, quote!(self_pinned.as_ref()), false);
-                if matches!(item.lookup_property(k.as_str()), Type::Signal{..}) {
+                if let Type::Signal { return_type, .. } = item.lookup_property(k.as_str()) {
                     let tokens_for_expression = compile_expression(binding_expression, &component);
+                    let tokens_for_expression = match return_type.as_deref() {
+                        Some(ty) => {
+                            let cast = value_cast(ty);
+                            quote!((#tokens_for_expression) #cast)
+                        }
+                        None => quote!(#tokens_for_expression;),
+                    };
                     init.push(quote!(
                         #rust_property.set_handler({
                             let self_weak = sixtyfps::re_exports::PinWeak::downgrade(self_pinned.clone());
                             move |args| {
                                 let self_pinned = self_weak.upgrade().unwrap();
                                 let _self = self_pinned.as_ref();
-                                #tokens_for_expression;
+                                #tokens_for_expression
                             }
                         });
                     ));
@@ -534,7 +551,7 @@ fn generate_component(
         #visibility struct #component_id {
             #(#item_names : sixtyfps::re_exports::#item_types,)*
             #(#declared_property_vars : sixtyfps::re_exports::Property<#declared_property_types>,)*
-            #(#declared_signals : sixtyfps::re_exports::Signal<(#(#declared_signals_types,)*)>,)*
+            #(#declared_signals : sixtyfps::re_exports::Signal<(#(#declared_signals_types,)*), #declared_signals_return_types>,)*
             #(#repeated_element_names : sixtyfps::re_exports::Repeater<#repeated_element_components>,)*
             self_weak: sixtyfps::re_exports::OnceCell<sixtyfps::re_exports::PinWeak<#component_id>>,
             #(parent : sixtyfps::re_exports::PinWeak<#parent_component_type>,)*
@@ -830,9 +847,29 @@ fn window_ref_expression(component: &Rc<Component>) -> TokenStream {
     quote!(#component_rust.as_ref().window)
 }
 
+/// The suffix to apply to an expression to get a value of type `ty`, as the number expressions
+/// are computed with f64, and the other values may be borrowed
+fn value_cast(ty: &Type) -> TokenStream {
+    match ty {
+        Type::Bool => quote!(as bool),
+        Type::Int32 => quote!(as i32),
+        Type::Float32 | Type::Length | Type::LogicalLength => quote!(as f32),
+        Type::Duration => quote!(as i64),
+        _ => quote!(.clone()),
+    }
+}
+
 fn compile_expression(e: &Expression, component: &Rc<Component>) -> TokenStream {
     match e {
         Expression::StringLiteral(s) => quote!(sixtyfps::re_exports::SharedString::from(#s)),
+        Expression::StringInterpolation { parts } => {
+            let parts = parts.iter().map(|p| compile_expression(p, component));
+            quote!({
+                let mut s = String::new();
+                #(s.push_str(&#parts);)*
+                sixtyfps::re_exports::SharedString::from(s.as_str())
+            })
+        }
         Expression::NumberLiteral(n, unit) => {
             let n = unit.normalize(*n);
             quote!(#n)
@@ -847,9 +884,10 @@ fn compile_expression(e: &Expression, component: &Rc<Component>) -> TokenStream
                 (Type::Float32, Type::Model) | (Type::Int32, Type::Model) => {
                     quote!(sixtyfps::re_exports::ModelHandle::Some(std::rc::Rc::<usize>::new(#f as usize)))
                 }
-                (Type::Float32, Type::Color) => {
+                (Type::Float32, Type::Color) | (Type::Int32, Type::Color) => {
                     quote!(sixtyfps::re_exports::Color::from_argb_encoded(#f as u32))
                 }
+                (Type::Color, Type::Int32) => quote!((#f).as_argb_encoded() as i32),
                 (Type::Object(ref o), Type::Component(c)) => {
                     let fields = o.iter().enumerate().map(|(index, (name, _))| {
                         let index = proc_macro2::Literal::usize_unsuffixed(index);
@@ -878,6 +916,46 @@ fn compile_expression(e: &Expression, component: &Rc<Component>) -> TokenStream
                 quote!(#window_ref.scale_factor)
             }
             BuiltinFunction::Debug => quote!((|x| println!("{:?}", x))),
+            BuiltinFunction::StringToUppercase => quote!(
+                (|x: sixtyfps::re_exports::SharedString| sixtyfps::re_exports::SharedString::from(
+                    x.to_uppercase().as_str()
+                ))
+            ),
+            BuiltinFunction::ArrayLength => quote!(
+                ({
+                    fn array_length<M: sixtyfps::re_exports::Model + ?Sized>(
+                        model: Option<std::rc::Rc<M>>,
+                    ) -> i32 {
+                        model.map_or(0, |m| m.row_count() as i32)
+                    }
+                    array_length
+                })
+            ),
+            BuiltinFunction::StringToLowercase => quote!(
+                (|x: sixtyfps::re_exports::SharedString| sixtyfps::re_exports::SharedString::from(
+                    x.to_lowercase().as_str()
+                ))
+            ),
+            BuiltinFunction::Clamp => quote!(
+                ({
+                    fn clamp(
+                        value: impl Into<f64>,
+                        min: impl Into<f64>,
+                        max: impl Into<f64>,
+                    ) -> f64 {
+                        let (value, min, max) = (value.into(), min.into(), max.into());
+                        if value.is_nan() || value < min || min > max {
+                            min
+                        } else {
+                            value.min(max)
+                        }
+                    }
+                    clamp
+                })
+            ),
+            BuiltinFunction::AnimationTick => {
+                quote!((|| sixtyfps::re_exports::animation_tick() as i64))
+            }
         },
         Expression::RepeaterIndexReference { element } => {
             let access = access_member(
@@ -934,13 +1012,8 @@ fn compile_expression(e: &Expression, component: &Rc<Component>) -> TokenStream
         Expression::FunctionCall { function, arguments } => {
             let f = compile_expression(function, &component);
             let a = arguments.iter().map(|a| compile_expression(a, &component));
-            if let Type::Signal { args } = function.ty() {
-                let cast = args.iter().map(|ty| match ty {
-                    Type::Bool => quote!(as bool),
-                    Type::Int32 => quote!(as i32),
-                    Type::Float32 => quote!(as f32),
-                    _ => quote!(.clone()),
-                });
+            if let Type::Signal { args, .. } = function.ty() {
+                let cast = args.iter().map(value_cast);
                 quote! { #f.emit(&(#((#a)#cast,)*).into())}
             } else {
                 quote! { #f(#(#a.clone()),*)}
@@ -1173,6 +1246,11 @@ fn collect_layouts_recursively<'a, 'b>(
                             layout_info });
                     }
 
+                    let collapsed = cell
+                        .collapsed
+                        .as_ref()
+                        .map_or_else(|| quote!(false), |e| compile_expression(e, component));
+
                     quote!(GridLayoutCellData {
                         x: #x,
                         y: #y,
@@ -1183,6 +1261,7 @@ fn collect_layouts_recursively<'a, 'b>(
                         colspan: #colspan,
                         rowspan: #rowspan,
                         constraint: #layout_info,
+                        collapsed: #collapsed,
                     })
                 })
                 .collect();
@@ -1383,9 +1462,39 @@ fn compute_layout(component: &Rc<Component>, repeated_element_names: &[Ident]) -
 
     let window_ref = window_ref_expression(component);
 
+    let root_id = format_ident!("{}", component.root_element.borrow().id);
+    let mut layout_info = vec![quote!(
+        let layout_info = Self::FIELD_OFFSETS.#root_id.apply_pin(self).layouting_info(&window);
+    )];
+    // Only the layouts that fill the root element constrain the size of the component
+    component
+        .layout_constraints
+        .borrow()
+        .iter()
+        .filter(|layout| {
+            matches!(layout, Layout::GridLayout(_))
+                && layout.rect().fills_element(&component.root_element)
+        })
+        .for_each(|layout| {
+            let mut layout_tree = Vec::new();
+            let root_layout_info = layout.get_layout_info_ref(&mut layout_tree, component);
+            layout_info.extend(layout_tree.iter().filter_map(|layout| match layout {
+                LayoutTreeItem::GridLayout { var_creation_code, .. } => {
+                    Some(var_creation_code.clone())
+                }
+                LayoutTreeItem::PathLayout(_) => None,
+            }));
+            layout_info.push(quote!(let layout_info = #root_layout_info.merge(&layout_info);));
+        });
+
     quote! {
         fn layout_info(self: ::core::pin::Pin<&Self>) -> sixtyfps::re_exports::LayoutInfo {
-            todo!("Implement in rust.rs")
+            #![allow(unused)]
+            use sixtyfps::re_exports::*;
+            let _self = self;
+            let window = #window_ref.clone();
+            #(#layout_info)*
+            layout_info
         }
         fn compute_layout(self: ::core::pin::Pin<&Self>) {
             #![allow(unused)]
@@ -1504,3 +1613,26 @@ fn compile_path(path: &Path, component: &Rc<Component>) -> TokenStream {
         }
     }
 }
+
+#[test]
+fn generated_code_parses() {
+    let source = r#"
+Hello := Rectangle {
+    property<int> count: 42;
+    property<string> label: count > 3 && count != 10 ? "many" : count;
+    signal clicked(int);
+    color: count > 3 ? red : blue;
+    Text { text: label; }
+    for x in [1, 2, 3] : Rectangle { width: x * 10px; }
+}
+"#;
+    let (syntax_node, diagnostics) = crate::parser::parse(source.into(), None);
+    let (doc, mut diagnostics) =
+        crate::compile_syntax_node(syntax_node, diagnostics, &Default::default());
+    assert!(!diagnostics.has_error(), "{:?}", diagnostics.to_string_vec());
+    let code = generate(&doc, &mut diagnostics).expect("the code generation failed");
+    assert!(!diagnostics.has_error(), "{:?}", diagnostics.to_string_vec());
+    if let Err(err) = syn::parse2::<syn::File>(code.clone()) {
+        panic!("the generated code does not parse: {}\n{}", err, code);
+    }
+}
@@ -106,6 +106,17 @@ impl LayoutRect {
             _ => None,
         }
     }
+
+    /// Returns true if the width of the layout is the width of the `element`, which is the case
+    /// for a layout that fills the element.
+    pub fn fills_element(&self, element: &ElementRc) -> bool {
+        match self.width_reference.as_ref() {
+            Expression::PropertyReference(nr) => {
+                nr.element.upgrade().map_or(false, |e| Rc::ptr_eq(&e, element))
+            }
+            _ => false,
+        }
+    }
 }
 
 impl ExpressionFieldsVisitor for LayoutRect {
@@ -129,6 +140,9 @@ pub struct GridLayoutElement {
     pub maximum_width: Option<Box<Expression>>,
     pub minimum_height: Option<Box<Expression>>,
     pub maximum_height: Option<Box<Expression>>,
+    /// A boolean expression that is true when the element is left out of the layout, because it is
+    /// not visible and has `collapse_when_hidden` set. None if the element is never left out.
+    pub collapsed: Option<Box<Expression>>,
 }
 
 impl GridLayoutElement {
@@ -199,6 +213,7 @@ impl ExpressionFieldsVisitor for GridLayout {
             cell.minimum_width.as_mut().map(|e| visitor(&mut *e));
             cell.maximum_height.as_mut().map(|e| visitor(&mut *e));
             cell.minimum_height.as_mut().map(|e| visitor(&mut *e));
+            cell.collapsed.as_mut().map(|e| visitor(&mut *e));
         }
         self.spacing.as_mut().map(|e| visitor(&mut *e));
         self.padding.visit_expressions(visitor);
@@ -90,9 +90,33 @@ pub fn lex_string(text: &str) -> usize {
     if !text.starts_with('"') {
         return 0;
     }
-    let end = text[1..].find('"').unwrap_or(0) + 2;
-    assert!(!text[..end].contains("\\"), "escape code not yet supported");
-    end
+    lex_string_content(text)
+}
+
+/// Lex the rest of a string literal, after its first character, which is either the opening
+/// quote or the `}` closing an interpolated expression.
+/// The token ends after the closing quote, or after the `\{` that starts an interpolated
+/// expression. Returns 0 if the string is not terminated.
+fn lex_string_content(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    while let Some((pos, c)) = chars.next() {
+        match c {
+            '"' => return pos + 1,
+            '\\' => match chars.next() {
+                Some((pos, '{')) => return pos + 1,
+                Some(_) => {}
+                None => return 0,
+            },
+            _ => {}
+        }
+    }
+    0
+}
+
+/// Returns true if this string literal token is followed by an interpolated expression,
+/// i.e. it ends with `\{`
+pub fn string_literal_continues(text: &str) -> bool {
+    text.ends_with("\\{")
 }
 
 pub fn lex_number(text: &str) -> usize {
@@ -157,8 +181,38 @@ pub fn lex_identifier(text: &str) -> usize {
 pub fn lex(mut source: &str) -> Vec<crate::parser::Token> {
     let mut result = vec![];
     let mut offset = 0;
+    // For each string interpolation we are in, the number of braces opened within the expression.
+    // The `}` that closes the interpolation continues the string literal.
+    let mut interpolations: Vec<usize> = vec![];
     while !source.is_empty() {
-        if let Some((len, kind)) = crate::parser::lex_next_token(source) {
+        let next_token = if interpolations.last() == Some(&0) && source.starts_with('}') {
+            interpolations.pop();
+            match lex_string_content(source) {
+                0 => None,
+                len => Some((len, crate::parser::SyntaxKind::StringLiteral)),
+            }
+        } else {
+            crate::parser::lex_next_token(source)
+        };
+        if let Some((len, kind)) = next_token {
+            match kind {
+                crate::parser::SyntaxKind::StringLiteral => {
+                    if string_literal_continues(&source[..len]) {
+                        interpolations.push(0);
+                    }
+                }
+                crate::parser::SyntaxKind::LBrace => {
+                    if let Some(depth) = interpolations.last_mut() {
+                        *depth += 1;
+                    }
+                }
+                crate::parser::SyntaxKind::RBrace => {
+                    if let Some(depth) = interpolations.last_mut() {
+                        *depth -= 1;
+                    }
+                }
+                _ => {}
+            }
             result.push(crate::parser::Token {
                 kind,
                 text: source[..len].into(),
@@ -222,6 +276,26 @@ fn basic_lexer_test() {
             (crate::parser::SyntaxKind::Identifier, "c"),
         ],
     );
+    compare(
+        r#""a\"b\\" "x\{ {y:"\{z}"}.y }!""#,
+        &[
+            (crate::parser::SyntaxKind::StringLiteral, r#""a\"b\\""#),
+            (crate::parser::SyntaxKind::Whitespace, " "),
+            (crate::parser::SyntaxKind::StringLiteral, r#""x\{"#),
+            (crate::parser::SyntaxKind::Whitespace, " "),
+            (crate::parser::SyntaxKind::LBrace, "{"),
+            (crate::parser::SyntaxKind::Identifier, "y"),
+            (crate::parser::SyntaxKind::Colon, ":"),
+            (crate::parser::SyntaxKind::StringLiteral, r#""\{"#),
+            (crate::parser::SyntaxKind::Identifier, "z"),
+            (crate::parser::SyntaxKind::StringLiteral, r#"}""#),
+            (crate::parser::SyntaxKind::RBrace, "}"),
+            (crate::parser::SyntaxKind::Dot, "."),
+            (crate::parser::SyntaxKind::Identifier, "y"),
+            (crate::parser::SyntaxKind::Whitespace, " "),
+            (crate::parser::SyntaxKind::StringLiteral, r#"}!""#),
+        ],
+    );
     compare(
         r#"/*/**/*//**/*"#,
         &[
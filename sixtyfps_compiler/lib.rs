@@ -44,15 +44,23 @@ mod passes {
         );
     }
 
+    pub mod check_duplicate_ids;
+    pub mod check_unused_animations;
     pub mod collect_resources;
     pub mod compile_paths;
     pub mod deduplicate_property_read;
+    pub mod helper_items;
     pub mod inlining;
+    pub mod lower_clip;
     pub mod lower_layout;
+    pub mod lower_opacity;
     pub mod lower_states;
+    pub mod lower_visible;
     pub mod materialize_fake_properties;
+    pub mod merge_animations;
     pub mod move_declarations;
     pub mod remove_aliases;
+    pub mod remove_constant_conditions;
     pub mod repeater_component;
     pub mod resolve_native_classes;
     pub mod resolving;
@@ -69,9 +77,47 @@ pub struct CompilerConfiguration<'a> {
     pub include_paths: &'a [std::path::PathBuf],
     /// the name of the style. (eg: "native")
     pub style: Option<&'a str>,
+    /// The features that are enabled. `feature("name")` is true in the .60 files for these names,
+    /// and false for any other name.
+    pub features: &'a [String],
 }
 
 pub fn compile_syntax_node(
+    doc_node: parser::SyntaxNodeWithSourceFile,
+    diagnostics: diagnostics::FileDiagnostics,
+    compiler_config: &CompilerConfiguration,
+) -> (object_tree::Document, diagnostics::BuildDiagnostics) {
+    let (doc, mut build_diagnostics) = load_document(doc_node, diagnostics, compiler_config);
+
+    if !build_diagnostics.has_error() {
+        // FIXME: ideally we would be able to run more passes, but currently we panic because invariant are not met.
+        run_passes(&doc, &mut build_diagnostics, compiler_config);
+    }
+
+    (doc, build_diagnostics)
+}
+
+/// Type-check the document without preparing it for the generators.
+///
+/// This reports the same diagnostics as [`compile_syntax_node`], but is cheaper as it stops
+/// after the last pass that can report diagnostics. This is meant for tools like editors that
+/// check the document on each keystroke.
+pub fn check_syntax_node(
+    doc_node: parser::SyntaxNodeWithSourceFile,
+    diagnostics: diagnostics::FileDiagnostics,
+    compiler_config: &CompilerConfiguration,
+) -> diagnostics::BuildDiagnostics {
+    let (doc, mut build_diagnostics) = load_document(doc_node, diagnostics, compiler_config);
+
+    if !build_diagnostics.has_error() {
+        run_checking_passes(&doc, &mut build_diagnostics, compiler_config);
+    }
+
+    build_diagnostics
+}
+
+/// Load the dependencies and build the object tree of the document
+fn load_document(
     doc_node: parser::SyntaxNodeWithSourceFile,
     mut diagnostics: diagnostics::FileDiagnostics,
     compiler_config: &CompilerConfiguration,
@@ -105,11 +151,6 @@ pub fn compile_syntax_node(
 
     build_diagnostics.add(diagnostics);
 
-    if !build_diagnostics.has_error() {
-        // FIXME: ideally we would be able to run more passes, but currently we panic because invariant are not met.
-        run_passes(&doc, &mut build_diagnostics, compiler_config);
-    }
-
     (doc, build_diagnostics)
 }
 
@@ -118,20 +159,36 @@ pub fn run_passes(
     diag: &mut diagnostics::BuildDiagnostics,
     compiler_config: &CompilerConfiguration,
 ) {
-    passes::resolving::resolve_expressions(doc, diag);
+    run_checking_passes(doc, diag, compiler_config);
+    passes::resolve_native_classes::resolve_native_classes(&doc.root_component);
+}
+
+/// Run the passes up to the last one that can report diagnostics
+fn run_checking_passes(
+    doc: &object_tree::Document,
+    diag: &mut diagnostics::BuildDiagnostics,
+    compiler_config: &CompilerConfiguration,
+) {
+    passes::resolving::resolve_expressions(doc, compiler_config.features, diag);
+    passes::check_duplicate_ids::check_duplicate_ids(doc, diag);
+    passes::check_unused_animations::check_unused_animations(doc, diag);
+    passes::remove_constant_conditions::remove_constant_conditions(&doc.root_component);
     passes::inlining::inline(doc);
     passes::compile_paths::compile_paths(&doc.root_component, &doc.local_registry, diag);
     passes::unique_id::assign_unique_id(&doc.root_component);
     passes::materialize_fake_properties::materialize_fake_properties(&doc.root_component);
     passes::collect_resources::collect_resources(&doc.root_component);
     doc.root_component.embed_file_resources.set(compiler_config.embed_resources);
+    passes::merge_animations::merge_animations(&doc.root_component, diag);
     passes::lower_states::lower_states(&doc.root_component, diag);
     passes::repeater_component::process_repeater_components(&doc.root_component);
     passes::lower_layout::lower_layouts(&doc.root_component, diag);
+    passes::lower_visible::lower_visible(&doc.root_component);
+    passes::lower_opacity::lower_opacity(&doc.root_component);
+    passes::lower_clip::lower_clip(&doc.root_component);
     passes::deduplicate_property_read::deduplicate_property_read(&doc.root_component);
     passes::move_declarations::move_declarations(&doc.root_component, diag);
     passes::remove_aliases::remove_aliases(&doc.root_component, diag);
-    passes::resolve_native_classes::resolve_native_classes(&doc.root_component);
 }
 
 mod library {
@@ -122,6 +122,26 @@ impl Component {
             child_insertion_point: RefCell::new(child_insertion_point),
             ..Default::default()
         };
+        if c.child_insertion_point.borrow().is_some() {
+            // Only the components with a $children placeholder have this property, which is
+            // documented in the language reference. The inlining pass sets the binding to the
+            // number of children that were placed in the placeholder
+            c.root_element
+                .borrow_mut()
+                .property_declarations
+                .entry("children_count".into())
+                .or_insert_with(|| PropertyDeclaration {
+                    property_type: Type::Int32,
+                    ..PropertyDeclaration::default()
+                });
+        }
+        recurse_elem(&c.root_element, &false, &mut |e, in_repeater| {
+            let in_repeater = *in_repeater || e.borrow().repeated.is_some();
+            // The repeated elements become their own component, so their states cannot change `root`
+            let root = if in_repeater { None } else { Some(&c.root_element) };
+            Element::parse_states_and_transitions(e, root, diag, tr);
+            in_repeater
+        });
         let c = Rc::new(c);
         let weak = Rc::downgrade(&c);
         recurse_elem(&c.root_element, &(), &mut |e, _| {
@@ -315,23 +335,22 @@ impl Element {
             );
 
             if let Some(csn) = prop_decl.BindingExpression() {
-                if r.bindings
-                    .insert(prop_name.clone(), ExpressionSpanned::new_uncompiled(csn.into()))
-                    .is_some()
-                {
-                    diag.push_error("Duplicated property binding".into(), &prop_name_token);
-                }
+                r.bindings.insert(prop_name.clone(), ExpressionSpanned::new_uncompiled(csn.into()));
             }
             if let Some(csn) = prop_decl.TwoWayBinding() {
-                if r.bindings
-                    .insert(prop_name, ExpressionSpanned::new_uncompiled(csn.into()))
-                    .is_some()
-                {
-                    diag.push_error("Duplicated property binding".into(), &prop_name_token);
-                }
+                r.bindings.insert(prop_name, ExpressionSpanned::new_uncompiled(csn.into()));
             }
         }
 
+        report_duplicated_bindings(
+            node.PropertyDeclaration()
+                .filter(|d| d.BindingExpression().is_some() || d.TwoWayBinding().is_some())
+                .filter_map(|d| d.DeclaredIdentifier().child_token(SyntaxKind::Identifier))
+                .chain(node.Binding().filter_map(|b| b.child_token(SyntaxKind::Identifier)))
+                .chain(node.TwoWayBinding().filter_map(|b| b.child_token(SyntaxKind::Identifier))),
+            diag,
+        );
+
         r.parse_bindings(
             &base,
             node.Binding().filter_map(|b| {
@@ -360,10 +379,12 @@ impl Element {
                 sig_decl.DeclaredIdentifier().child_token(SyntaxKind::Identifier).unwrap();
             let name = name_token.text().to_string();
             let args = sig_decl.Type().map(|node_ty| type_from_node(node_ty, diag, tr)).collect();
+            let return_type =
+                sig_decl.ReturnType().map(|ret| Box::new(type_from_node(ret.Type(), diag, tr)));
             r.property_declarations.insert(
                 name,
                 PropertyDeclaration {
-                    property_type: Type::Signal { args },
+                    property_type: Type::Signal { return_type, args },
                     type_node: Some(sig_decl.into()),
                     ..Default::default()
                 },
@@ -377,7 +398,7 @@ impl Element {
             };
             let name = name_token.text().to_string();
             let prop_type = r.lookup_property(&name);
-            if let Type::Signal { args } = prop_type {
+            if let Type::Signal { args, .. } = prop_type {
                 let num_arg = con_node.DeclaredIdentifier().count();
                 if num_arg > args.len() {
                     diag.push_error(
@@ -499,18 +520,44 @@ impl Element {
             }
         }
 
+        r
+    }
+
+    /// Create the states and the transitions of the element `r` from its syntax node.
+    /// This is done once all the elements of the component were created, so that they can
+    /// refer to the `root` element of the component, if given.
+    fn parse_states_and_transitions(
+        r: &ElementRc,
+        root: Option<&ElementRc>,
+        diag: &mut FileDiagnostics,
+        tr: &TypeRegister,
+    ) {
+        let node = match r.borrow().node.clone() {
+            Some(node) => node,
+            None => return,
+        };
+        if node.States().next().is_none() && node.Transitions().next().is_none() {
+            return;
+        }
+        // The ids of the elements that the states and transitions can refer to
+        let mut ids = element_ids(r);
+        if let Some(root) = root {
+            ids.entry("root".into()).or_insert_with(|| root.clone());
+        }
+
         for state in node.States().flat_map(|s| s.State()) {
             let s = State {
                 id: state
                     .DeclaredIdentifier()
                     .child_text(SyntaxKind::Identifier)
                     .unwrap_or_default(),
+                id_node: Some(state.DeclaredIdentifier().into()),
                 condition: state.Expression().map(|e| Expression::Uncompiled(e.into())),
                 property_changes: state
                     .StatePropertyChange()
                     .map(|s| {
                         let (ne, _) =
-                            lookup_property_from_qualified_name(s.QualifiedName(), &r, diag);
+                            lookup_property_from_qualified_name(s.QualifiedName(), r, &ids, diag);
                         (ne, Expression::Uncompiled(s.BindingExpression().into()))
                     })
                     .collect(),
@@ -522,8 +569,9 @@ impl Element {
             if let Some(star) = trs.child_token(SyntaxKind::Star) {
                 diag.push_error("TODO: catch-all not yet implemented".into(), &star);
             };
+            let direction = trs.child_text(SyntaxKind::Identifier).unwrap_or_default();
             let trans = Transition {
-                is_out: trs.child_text(SyntaxKind::Identifier).unwrap_or_default() == "out",
+                is_out: direction == "out",
                 state_id: trs
                     .DeclaredIdentifier()
                     .child_text(SyntaxKind::Identifier)
@@ -533,7 +581,7 @@ impl Element {
                     .flat_map(|pa| pa.QualifiedName().map(move |qn| (pa.clone(), qn)))
                     .filter_map(|(pa, qn)| {
                         let (ne, prop_type) =
-                            lookup_property_from_qualified_name(qn.clone(), &r, diag);
+                            lookup_property_from_qualified_name(qn.clone(), r, &ids, diag);
                         if prop_type == Type::Invalid {
                             debug_assert!(diag.has_error()); // Error should have been reported already
                             return None;
@@ -543,10 +591,32 @@ impl Element {
                     })
                     .collect(),
             };
+            // `in-out`: the same animations are used when entering and when leaving the state
+            let out_trans = if direction == "in" {
+                Some(Transition {
+                    is_out: true,
+                    state_id: trans.state_id.clone(),
+                    property_animations: trans
+                        .property_animations
+                        .iter()
+                        .map(|(ne, anim)| {
+                            let anim = anim.borrow();
+                            let copy = Element {
+                                id: anim.id.clone(),
+                                base_type: anim.base_type.clone(),
+                                bindings: anim.bindings.clone(),
+                                ..Default::default()
+                            };
+                            (ne.clone(), Rc::new(RefCell::new(copy)))
+                        })
+                        .collect(),
+                })
+            } else {
+                None
+            };
             r.borrow_mut().transitions.push(trans);
+            r.borrow_mut().transitions.extend(out_trans);
         }
-
-        r
     }
 
     fn from_repeated_node(
@@ -568,9 +638,17 @@ impl Element {
                 .unwrap_or_default(),
             is_conditional_element: false,
         };
+        let se = node.SubElement();
+        let id = se.child_text(SyntaxKind::Identifier).unwrap_or_default();
+        if matches!(id.as_ref(), "parent" | "self" | "root") {
+            diag.push_error(
+                format!("'{}' is a reserved id", id),
+                &se.child_token(SyntaxKind::Identifier).unwrap(),
+            )
+        }
         let e = Element::from_node(
-            node.Element(),
-            String::new(),
+            se.Element(),
+            id,
             parent_type,
             component_child_insertion_point,
             diag,
@@ -642,13 +720,40 @@ impl Element {
                     &name_token,
                 );
             }
-            if self.bindings.insert(name, ExpressionSpanned::new_uncompiled(b)).is_some() {
-                diag.push_error("Duplicated property binding".into(), &name_token);
-            }
+            self.bindings.insert(name, ExpressionSpanned::new_uncompiled(b));
         }
     }
 }
 
+/// Report an error on each of the bindings of a property that is bound more than once.
+///
+/// `binding_names` are the tokens naming the bound properties, whether the binding comes with the
+/// property declaration, is a plain binding, or is a two-way binding.
+fn report_duplicated_bindings(
+    binding_names: impl Iterator<Item = crate::parser::SyntaxTokenWithSourceFile>,
+    diag: &mut FileDiagnostics,
+) {
+    let mut by_name = HashMap::<String, Vec<_>>::new();
+    for token in binding_names {
+        by_name.entry(token.text().to_string()).or_default().push(token);
+    }
+    let mut duplicated = by_name
+        .into_iter()
+        .filter(|(_, tokens)| tokens.len() > 1)
+        .flat_map(|(name, tokens)| {
+            let count = tokens.len();
+            tokens.into_iter().map(move |t| (name.clone(), count, t))
+        })
+        .collect::<Vec<_>>();
+    duplicated.sort_by_key(|(_, _, token)| token.span().offset);
+    for (name, count, token) in duplicated {
+        diag.push_error(
+            format!("Duplicated property binding: '{}' is bound {} times", name, count),
+            &token,
+        );
+    }
+}
+
 fn type_from_node(node: syntax_nodes::Type, diag: &mut FileDiagnostics, tr: &TypeRegister) -> Type {
     if let Some(qualified_type_node) = node.QualifiedName() {
         let qualified_type = QualifiedTypeName::from_node(qualified_type_node.clone());
@@ -708,6 +813,10 @@ fn animation_element_from_node(
             }),
             diag,
         );
+        report_duplicated_bindings(
+            anim.Binding().filter_map(|b| b.child_token(SyntaxKind::Identifier)),
+            diag,
+        );
         Some(Rc::new(RefCell::new(anim_element)))
     }
 }
@@ -739,6 +848,7 @@ impl std::fmt::Display for QualifiedTypeName {
 fn lookup_property_from_qualified_name(
     node: syntax_nodes::QualifiedName,
     r: &Rc<RefCell<Element>>,
+    ids: &HashMap<String, ElementRc>,
     diag: &mut FileDiagnostics,
 ) -> (NamedReference, Type) {
     let qualname = QualifiedTypeName::from_node(node.clone());
@@ -751,12 +861,20 @@ fn lookup_property_from_qualified_name(
             (NamedReference { element: Rc::downgrade(&r), name: prop_name.clone() }, ty)
         }
         [elem_id, prop_name] => {
-            let (element, ty) = if let Some(element) = find_element_by_id(&r, elem_id.as_ref()) {
+            let element = if elem_id == "self" { Some(r) } else { ids.get(elem_id) };
+            let (element, ty) = if let Some(element) = element {
                 let ty = element.borrow().lookup_property(prop_name.as_ref());
                 if !ty.is_property_type() {
                     diag.push_error(format!("'{}' not found in '{}'", prop_name, elem_id), &node);
                 }
-                (Rc::downgrade(&element), ty)
+                (Rc::downgrade(element), ty)
+            } else if elem_id == "root" {
+                diag.push_error(
+                    "'root' cannot be referred to from the states and transitions of a repeated element"
+                        .into(),
+                    &node,
+                );
+                (Weak::new(), Type::Invalid)
             } else {
                 diag.push_error(format!("'{}' is not a valid element id", elem_id), &node);
                 (Weak::new(), Type::Invalid)
@@ -770,21 +888,26 @@ fn lookup_property_from_qualified_name(
     }
 }
 
-/// FIXME: this is duplicated the resolving pass. Also, we should use a hash table
-fn find_element_by_id(e: &ElementRc, name: &str) -> Option<ElementRc> {
-    if e.borrow().id == name {
-        return Some(e.clone());
-    }
-    for x in &e.borrow().children {
-        if x.borrow().repeated.is_some() {
-            continue;
+/// Map the ids of the elements that can be looked up from within `root` to these elements.
+///
+/// This is `root` and its children recursively, except the repeated children and their own
+/// children, which have their own scope. If an id is used more than once, the first element
+/// in the tree wins.
+pub fn element_ids(root: &ElementRc) -> HashMap<String, ElementRc> {
+    fn collect(e: &ElementRc, ids: &mut HashMap<String, ElementRc>) {
+        let id = e.borrow().id.clone();
+        if !id.is_empty() {
+            ids.entry(id).or_insert_with(|| e.clone());
         }
-        if let Some(x) = find_element_by_id(x, name) {
-            return Some(x);
+        for x in &e.borrow().children {
+            if x.borrow().repeated.is_none() {
+                collect(x, ids);
+            }
         }
     }
-
-    None
+    let mut ids = HashMap::new();
+    collect(root, &mut ids);
+    ids
 }
 
 /// Call the visitor for each children of the element recursively, starting with the element itself
@@ -846,15 +969,23 @@ pub fn visit_element_expressions(
     }
     elem.borrow_mut().states = states;
 
-    let property_animations = std::mem::take(&mut elem.borrow_mut().property_animations);
-    for anim_elem in property_animations.values() {
+    let mut visit_animation = |anim_elem: &ElementRc| {
         let mut bindings = std::mem::take(&mut anim_elem.borrow_mut().bindings);
         for (name, expr) in &mut bindings {
             vis(expr, &|| anim_elem.borrow().lookup_property(name));
         }
         anim_elem.borrow_mut().bindings = bindings;
+    };
+    let property_animations = std::mem::take(&mut elem.borrow_mut().property_animations);
+    for anim_elem in property_animations.values() {
+        visit_animation(anim_elem);
     }
     elem.borrow_mut().property_animations = property_animations;
+    let transitions = std::mem::take(&mut elem.borrow_mut().transitions);
+    for anim_elem in transitions.iter().flat_map(|t| t.property_animations.iter().map(|(_, a)| a)) {
+        visit_animation(anim_elem);
+    }
+    elem.borrow_mut().transitions = transitions;
 }
 
 pub fn visit_all_named_references(elem: &ElementRc, mut vis: impl FnMut(&mut NamedReference)) {
@@ -895,13 +1026,16 @@ pub fn visit_all_named_references(elem: &ElementRc, mut vis: impl FnMut(&mut Nam
 #[derive(Debug, Clone)]
 pub struct State {
     pub id: String,
+    /// The DeclaredIdentifier node of the state, used to report diagnostics
+    pub id_node: Option<SyntaxNodeWithSourceFile>,
     pub condition: Option<Expression>,
     pub property_changes: Vec<(NamedReference, Expression)>,
 }
 
 #[derive(Debug)]
 pub struct Transition {
-    /// false for 'to', true for 'out'
+    /// false for 'to', true for 'out'. An `in-out` transition is expanded into one transition
+    /// of each direction.
     pub is_out: bool,
     pub state_id: String,
     pub property_animations: Vec<(NamedReference, ElementRc)>,
@@ -239,6 +239,7 @@ declare_syntax! {
         ColorLiteral -> &crate::lexer::lex_color,
         Identifier -> &crate::lexer::lex_identifier,
         DoubleArrow -> "<=>",
+        Arrow -> "->",
         PlusEqual -> "+=",
         MinusEqual -> "-=",
         StarEqual -> "*=",
@@ -249,6 +250,7 @@ declare_syntax! {
         NotEqual -> "!=",
         ColonEqual -> ":=",
         FatArrow -> "=>",
+        DotDotDot -> "...",
         OrOr -> "||",
         AndAnd -> "&&",
         LBrace -> "{",
@@ -281,10 +283,12 @@ declare_syntax! {
         Element -> [ ?QualifiedName, *PropertyDeclaration, *Binding, *SignalConnection,
                      *SignalDeclaration, *SubElement, *RepeatedElement, *PropertyAnimation,
                      *TwoWayBinding, *States, *Transitions, ?ChildrenPlaceholder ],
-        RepeatedElement -> [ ?DeclaredIdentifier, ?RepeatedIndex, Expression , Element],
+        RepeatedElement -> [ ?DeclaredIdentifier, ?RepeatedIndex, Expression , SubElement],
         RepeatedIndex -> [],
         ConditionalElement -> [ Expression , Element],
-        SignalDeclaration -> [ DeclaredIdentifier, *Type ],
+        SignalDeclaration -> [ DeclaredIdentifier, *Type, ?ReturnType ],
+        /// `-> type` in a signal declaration
+        ReturnType -> [ Type ],
         SignalConnection -> [ *DeclaredIdentifier,  CodeBlock ],
         /// Declaration of a propery.
         PropertyDeclaration-> [ Type , DeclaredIdentifier, ?BindingExpression, ?TwoWayBinding ],
@@ -305,7 +309,7 @@ declare_syntax! {
         // FIXME: the test should test that as alternative rather than several of them (but it can also be a literal)
         Expression-> [ ?Expression, ?BangExpression, ?FunctionCallExpression, ?SelfAssignment,
                        ?ConditionalExpression, ?QualifiedName, ?BinaryExpression, ?Array, ?ObjectLiteral,
-                       ?UnaryOpExpression, ?CodeBlock],
+                       ?UnaryOpExpression, ?CodeBlock, ?StringTemplate],
         /// `foo!bar`
         BangExpression -> [Expression],
         /// expression()
@@ -318,10 +322,14 @@ declare_syntax! {
         BinaryExpression -> [2 Expression],
         /// `- expr`
         UnaryOpExpression -> [Expression],
+        /// `"Hello \{name}!"`: the string literal tokens with the interpolated expressions in between
+        StringTemplate -> [ *Expression ],
         /// `[ ... ]`
         Array -> [ *Expression ],
         /// `{ foo: bar }`
-        ObjectLiteral -> [ *ObjectMember ],
+        ObjectLiteral -> [ ?ObjectSpread, *ObjectMember ],
+        /// `...base` at the start of an ObjectLiteral
+        ObjectSpread -> [ Expression ],
         /// `foo: bar` inside an ObjectLiteral
         ObjectMember -> [ Expression ],
         /// `states: [...]`
@@ -332,7 +340,8 @@ declare_syntax! {
         StatePropertyChange -> [ QualifiedName, BindingExpression ],
         /// `transitions: [...]`
         Transitions -> [*Transition],
-        /// There is an idientfier "to" or "out", the DeclaredIdentifier is the state name
+        /// There is an idientfier "to" or "out", or the "in" "-" "out" tokens for `in-out`,
+        /// the DeclaredIdentifier is the state name
         Transition -> [DeclaredIdentifier, *PropertyAnimation],
         /// Export a set of declared components by name
         ExportsList -> [ *ExportSpecifier, *Component ],
@@ -342,7 +351,10 @@ declare_syntax! {
         ExportIdentifier -> [],
         ExportName -> [],
         /// import { foo, bar, baz } from "blah"; The import uri is stored as string literal.
-        ImportSpecifier -> [ ImportIdentifierList ],
+        /// or import * as Foo from "blah";
+        ImportSpecifier -> [ ?ImportIdentifierList, ?ImportNamespace ],
+        /// * as Foo
+        ImportNamespace -> [ InternalName ],
         ImportIdentifierList -> [ *ImportIdentifier ],
         /// { foo as bar } or just { foo }
         ImportIdentifier -> [ ExternalName, ?InternalName ],
@@ -798,3 +810,91 @@ pub fn parse_tokens(tokens: Vec<Token>) -> (SyntaxNode, FileDiagnostics) {
     document::parse_document(&mut p);
     (SyntaxNode::new_root(p.builder.finish()), p.diags)
 }
+
+/// Serialize the syntax tree to `out`, so that it can be read back with [`read_syntax_tree`]
+/// without parsing the source again.
+///
+/// Each node is written as `(`, its kind, its children and `)`, and each token as `t`, its kind,
+/// the length of its text and the text. The kinds and lengths are little endian u16 and u32.
+pub fn write_syntax_tree(node: &SyntaxNode, out: &mut Vec<u8>) {
+    for event in node.preorder_with_tokens() {
+        match event {
+            rowan::WalkEvent::Enter(rowan::NodeOrToken::Node(node)) => {
+                out.push(b'(');
+                out.extend_from_slice(&u16::from(node.kind()).to_le_bytes());
+            }
+            rowan::WalkEvent::Enter(rowan::NodeOrToken::Token(token)) => {
+                out.push(b't');
+                out.extend_from_slice(&u16::from(token.kind()).to_le_bytes());
+                out.extend_from_slice(&(token.text().len() as u32).to_le_bytes());
+                out.extend_from_slice(token.text().as_bytes());
+            }
+            rowan::WalkEvent::Leave(rowan::NodeOrToken::Node(_)) => out.push(b')'),
+            rowan::WalkEvent::Leave(rowan::NodeOrToken::Token(_)) => {}
+        }
+    }
+}
+
+/// Read a syntax tree written by [`write_syntax_tree`]. Returns None if the data is not a
+/// valid syntax tree.
+pub fn read_syntax_tree(mut data: &[u8]) -> Option<SyntaxNode> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if data.len() < len {
+            return None;
+        }
+        let (head, tail) = data.split_at(len);
+        *data = tail;
+        Some(head)
+    }
+    fn take_kind(data: &mut &[u8]) -> Option<rowan::SyntaxKind> {
+        let raw = u16::from_le_bytes(TryFrom::try_from(take(data, 2)?).ok()?);
+        Some(SyntaxKind::try_from(raw).ok()?.into())
+    }
+
+    let mut builder = rowan::GreenNodeBuilder::new();
+    let mut depth = 0usize;
+    loop {
+        match take(&mut data, 1)?[0] {
+            b'(' => {
+                builder.start_node(take_kind(&mut data)?);
+                depth += 1;
+            }
+            b't' if depth > 0 => {
+                let kind = take_kind(&mut data)?;
+                let len = u32::from_le_bytes(TryFrom::try_from(take(&mut data, 4)?).ok()?);
+                let text = std::str::from_utf8(take(&mut data, len as usize)?).ok()?;
+                builder.token(kind, text.into());
+            }
+            b')' if depth > 0 => {
+                builder.finish_node();
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => return None,
+        }
+    }
+    if !data.is_empty() {
+        return None;
+    }
+    Some(SyntaxNode::new_root(builder.finish()))
+}
+
+#[test]
+fn test_syntax_tree_serialization() {
+    let source = "Hello := Rectangle {\n    property <string> text: \"héllo\"; // comment\n}\n";
+    let (node, diag) = parse(source.into(), None);
+    assert!(!diag.has_error());
+    let mut data = Vec::new();
+    write_syntax_tree(&node.node, &mut data);
+    let read = read_syntax_tree(&data).unwrap();
+    assert_eq!(read.to_string(), source);
+    assert_eq!(format!("{:#?}", read), format!("{:#?}", node.node));
+
+    assert!(read_syntax_tree(&data[..data.len() - 1]).is_none());
+    let mut trailing = data.clone();
+    trailing.push(b')');
+    assert!(read_syntax_tree(&trailing).is_none());
+    assert!(read_syntax_tree(&[]).is_none());
+}
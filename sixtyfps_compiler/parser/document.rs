@@ -186,6 +186,7 @@ fn parse_sub_element(p: &mut impl Parser) {
 /// for xx in mm: Elem { }
 /// for [idx] in mm: Elem { }
 /// for xx [idx] in foo.bar: Elem { }
+/// for xx in mm: foo := Elem { }
 /// ```
 /// Must consume at least one token
 fn parse_repeated_element(p: &mut impl Parser) {
@@ -209,7 +210,7 @@ fn parse_repeated_element(p: &mut impl Parser) {
     p.consume(); // "in"
     parse_expression(&mut *p);
     p.expect(SyntaxKind::Colon);
-    parse_element(&mut *p);
+    parse_sub_element(&mut *p);
 }
 
 #[cfg_attr(test, parser_test)]
@@ -351,6 +352,7 @@ fn parse_two_way_binding(p: &mut impl Parser) {
 /// signal foo(int, string);
 /// signal one_arg({ a: string, b: string});
 /// signal end_coma(a, b, c,);
+/// signal with_return(int) -> string;
 /// ```
 /// Must consume at least one token
 fn parse_signal_declaration(p: &mut impl Parser) {
@@ -369,6 +371,11 @@ fn parse_signal_declaration(p: &mut impl Parser) {
             }
         }
         p.expect(SyntaxKind::RParent);
+        if p.peek().kind() == SyntaxKind::Arrow {
+            let mut p = p.start_node(SyntaxKind::ReturnType);
+            p.consume();
+            parse_type(&mut *p);
+        }
     }
     p.expect(SyntaxKind::Semicolon);
 }
@@ -508,6 +515,7 @@ fn parse_state(p: &mut impl Parser) -> bool {
 /// ```test,Transitions
 /// transitions []
 /// transitions [to checked: {animate x { duration: 88ms; }} out checked: {animate x { duration: 88ms; }}]
+/// transitions [in-out checked: {animate x { duration: 88ms; }}]
 /// ```
 fn parse_transitions(p: &mut impl Parser) {
     debug_assert_eq!(p.peek().as_str(), "transitions");
@@ -523,14 +531,24 @@ fn parse_transitions(p: &mut impl Parser) {
 /// to pressed : {}
 /// to pressed: { animate x { duration: 88ms; } }
 /// out pressed: { animate x { duration: 88ms; } }
+/// in-out pressed: { animate x { duration: 88ms; } }
 /// ```
 fn parse_transition(p: &mut impl Parser) -> bool {
-    if !matches!(p.peek().as_str(), "to" | "out") {
-        p.error("Expected 'to' or 'out' to declare a transition");
+    if !matches!(p.peek().as_str(), "to" | "out" | "in") {
+        p.error("Expected 'to', 'out' or 'in-out' to declare a transition");
         return false;
     }
     let mut p = p.start_node(SyntaxKind::Transition);
-    p.consume(); // "to" or "out"
+    if p.peek().as_str() == "in" {
+        p.consume(); // "in"
+        p.expect(SyntaxKind::Minus);
+        if p.peek().as_str() != "out" {
+            p.error("Expected 'in-out' to declare a transition");
+        }
+        p.expect(SyntaxKind::Identifier); // "out"
+    } else {
+        p.consume(); // "to" or "out"
+    }
     {
         let mut p = p.start_node(SyntaxKind::DeclaredIdentifier);
         p.expect(SyntaxKind::Identifier);
@@ -618,12 +636,17 @@ fn parse_export_specifier(p: &mut impl Parser) -> bool {
 #[cfg_attr(test, parser_test)]
 /// ```test,ImportSpecifier
 /// import { Type1, Type2 } from "somewhere";
+/// import * as Somewhere from "somewhere";
 /// ```
 fn parse_import_specifier(p: &mut impl Parser) -> bool {
     debug_assert_eq!(p.peek().as_str(), "import");
     let mut p = p.start_node(SyntaxKind::ImportSpecifier);
     p.consume(); // "import"
-    if !parse_import_identifier_list(&mut *p) {
+    if p.nth(0).kind() == SyntaxKind::Star {
+        if !parse_import_namespace(&mut *p) {
+            return false;
+        }
+    } else if !parse_import_identifier_list(&mut *p) {
         return false;
     }
     if p.peek().as_str() != "from" {
@@ -639,6 +662,24 @@ fn parse_import_specifier(p: &mut impl Parser) -> bool {
     p.expect(SyntaxKind::Semicolon)
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,ImportNamespace
+/// * as Module
+/// ```
+fn parse_import_namespace(p: &mut impl Parser) -> bool {
+    let mut p = p.start_node(SyntaxKind::ImportNamespace);
+    if !p.expect(SyntaxKind::Star) {
+        return false;
+    }
+    if p.peek().as_str() != "as" {
+        p.error("Expected as keyword after * in import statement");
+        return false;
+    }
+    p.consume();
+    let mut p = p.start_node(SyntaxKind::InternalName);
+    p.expect(SyntaxKind::Identifier)
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,ImportIdentifierList
 /// { Type1 }
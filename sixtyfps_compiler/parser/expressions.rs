@@ -14,6 +14,7 @@ use super::prelude::*;
 /// ```test,Expression
 /// something
 /// "something"
+/// "some\{thing}"
 /// 0.3
 /// 42
 /// 42px
@@ -30,6 +31,9 @@ use super::prelude::*;
 /// 4 + 8 * 7 / 5 + 3 - 7 - 7 * 8
 /// -0.3px + 0.3px - 3.pt+3pt
 /// aa == cc && bb && (xxx || fff) && 3 + aaa == bbb
+/// a > 0 && b < 10
+/// a != b || !c || -d * 2 != e - 1
+/// (a != b && c) || !(d <= e)
 /// [array]
 /// {object:42}
 /// ```
@@ -65,7 +69,13 @@ fn parse_expression_helper(p: &mut impl Parser, precedence: OperatorPrecedence)
                 parse_qualified_name(&mut *p);
             }
         }
-        SyntaxKind::StringLiteral => p.consume(),
+        SyntaxKind::StringLiteral => {
+            if crate::lexer::string_literal_continues(p.nth(0).as_str()) {
+                parse_string_template(&mut *p)
+            } else {
+                p.consume()
+            }
+        }
         SyntaxKind::NumberLiteral => p.consume(),
         SyntaxKind::ColorLiteral => p.consume(),
         SyntaxKind::LParent => {
@@ -212,6 +222,31 @@ fn parse_bang_expression(p: &mut impl Parser) {
     parse_expression_helper(&mut *p, OperatorPrecedence::Bang);
 }
 
+#[cfg_attr(test, parser_test)]
+/// ```test,StringTemplate
+/// "\{a}"
+/// "Hello \{name}!"
+/// "\{a + 1} and \{ foo("\{b}") }"
+/// "{\{ {x: 1} }}"
+/// ```
+fn parse_string_template(p: &mut impl Parser) {
+    let mut p = p.start_node(SyntaxKind::StringTemplate);
+    debug_assert_eq!(p.nth(0).kind(), SyntaxKind::StringLiteral);
+    p.consume();
+    loop {
+        parse_expression(&mut *p);
+        if p.nth(0).kind() != SyntaxKind::StringLiteral || !p.nth(0).as_str().starts_with('}') {
+            p.error("Expected '}' to end the interpolated expression");
+            return;
+        }
+        let continues = crate::lexer::string_literal_continues(p.nth(0).as_str());
+        p.consume();
+        if !continues {
+            break;
+        }
+    }
+}
+
 #[cfg_attr(test, parser_test)]
 /// ```test,Array
 /// [ a, b, c , d]
@@ -238,12 +273,22 @@ fn parse_array(p: &mut impl Parser) {
 /// {a:b}
 /// { a: "foo" , }
 /// {a:b, c: 4 + 4, d: [a,] }
+/// { ...a }
+/// { ...a.b, c: 4, }
 /// ```
 fn parse_object_notation(p: &mut impl Parser) {
     let mut p = p.start_node(SyntaxKind::ObjectLiteral);
     p.expect(SyntaxKind::LBrace);
 
-    while p.nth(0).kind() != SyntaxKind::RBrace {
+    let mut has_more_members = true;
+    if p.nth(0).kind() == SyntaxKind::DotDotDot {
+        let mut p = p.start_node(SyntaxKind::ObjectSpread);
+        p.consume();
+        parse_expression(&mut *p);
+        has_more_members = p.test(SyntaxKind::Comma);
+    }
+
+    while has_more_members && p.nth(0).kind() != SyntaxKind::RBrace {
         let mut p = p.start_node(SyntaxKind::ObjectMember);
         p.expect(SyntaxKind::Identifier);
         p.expect(SyntaxKind::Colon);
@@ -0,0 +1,81 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that warns about elements declared with the same id in a component.
+//!
+//! An id is looked up in the elements of the component, or of the repeated element, that declares
+//! it. When several elements of that scope have the same id, only the first one can be referenced.
+//! This needs to run before the inlining, which brings the ids of the other components in.
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCategory};
+use crate::object_tree::*;
+use crate::parser::{SyntaxKind, SyntaxNodeWithSourceFile, SyntaxTokenWithSourceFile};
+use std::collections::HashMap;
+
+pub fn check_duplicate_ids(doc: &Document, diag: &mut BuildDiagnostics) {
+    for component in &doc.inner_components {
+        check_scope(&component.root_element, diag);
+    }
+}
+
+/// Check the ids of the elements in the scope starting at `scope_root`, and of the scopes of the
+/// repeated elements it contains
+fn check_scope(scope_root: &ElementRc, diag: &mut BuildDiagnostics) {
+    let mut ids = HashMap::<String, ElementRc>::new();
+    let mut repeated = vec![];
+    collect_ids(scope_root, &mut ids, &mut repeated, diag);
+    for elem in repeated {
+        check_scope(&elem, diag);
+    }
+}
+
+fn collect_ids(
+    elem: &ElementRc,
+    ids: &mut HashMap<String, ElementRc>,
+    repeated: &mut Vec<ElementRc>,
+    diag: &mut BuildDiagnostics,
+) {
+    let id = elem.borrow().id.clone();
+    if !id.is_empty() {
+        if let Some(first) = ids.get(&id) {
+            if let (Some(token), Some(first_token)) = (id_token(elem), id_token(first)) {
+                diag.push_warning(
+                    format!(
+                        "The id '{}' is already used by another element. This element cannot be referenced by its id",
+                        id
+                    ),
+                    &token,
+                    DiagnosticCategory::Correctness,
+                );
+                diag.push_warning(
+                    format!("The id '{}' is used again by another element", id),
+                    &first_token,
+                    DiagnosticCategory::Correctness,
+                );
+            }
+        } else {
+            ids.insert(id, elem.clone());
+        }
+    }
+    for child in &elem.borrow().children {
+        if child.borrow().repeated.is_some() {
+            repeated.push(child.clone());
+        } else {
+            collect_ids(child, ids, repeated, diag);
+        }
+    }
+}
+
+/// The token of the id in `id := Element { ... }`, if the id was declared that way
+fn id_token(elem: &ElementRc) -> Option<SyntaxTokenWithSourceFile> {
+    let node = elem.borrow().node.clone()?;
+    let sub_element = node.0.node.parent().filter(|p| p.kind() == SyntaxKind::SubElement)?;
+    SyntaxNodeWithSourceFile { node: sub_element, source_file: node.0.source_file.clone() }
+        .child_token(SyntaxKind::Identifier)
+}
@@ -0,0 +1,141 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that warns about the animations of properties that never change.
+//!
+//! An animation declared with `animate` on a property which has no binding, is not changed by a
+//! state, and is never assigned, has no effect. This is usually a copy-paste mistake.
+//! The properties of the root element of a component are not checked, as they can be set by the
+//! user of the component. The geometry of the elements in a layout is set by the layout, so it is
+//! not reported either. This needs to run before the inlining and the lowering of the states.
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCategory};
+use crate::expression_tree::{Expression, NamedReference};
+use crate::object_tree::*;
+use crate::parser::syntax_nodes;
+use crate::typeregister::Type;
+use by_address::ByAddress;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+type ChangedProperties = HashSet<(ByAddress<ElementRc>, String)>;
+
+pub fn check_unused_animations(doc: &Document, diag: &mut BuildDiagnostics) {
+    let mut changed = ChangedProperties::new();
+    let mut visited = HashSet::new();
+    for component in &doc.inner_components {
+        collect_changed_properties(component, &mut changed, &mut visited);
+    }
+
+    for component in &doc.inner_components {
+        recurse_elem(&component.root_element, &false, &mut |elem, in_layout| {
+            let is_layout = is_layout(elem);
+            if Rc::ptr_eq(elem, &component.root_element) {
+                return is_layout;
+            }
+            for prop_name in elem.borrow().property_animations.keys() {
+                if is_changed(elem, prop_name, &changed)
+                    || (*in_layout && matches!(prop_name.as_str(), "x" | "y" | "width" | "height"))
+                {
+                    continue;
+                }
+                if let Some(node) = animation_node(elem, prop_name) {
+                    diag.push_warning(
+                        format!(
+                            "The property '{}' is animated but it has no binding and is never assigned. The animation has no effect",
+                            prop_name
+                        ),
+                        &node,
+                        DiagnosticCategory::Unused,
+                    );
+                }
+            }
+            is_layout
+        })
+    }
+}
+
+/// Returns true if the element sets the geometry of its children
+fn is_layout(elem: &ElementRc) -> bool {
+    match &elem.borrow().base_type {
+        Type::Builtin(be) => {
+            matches!(be.native_class.class_name.as_str(), "GridLayout" | "Row" | "PathLayout")
+        }
+        _ => false,
+    }
+}
+
+/// Collect the properties that are changed by a state, an assignment, or a two way binding in
+/// the component, and in the components it uses
+fn collect_changed_properties(
+    component: &Rc<Component>,
+    changed: &mut ChangedProperties,
+    visited: &mut HashSet<ByAddress<Rc<Component>>>,
+) {
+    if !visited.insert(ByAddress(component.clone())) {
+        return;
+    }
+    let mut insert = |nr: &NamedReference| {
+        if let Some(element) = nr.element.upgrade() {
+            changed.insert((ByAddress(element), nr.name.clone()));
+        }
+    };
+    let mut used_components = vec![];
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        visit_element_expressions(elem, |expr, _| {
+            collect_from_expression(expr, &mut insert);
+        });
+        for state in &elem.borrow().states {
+            for (nr, _) in &state.property_changes {
+                insert(nr);
+            }
+        }
+        if let Type::Component(base) = &elem.borrow().base_type {
+            used_components.push(base.clone());
+        }
+    });
+    for c in used_components {
+        collect_changed_properties(&c, changed, visited);
+    }
+}
+
+fn collect_from_expression(expr: &Expression, insert: &mut impl FnMut(&NamedReference)) {
+    match expr {
+        Expression::SelfAssignment { lhs, .. } => {
+            if let Expression::PropertyReference(nr) = &**lhs {
+                insert(nr);
+            }
+        }
+        Expression::TwoWayBinding(nr) => insert(nr),
+        _ => {}
+    }
+    expr.visit(|sub| collect_from_expression(sub, insert));
+}
+
+/// Returns true if the property has a binding, is changed somewhere, or if it is changed by the
+/// component the element is an instance of
+fn is_changed(elem: &ElementRc, prop_name: &str, changed: &ChangedProperties) -> bool {
+    if elem.borrow().bindings.contains_key(prop_name)
+        || changed.contains(&(ByAddress(elem.clone()), prop_name.to_owned()))
+    {
+        return true;
+    }
+    match &elem.borrow().base_type {
+        Type::Component(base) => is_changed(&base.root_element, prop_name, changed),
+        _ => false,
+    }
+}
+
+/// The `animate` block declaring the animation of this property in the element
+fn animation_node(elem: &ElementRc, prop_name: &str) -> Option<syntax_nodes::PropertyAnimation> {
+    let node = elem.borrow().node.clone()?;
+    node.PropertyAnimation().find(|anim| {
+        anim.QualifiedName().any(|qn| QualifiedTypeName::from_node(qn).to_string() == prop_name)
+    })
+}
@@ -0,0 +1,99 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Helpers for the passes that make a property work by inserting a native item around an
+//! element, or between an element and its children. These items are created by the compiler
+//! and cannot be used directly in .60 files.
+
+use crate::expression_tree::{Expression, NamedReference};
+use crate::object_tree::*;
+use crate::typeregister::{BuiltinElement, NativeClass, Type};
+use std::rc::Rc;
+
+/// Returns the type of the native item `class_name`, which is not in the type register.
+pub fn helper_item_type(class_name: &str, properties: &[(&str, Type)]) -> Type {
+    Type::Builtin(Rc::new(BuiltinElement::new(Rc::new(NativeClass::new_with_properties(
+        class_name,
+        properties.iter().map(|(name, ty)| (name.to_string(), ty.clone())),
+    )))))
+}
+
+/// Calls `visitor` for the elements of the component, and of the components of its repeated
+/// elements, except for the repeated elements themselves.
+pub fn recurse_non_repeated_elem(component: &Rc<Component>, visitor: &mut impl FnMut(&ElementRc)) {
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        if elem.borrow().repeated.is_some() {
+            if let Type::Component(base) = &elem.borrow().base_type {
+                recurse_non_repeated_elem(base, visitor);
+            }
+            return;
+        }
+        visitor(elem);
+    })
+}
+
+/// Wraps the elements that have a binding on `property` in an element of type `helper_type`,
+/// whose `property` is bound to the one of the element.
+///
+/// The root element of a component, including the elements repeated with `for` or `if`,
+/// cannot be wrapped, so they are left alone.
+pub fn wrap_elements_with_binding(component: &Rc<Component>, property: &str, helper_type: &Type) {
+    recurse_non_repeated_elem(component, &mut |elem| {
+        let children = std::mem::take(&mut elem.borrow_mut().children);
+        elem.borrow_mut().children = children
+            .into_iter()
+            .map(|child| {
+                if child.borrow().repeated.is_none()
+                    && child.borrow().bindings.contains_key(property)
+                {
+                    create_helper_element(
+                        &child,
+                        property,
+                        helper_type,
+                        &[property],
+                        vec![child.clone()],
+                    )
+                } else {
+                    child
+                }
+            })
+            .collect();
+    })
+}
+
+/// Creates an element of type `helper_type` with the given children, whose `properties` are
+/// bound to the properties of the same name of `elem`. Its id is the one of `elem` followed by
+/// `id_suffix`.
+pub fn create_helper_element(
+    elem: &ElementRc,
+    id_suffix: &str,
+    helper_type: &Type,
+    properties: &[&str],
+    children: Vec<ElementRc>,
+) -> ElementRc {
+    let bindings = properties
+        .iter()
+        .map(|name| {
+            let binding = Expression::PropertyReference(NamedReference {
+                element: Rc::downgrade(elem),
+                name: name.to_string(),
+            });
+            (name.to_string(), binding.into())
+        })
+        .collect();
+    let element = Element {
+        id: format!("{}_{}", elem.borrow().id, id_suffix),
+        base_type: helper_type.clone(),
+        enclosing_component: elem.borrow().enclosing_component.clone(),
+        bindings,
+        children,
+        ..Default::default()
+    };
+    Rc::new(std::cell::RefCell::new(element))
+}
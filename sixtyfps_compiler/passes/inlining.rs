@@ -9,10 +9,13 @@
 LICENSE END */
 //! Inline each object_tree::Component within the main Component
 
-use crate::{expression_tree::NamedReference, object_tree::*, typeregister::Type};
+use crate::expression_tree::{
+    BuiltinFunction, Expression, ExpressionSpanned, NamedReference, Unit,
+};
+use crate::{object_tree::*, typeregister::Type};
 use by_address::ByAddress;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 pub fn inline(doc: &Document) {
@@ -78,6 +81,13 @@ fn inline_element(
             .map(|x| duplicate_element_with_mapping(x, &mut mapping, root_component)),
     );
 
+    if inlined_component.child_insertion_point.borrow().is_some()
+        && !elem_mut.bindings.contains_key("children_count")
+    {
+        let count = children_count(&elem_mut.children);
+        elem_mut.bindings.insert("children_count".into(), count.into());
+    }
+
     match inlined_component
         .child_insertion_point
         .borrow()
@@ -99,14 +109,33 @@ fn inline_element(
 
     elem_mut.children = new_children;
 
-    elem_mut.bindings.extend(
-        inlined_component
-            .root_element
-            .borrow()
-            .bindings
-            .iter()
-            .map(|(k, val)| (k.clone(), val.clone())),
-    );
+    elem_mut.states.extend(inlined_component.root_element.borrow().states.iter().cloned());
+    let transitions = inlined_component
+        .root_element
+        .borrow()
+        .transitions
+        .iter()
+        .map(|t| duplicate_transition(t, &mut mapping, root_component))
+        .collect::<Vec<_>>();
+    elem_mut.transitions.extend(transitions);
+
+    for (k, val) in inlined_component.root_element.borrow().bindings.iter() {
+        if !elem_mut.bindings.contains_key(k) {
+            elem_mut.bindings.insert(k.clone(), val.clone());
+            continue;
+        }
+        // The binding set where the component is used takes precedence over the default from
+        // the component. But if the component links that property to one of its own elements,
+        // the link must be kept: so the other side of the two way binding is linked instead.
+        if let Expression::TwoWayBinding(nr) = &val.expression {
+            let mut link = val.clone();
+            link.expression = Expression::TwoWayBinding(NamedReference {
+                element: Rc::downgrade(elem),
+                name: k.clone(),
+            });
+            link_unlinked_end(nr, link, elem, &mut elem_mut, &mapping);
+        }
+    }
 
     core::mem::drop(elem_mut);
 
@@ -116,6 +145,91 @@ fn inline_element(
     }
 }
 
+/// Returns the expression that computes the number of elements created for `children`.
+/// An `if` counts when its condition is true, and a `for` counts for each element of its model.
+fn children_count(children: &[ElementRc]) -> Expression {
+    let mut count = 0;
+    let mut dynamic_counts = vec![];
+    for child in children {
+        match &child.borrow().repeated {
+            None => count += 1,
+            Some(r) if r.is_conditional_element => dynamic_counts.push(Expression::Condition {
+                condition: Box::new(r.model.clone()),
+                true_expr: Box::new(Expression::NumberLiteral(1., Unit::None)),
+                false_expr: Box::new(Expression::NumberLiteral(0., Unit::None)),
+            }),
+            Some(r) => {
+                // The model was converted to a model by the resolving pass
+                let model = match &r.model {
+                    Expression::Cast { from, to: Type::Model } => &**from,
+                    model => model,
+                };
+                match model {
+                    Expression::Array { values, .. } => count += values.len(),
+                    _ if matches!(model.ty(), Type::Int32 | Type::Float32) => {
+                        dynamic_counts.push(model.clone())
+                    }
+                    _ => dynamic_counts.push(Expression::FunctionCall {
+                        function: Box::new(Expression::BuiltinFunctionReference(
+                            BuiltinFunction::ArrayLength,
+                        )),
+                        arguments: vec![r.model.clone()],
+                    }),
+                }
+            }
+        }
+    }
+    let sum = dynamic_counts
+        .into_iter()
+        .fold(Expression::NumberLiteral(count as _, Unit::None), |lhs, rhs| {
+            Expression::BinaryExpression { lhs: Box::new(lhs), rhs: Box::new(rhs), op: '+' }
+        });
+    Expression::Cast { from: Box::new(sum), to: Type::Int32 }
+}
+
+/// Follow the chain of two way bindings starting at `nr` within the inlined component, and set
+/// `link` as the binding of the first property of the chain which is not linked to another one.
+///
+/// The bindings of the duplicated elements still refer to the elements of the inlined component,
+/// so `mapping` is used to find the new elements. `elem_mut` is the borrowed `elem`.
+fn link_unlinked_end(
+    nr: &NamedReference,
+    link: ExpressionSpanned,
+    elem: &ElementRc,
+    elem_mut: &mut Element,
+    mapping: &HashMap<ByAddress<ElementRc>, ElementRc>,
+) {
+    let mut current = nr.clone();
+    let mut visited = HashSet::new();
+    loop {
+        let e = match current.element.upgrade().and_then(|e| mapping.get(&element_key(e))) {
+            Some(e) => e.clone(),
+            None => return,
+        };
+        if !visited.insert((element_key(e.clone()), current.name.clone())) {
+            // This is a loop, which is reported later
+            return;
+        }
+        let next = {
+            let mut e_borrowed;
+            let bindings = if Rc::ptr_eq(&e, elem) {
+                &mut elem_mut.bindings
+            } else {
+                e_borrowed = e.borrow_mut();
+                &mut e_borrowed.bindings
+            };
+            match bindings.get(&current.name).map(|b| &b.expression) {
+                Some(Expression::TwoWayBinding(next)) => next.clone(),
+                _ => {
+                    bindings.insert(current.name, link);
+                    return;
+                }
+            }
+        };
+        current = next;
+    }
+}
+
 // Duplicate the element elem and all its children. And fill the mapping to point from the old to the new
 fn duplicate_element_with_mapping(
     element: &ElementRc,
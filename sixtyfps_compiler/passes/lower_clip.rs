@@ -0,0 +1,43 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that makes the `clip` property work: a `Clip` item is inserted as the only child of the
+//! elements that have a binding on it, and the children of the element are moved into it.
+//! The `Clip` item has the size of the element, so the children are clipped to its rectangle.
+//!
+//! Unlike `visible` and `opacity`, this also works on the root element of a component, since
+//! the element itself is not wrapped. A `Flickable` always clips its children, so it is skipped.
+
+use super::helper_items::{create_helper_element, helper_item_type, recurse_non_repeated_elem};
+use crate::object_tree::*;
+use crate::typeregister::Type;
+use std::rc::Rc;
+
+pub fn lower_clip(component: &Rc<Component>) {
+    let clip_type = helper_item_type(
+        "Clip",
+        &[("width", Type::Length), ("height", Type::Length), ("clip", Type::Bool)],
+    );
+    recurse_non_repeated_elem(component, &mut |elem| {
+        if !elem.borrow().bindings.contains_key("clip") || is_flickable(elem) {
+            return;
+        }
+        let children = std::mem::take(&mut elem.borrow_mut().children);
+        let clip =
+            create_helper_element(elem, "clip", &clip_type, &["width", "height", "clip"], children);
+        elem.borrow_mut().children = vec![clip];
+    })
+}
+
+fn is_flickable(elem: &ElementRc) -> bool {
+    match &elem.borrow().base_type {
+        Type::Builtin(b) => b.native_class.class_name == "Flickable",
+        _ => false,
+    }
+}
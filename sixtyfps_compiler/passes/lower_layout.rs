@@ -305,10 +305,30 @@ impl GridLayout {
             maximum_width: find_expression("maximum_width", &item_element),
             minimum_height: find_expression("minimum_height", &item_element),
             maximum_height: find_expression("maximum_height", &item_element),
+            collapsed: collapsed_expression(&item_element),
         });
     }
 }
 
+/// Returns the expression that is true when the element is left out of its layout, which is
+/// `!visible && collapse_when_hidden`. An element without a binding on both of these properties
+/// is never left out.
+fn collapsed_expression(item_element: &ElementRc) -> Option<Box<Expression>> {
+    let elem = item_element.borrow();
+    if !elem.bindings.contains_key("collapse_when_hidden") || !elem.bindings.contains_key("visible")
+    {
+        return None;
+    }
+    Some(Box::new(Expression::BinaryExpression {
+        lhs: Box::new(Expression::UnaryOp {
+            sub: property_reference(item_element, "visible"),
+            op: '!',
+        }),
+        rhs: property_reference(item_element, "collapse_when_hidden"),
+        op: '&',
+    }))
+}
+
 fn find_expression(name: &str, item_element: &ElementRc) -> Option<Box<Expression>> {
     item_element.borrow().bindings.get(name).map(|_| property_reference(item_element, name))
 }
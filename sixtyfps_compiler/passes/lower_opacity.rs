@@ -0,0 +1,24 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that makes the `opacity` property work: the elements that have a binding on it are
+//! wrapped in an `Opacity` item, which multiplies the alpha of the colors of its children.
+//!
+//! Like for the `visible` property, this must run after the layouts were lowered, and
+//! `opacity` has no effect on the root element of a component.
+
+use super::helper_items::{helper_item_type, wrap_elements_with_binding};
+use crate::object_tree::Component;
+use crate::typeregister::Type;
+use std::rc::Rc;
+
+pub fn lower_opacity(component: &Rc<Component>) {
+    let opacity_type = helper_item_type("Opacity", &[("opacity", Type::Float32)]);
+    wrap_elements_with_binding(component, "opacity", &opacity_type);
+}
@@ -9,7 +9,7 @@
 LICENSE END */
 //! Pass that create a state property, and change all the binding to depend on that property
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCategory};
 use crate::expression_tree::*;
 use crate::object_tree::*;
 use crate::typeregister::Type;
@@ -19,16 +19,18 @@ pub fn lower_states(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
     recurse_elem(&component.root_element, &(), &mut |elem, _| lower_state_in_element(elem, diag));
 }
 
-fn lower_state_in_element(root_element: &ElementRc, _diag: &mut BuildDiagnostics) {
+fn lower_state_in_element(root_element: &ElementRc, diag: &mut BuildDiagnostics) {
     if root_element.borrow().states.is_empty() {
         return;
     }
-    let state_property = compute_state_property_name(root_element);
+    check_unreachable_states(&root_element.borrow().states, diag);
+    let state_property = compute_property_name(root_element, "state");
     let state_property_ref = Expression::PropertyReference(NamedReference {
         element: Rc::downgrade(root_element),
         name: state_property.clone(),
     });
     let mut state_value = Expression::NumberLiteral(0., Unit::None);
+    let mut current_state_value = Expression::StringLiteral(String::new());
     let states = std::mem::take(&mut root_element.borrow_mut().states);
     for (idx, state) in states.into_iter().enumerate().rev() {
         if let Some(condition) = &state.condition {
@@ -38,6 +40,15 @@ fn lower_state_in_element(root_element: &ElementRc, _diag: &mut BuildDiagnostics
                 false_expr: Box::new(std::mem::take(&mut state_value)),
             };
         }
+        current_state_value = Expression::Condition {
+            condition: Box::new(Expression::BinaryExpression {
+                lhs: Box::new(state_property_ref.clone()),
+                rhs: Box::new(Expression::NumberLiteral((idx + 1) as _, Unit::None)),
+                op: '=',
+            }),
+            true_expr: Box::new(Expression::StringLiteral(state.id.clone())),
+            false_expr: Box::new(current_state_value),
+        };
         for (ne, expr) in state.property_changes {
             let e = ne.element.upgrade().unwrap();
             let property_expr = expression_for_property(&e, ne.name.as_str());
@@ -61,11 +72,65 @@ fn lower_state_in_element(root_element: &ElementRc, _diag: &mut BuildDiagnostics
         PropertyDeclaration { property_type: Type::Int32, ..PropertyDeclaration::default() },
     );
     root_element.borrow_mut().bindings.insert(state_property.clone(), state_value.into());
+
+    let current_state_property = compute_property_name(root_element, "current_state");
+    root_element.borrow_mut().property_declarations.insert(
+        current_state_property.clone(),
+        PropertyDeclaration { property_type: Type::String, ..PropertyDeclaration::default() },
+    );
+    root_element.borrow_mut().bindings.insert(current_state_property, current_state_value.into());
+}
+
+/// Warn about states that can never become active: the first state whose condition is true wins,
+/// so a state with a `false` condition, or with the same condition as a previous state, is useless.
+fn check_unreachable_states(states: &[State], diag: &mut BuildDiagnostics) {
+    for (idx, state) in states.iter().enumerate() {
+        let condition = match &state.condition {
+            Some(condition) => condition,
+            None => continue,
+        };
+        let message = if matches!(condition, Expression::BoolLiteral(false)) {
+            format!("The state '{}' can never be active because its condition is false", state.id)
+        } else if let Some(previous) = states[..idx]
+            .iter()
+            .find(|s| s.condition.as_ref().map_or(false, |c| is_same_condition(c, condition)))
+        {
+            format!(
+                "The state '{}' can never be active because the state '{}' has the same condition",
+                state.id, previous.id
+            )
+        } else {
+            continue;
+        };
+        diag.push_warning(message, &state.id_node, DiagnosticCategory::Unused);
+    }
+}
+
+/// Conservative structural comparison of two conditions: only returns true for simple
+/// expressions that are obviously the same.
+fn is_same_condition(a: &Expression, b: &Expression) -> bool {
+    match (a, b) {
+        (Expression::BoolLiteral(a), Expression::BoolLiteral(b)) => a == b,
+        (Expression::NumberLiteral(a, ua), Expression::NumberLiteral(b, ub)) => a == b && ua == ub,
+        (Expression::StringLiteral(a), Expression::StringLiteral(b)) => a == b,
+        (Expression::PropertyReference(a), Expression::PropertyReference(b)) => a == b,
+        (Expression::Cast { from: a, to: ta }, Expression::Cast { from: b, to: tb }) => {
+            ta == tb && is_same_condition(a, b)
+        }
+        (Expression::UnaryOp { sub: a, op: oa }, Expression::UnaryOp { sub: b, op: ob }) => {
+            oa == ob && is_same_condition(a, b)
+        }
+        (
+            Expression::BinaryExpression { lhs: la, rhs: ra, op: oa },
+            Expression::BinaryExpression { lhs: lb, rhs: rb, op: ob },
+        ) => oa == ob && is_same_condition(la, lb) && is_same_condition(ra, rb),
+        _ => false,
+    }
 }
 
-/// Returns a suitable unique name for the "state" property
-fn compute_state_property_name(root_element: &ElementRc) -> String {
-    let mut property_name = "state".to_owned();
+/// Returns a name starting with `base_name` that is not yet a property of the element
+fn compute_property_name(root_element: &ElementRc, base_name: &str) -> String {
+    let mut property_name = base_name.to_owned();
     while root_element.borrow().lookup_property(property_name.as_ref()) != Type::Invalid {
         property_name += "_";
     }
@@ -0,0 +1,26 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that makes the `visible` property work: the elements that have a binding on it are
+//! wrapped in a `Visibility` item that hides them, and frees their graphics resources, when
+//! the property is false.
+//!
+//! This must run after the layouts were lowered, so that the element stays in its layout.
+//! The root element of a component, including the elements repeated with `for` or `if`,
+//! cannot be wrapped, so `visible` has no effect on them.
+
+use super::helper_items::{helper_item_type, wrap_elements_with_binding};
+use crate::object_tree::Component;
+use crate::typeregister::Type;
+use std::rc::Rc;
+
+pub fn lower_visible(component: &Rc<Component>) {
+    let visibility_type = helper_item_type("Visibility", &[("visible", Type::Bool)]);
+    wrap_elements_with_binding(component, "visible", &visibility_type);
+}
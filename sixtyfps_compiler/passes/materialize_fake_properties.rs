@@ -9,22 +9,28 @@
 LICENSE END */
 //! This pass creates properties that are used but are otherwise not real
 
-use crate::expression_tree::NamedReference;
+use crate::expression_tree::{Expression, NamedReference, Unit};
 use crate::object_tree::*;
 use crate::typeregister::Type;
 use std::collections::HashMap;
 use std::rc::Rc;
 
 pub fn materialize_fake_properties(component: &Rc<Component>) {
+    // The properties that were materialized without a binding. They get their default binding
+    // once all the elements were visited, as the bindings of an element are taken out of it
+    // while its named references are visited.
+    let mut without_binding = vec![];
     recurse_elem_no_borrow(&component.root_element, &(), &mut |elem, _| {
         visit_all_named_references(elem, |NamedReference { element, name }| {
-            let elem = element.upgrade().unwrap();
-            let elem = elem.borrow_mut();
+            let element = element.upgrade().unwrap();
+            let elem = element.borrow_mut();
             let (base_type, mut property_declarations) =
                 std::cell::RefMut::map_split(elem, |elem| {
                     (&mut elem.base_type, &mut elem.property_declarations)
                 });
-            maybe_materialize(&mut property_declarations, &base_type, name);
+            if maybe_materialize(&mut property_declarations, &base_type, name) {
+                without_binding.push((element.clone(), name.clone()));
+            }
         });
         let elem = elem.borrow_mut();
         let base_type = elem.base_type.clone();
@@ -34,16 +40,33 @@ pub fn materialize_fake_properties(component: &Rc<Component>) {
         for (prop, _) in bindings.iter() {
             maybe_materialize(&mut property_declarations, &base_type, prop);
         }
-    })
+    });
+    for (elem, prop) in without_binding {
+        if let Some(default_binding) = default_binding(&prop) {
+            elem.borrow_mut().bindings.entry(prop).or_insert_with(|| default_binding.into());
+        }
+    }
+}
+
+/// The value of the materialized properties that have no binding, when it is not the default
+/// value of their type
+fn default_binding(prop: &str) -> Option<Expression> {
+    match prop {
+        "visible" => Some(Expression::BoolLiteral(true)),
+        "opacity" => Some(Expression::NumberLiteral(1., Unit::None)),
+        _ => None,
+    }
 }
 
+/// Declare the property in the element if it is a reserved property that is not declared yet.
+/// Returns true if the property was declared.
 fn maybe_materialize(
     property_declarations: &mut HashMap<String, PropertyDeclaration>,
     base_type: &Type,
     prop: &String,
-) {
+) -> bool {
     if property_declarations.contains_key(prop) {
-        return;
+        return false;
     }
     let has_declared_property = match &base_type {
         crate::typeregister::Type::Component(c) => {
@@ -61,8 +84,10 @@ fn maybe_materialize(
                 prop.to_owned(),
                 PropertyDeclaration { property_type: ty, ..PropertyDeclaration::default() },
             );
+            return true;
         }
     }
+    false
 }
 
 /// Returns true if the property is declared in this element or parent
@@ -0,0 +1,76 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that merges the animations declared with `animate` in an element into the animations
+//! of the transitions animating the same property.
+//!
+//! The animation declared in the transition takes precedence during the transition: its bindings
+//! are kept, and only the bindings it does not set are taken from the element's animation.
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCategory};
+use crate::expression_tree::*;
+use crate::object_tree::*;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub fn merge_animations(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        // The two transitions of an `in-out` have the same bindings, only warn once about them
+        let mut warned_offsets = HashSet::new();
+        for transition in &elem.borrow().transitions {
+            for (ne, transition_anim) in &transition.property_animations {
+                let element_anim = match ne
+                    .element
+                    .upgrade()
+                    .and_then(|e| e.borrow().property_animations.get(&ne.name).cloned())
+                {
+                    Some(element_anim) => element_anim,
+                    None => continue,
+                };
+                let element_anim = element_anim.borrow();
+                let mut transition_anim = transition_anim.borrow_mut();
+                for (name, binding) in &element_anim.bindings {
+                    match transition_anim.bindings.get(name) {
+                        Some(transition_binding) => {
+                            if name == "duration"
+                                && is_different_constant(&binding.expression, &transition_binding)
+                                && transition_binding
+                                    .span
+                                    .as_ref()
+                                    .map_or(true, |(_, span)| warned_offsets.insert(span.offset))
+                            {
+                                diag.push_warning(
+                                    format!(
+                                        "The transition to state '{}' animates '{}' with a different duration than the animation declared in the element. The duration of the transition takes precedence",
+                                        transition.state_id, ne.name
+                                    ),
+                                    transition_binding,
+                                    DiagnosticCategory::Correctness,
+                                );
+                            }
+                        }
+                        None => {
+                            transition_anim.bindings.insert(name.clone(), binding.clone());
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Returns true if both expressions are literal numbers with a different value
+fn is_different_constant(a: &Expression, b: &Expression) -> bool {
+    match (a, b) {
+        (Expression::NumberLiteral(a, unit_a), Expression::NumberLiteral(b, unit_b)) => {
+            unit_a.normalize(*a) != unit_b.normalize(*b)
+        }
+        _ => false,
+    }
+}
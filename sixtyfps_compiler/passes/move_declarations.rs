@@ -10,7 +10,7 @@ LICENSE END */
 //! This pass moves all declaration of properties or signal to the root
 
 use crate::{
-    diagnostics::{BuildDiagnostics, Level},
+    diagnostics::{BuildDiagnostics, DiagnosticCategory},
     expression_tree::{Expression, NamedReference},
     object_tree::*,
     passes::ExpressionFieldsVisitor,
@@ -36,10 +36,10 @@ pub fn move_declarations(component: &Rc<Component>, diag: &mut BuildDiagnostics)
         if d.property_type.ok_for_public_api() {
             d.expose_in_public_api = true
         } else {
-            diag.push_diagnostic(
+            diag.push_warning(
                  format!("Properties of type {} are not supported yet for public API. The property will not be exposed.", d.property_type),
                  &d.type_node,
-                 Level::Warning
+                 DiagnosticCategory::Correctness
             );
         }
     });
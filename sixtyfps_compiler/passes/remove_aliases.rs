@@ -118,6 +118,11 @@ fn process_alias<'a>(
             return;
         }
         let k = NamedReference { element: Rc::downgrade(&from.0), name: from.1.to_string() };
+        if aliases_to_remove.contains_key(&k) {
+            // Already an alias to another property, which happens when properties are chained
+            // through several components. Keeping this binding links it to that other property.
+            return;
+        }
         match aliases_to_invert.entry(k) {
             Entry::Occupied(_) => {
                 // TODO: maybe there are still way to optimize (three way bindings)
@@ -135,7 +140,8 @@ fn process_alias<'a>(
     let k = NamedReference { element: Rc::downgrade(&from.0), name: from.1.to_string() };
     match aliases_to_remove.entry(k) {
         Entry::Occupied(_) => {
-            todo!("TODO: what to do in that case? Does that mean it is an impossible relation")
+            // The property is the target of another alias, and its binding will be moved to that
+            // other property by the inversion, so the chain is still linked.
         }
         Entry::Vacant(e) => {
             e.insert(NamedReference { element: Rc::downgrade(&to.0), name: to.1.to_string() });
@@ -0,0 +1,69 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Pass that resolves at compile time the `if` elements whose condition is a constant, such as
+//! `if feature("debug_overlay") : DebugOverlay {}`: the element is removed if the condition is
+//! false, and becomes a regular element if it is true.
+//!
+//! This must run before the inlining, since the elements that are not repeated any more may need
+//! to be inlined.
+
+use crate::expression_tree::Expression;
+use crate::object_tree::*;
+use crate::typeregister::Type;
+use by_address::ByAddress;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+pub fn remove_constant_conditions(component: &Rc<Component>) {
+    remove_constant_conditions_in_component(component, &mut HashSet::new())
+}
+
+fn remove_constant_conditions_in_component(
+    component: &Rc<Component>,
+    visited: &mut HashSet<ByAddress<Rc<Component>>>,
+) {
+    if !visited.insert(ByAddress(component.clone())) {
+        return;
+    }
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        let mut elem = elem.borrow_mut();
+        elem.children.retain(|child| constant_condition(child) != Some(false));
+        for child in &elem.children {
+            if constant_condition(child) == Some(true) {
+                child.borrow_mut().repeated = None;
+            }
+        }
+        if let Type::Component(base) = &elem.base_type {
+            remove_constant_conditions_in_component(base, visited);
+        }
+    });
+}
+
+/// Returns the value of the condition of a conditional element, if it is known at compile time
+fn constant_condition(elem: &ElementRc) -> Option<bool> {
+    match &elem.borrow().repeated {
+        Some(repeated) if repeated.is_conditional_element => constant_value(&repeated.model),
+        _ => None,
+    }
+}
+
+fn constant_value(expr: &Expression) -> Option<bool> {
+    match expr {
+        Expression::BoolLiteral(value) => Some(*value),
+        Expression::UnaryOp { sub, op: '!' } => constant_value(sub).map(|value| !value),
+        Expression::BinaryExpression { lhs, rhs, op: '&' } => {
+            Some(constant_value(lhs)? && constant_value(rhs)?)
+        }
+        Expression::BinaryExpression { lhs, rhs, op: '|' } => {
+            Some(constant_value(lhs)? || constant_value(rhs)?)
+        }
+        _ => None,
+    }
+}
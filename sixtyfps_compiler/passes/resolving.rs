@@ -14,13 +14,13 @@ LICENSE END */
 //!
 //! Most of the code for the resolving actualy lies in the expression_tree module
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCategory};
 use crate::expression_tree::*;
 use crate::object_tree::*;
 use crate::parser::{syntax_nodes, SyntaxKind, SyntaxNodeWithSourceFile};
 use crate::typeregister::Type;
 use by_address::ByAddress;
-use std::{collections::HashMap, collections::HashSet, rc::Rc};
+use std::{collections::BTreeMap, collections::HashMap, collections::HashSet, rc::Rc};
 
 #[derive(Default)]
 /// Helper type to trace through a document and locate all the used components.
@@ -64,15 +64,27 @@ impl ComponentCollection {
 #[derive(Clone)]
 struct ComponentScope(Vec<ElementRc>);
 
+/// For the root element of each component and each repeated element, the elements that can be
+/// looked up by id from there. (See `element_ids`)
+type ElementIds = HashMap<ByAddress<ElementRc>, HashMap<String, ElementRc>>;
+
 fn resolve_expression(
     expr: &mut Expression,
     property_type: Type,
     scope: &ComponentScope,
+    element_ids: &ElementIds,
+    features: &[String],
     diag: &mut BuildDiagnostics,
 ) {
     if let Expression::Uncompiled(node) = expr {
-        let mut lookup_ctx =
-            LookupCtx { property_type, component_scope: &scope.0, diag, arguments: vec![] };
+        let mut lookup_ctx = LookupCtx {
+            property_type,
+            component_scope: &scope.0,
+            element_ids,
+            diag,
+            arguments: vec![],
+            features,
+        };
 
         let new_expr = match node.kind() {
             SyntaxKind::SignalConnection => {
@@ -99,11 +111,17 @@ fn resolve_expression(
     }
 }
 
-pub fn resolve_expressions(doc: &Document, diag: &mut BuildDiagnostics) {
+pub fn resolve_expressions(doc: &Document, features: &[String], diag: &mut BuildDiagnostics) {
     let mut all_components = ComponentCollection::default();
     all_components.add_document(&doc);
     for component in all_components.iter() {
         let scope = ComponentScope(vec![component.root_element.clone()]);
+        let mut ids = ElementIds::new();
+        recurse_elem(&component.root_element, &(), &mut |elem, _| {
+            if Rc::ptr_eq(elem, &component.root_element) || elem.borrow().repeated.is_some() {
+                ids.insert(ByAddress(elem.clone()), element_ids(elem));
+            }
+        });
 
         recurse_elem(&component.root_element, &scope, &mut |elem, scope| {
             let mut new_scope = scope.clone();
@@ -116,10 +134,10 @@ pub fn resolve_expressions(doc: &Document, diag: &mut BuildDiagnostics) {
                 if is_repeated {
                     // The first expression is always the model and it needs to be resolved with the parent scope
                     debug_assert!(elem.borrow().repeated.as_ref().is_none()); // should be none because it is taken by the visit_element_expressions function
-                    resolve_expression(expr, property_type(), scope, diag);
+                    resolve_expression(expr, property_type(), scope, &ids, features, diag);
                     is_repeated = false;
                 } else {
-                    resolve_expression(expr, property_type(), &new_scope, diag)
+                    resolve_expression(expr, property_type(), &new_scope, &ids, features, diag)
                 }
             });
             new_scope.0.pop();
@@ -137,26 +155,47 @@ struct LookupCtx<'a> {
     /// Here is the stack in which id applies
     component_scope: &'a [ElementRc],
 
+    /// The elements that can be looked up by id from the elements of the `component_scope`
+    element_ids: &'a ElementIds,
+
     /// Somewhere to report diagnostics
     diag: &'a mut BuildDiagnostics,
 
     /// The name of the arguments of the signal or function
     arguments: Vec<String>,
+
+    /// The features enabled in the compiler configuration
+    features: &'a [String],
 }
 
-fn find_element_by_id(roots: &[ElementRc], name: &str) -> Option<ElementRc> {
-    for e in roots.iter().rev() {
-        if e.borrow().id == name {
-            return Some(e.clone());
-        }
-        for x in &e.borrow().children {
-            if x.borrow().repeated.is_some() {
-                continue;
-            }
-            if let Some(x) = find_element_by_id(&[x.clone()], name) {
-                return Some(x);
+fn find_element_by_id(ctx: &LookupCtx, name: &str) -> Option<ElementRc> {
+    // The elements of the scope that are neither the root nor repeated have no entry: the
+    // elements they contain are already in the entry of the enclosing repeated element or root.
+    ctx.component_scope
+        .iter()
+        .rev()
+        .find_map(|e| ctx.element_ids.get(&ByAddress(e.clone()))?.get(name).cloned())
+}
+
+/// Returns a description of what an argument named `name` of a signal handler would hide in
+/// the lookup of its body, if anything.
+fn shadowed_by_argument(name: &str, ctx: &LookupCtx) -> Option<String> {
+    if matches!(name, "true" | "false") {
+        return Some(format!("the literal '{}'", name));
+    }
+    if matches!(name, "self" | "parent") || find_element_by_id(ctx, name).is_some() {
+        return Some(format!("the element '{}'", name));
+    }
+    for elem in ctx.component_scope.iter().rev() {
+        if let Some(repeated) = &elem.borrow().repeated {
+            if name == repeated.index_id || name == repeated.model_data_id {
+                return Some(format!("the repeater variable '{}'", name));
             }
         }
+        let property = elem.borrow().lookup_property(name);
+        if property.is_property_type() || matches!(property, Type::Signal { .. }) {
+            return Some(format!("the property '{}'", name));
+        }
     }
     None
 }
@@ -215,7 +254,41 @@ impl Expression {
             .DeclaredIdentifier()
             .map(|x| x.child_text(SyntaxKind::Identifier).unwrap_or_default())
             .collect();
-        Self::from_codeblock_node(node.CodeBlock(), ctx)
+        // The arguments take precedence over the ids and the properties in the lookup
+        for identifier in node.DeclaredIdentifier() {
+            let token = match identifier.child_token(SyntaxKind::Identifier) {
+                Some(token) => token,
+                None => continue,
+            };
+            if let Some(shadowed) = shadowed_by_argument(token.text().as_str(), ctx) {
+                ctx.diag.push_warning(
+                    format!("The argument '{}' shadows {}", token.text(), shadowed),
+                    &token,
+                    DiagnosticCategory::Style,
+                );
+            }
+        }
+        let code_block = Self::from_codeblock_node(node.CodeBlock(), ctx);
+        let return_type = match &ctx.property_type {
+            Type::Signal { return_type: Some(return_type), .. } => (**return_type).clone(),
+            _ => return code_block,
+        };
+        // The value of the last expression of the handler is the value returned by the signal
+        match code_block {
+            Expression::CodeBlock(mut sub) => {
+                match sub.pop() {
+                    Some(last) => {
+                        sub.push(last.maybe_convert_to(return_type, &node, &mut ctx.diag))
+                    }
+                    None => ctx.diag.push_error(
+                        format!("The signal handler must return a value of type {}", return_type),
+                        &node,
+                    ),
+                }
+                Expression::CodeBlock(sub)
+            }
+            code_block => code_block,
+        }
     }
 
     fn from_two_way_binding(node: syntax_nodes::TwoWayBinding, ctx: &mut LookupCtx) -> Expression {
@@ -248,6 +321,7 @@ impl Expression {
                 node.BangExpression().map(|n| Self::from_bang_expression_node(n.into(), ctx))
             })
             .or_else(|| node.QualifiedName().map(|s| Self::from_qualified_name_node(s.into(), ctx)))
+            .or_else(|| node.StringTemplate().map(|n| Self::from_string_template_node(n, ctx)))
             .or_else(|| {
                 node.child_text(SyntaxKind::StringLiteral).map(|s| {
                     unescape_string(&s).map(Self::StringLiteral).unwrap_or_else(|| {
@@ -344,6 +418,41 @@ impl Expression {
         }
     }
 
+    fn from_string_template_node(node: syntax_nodes::StringTemplate, ctx: &mut LookupCtx) -> Self {
+        let mut parts = vec![];
+        for child in node.children_with_tokens() {
+            if let Some(token) = child.as_token() {
+                if token.kind() != SyntaxKind::StringLiteral {
+                    continue;
+                }
+                // Remove the `"` or the `}` at the start, and the `"` or the `\{` at the end
+                let text = token.text().as_str();
+                let end = if crate::lexer::string_literal_continues(text) {
+                    text.len() - 2
+                } else {
+                    text.len() - 1
+                };
+                match text.get(1..end).and_then(unescape_string_content) {
+                    Some(s) if s.is_empty() => {}
+                    Some(s) => parts.push(Expression::StringLiteral(s)),
+                    None => {
+                        ctx.diag.push_error("Cannot parse string literal".into(), &token);
+                        return Self::Invalid;
+                    }
+                }
+            } else if let Some(n) = child.as_node() {
+                if n.kind() == SyntaxKind::Expression {
+                    parts.push(Self::from_expression_node(n.clone().into(), ctx).maybe_convert_to(
+                        Type::String,
+                        &n,
+                        ctx.diag,
+                    ));
+                }
+            }
+        }
+        Expression::StringInterpolation { parts }
+    }
+
     /// Perform the lookup
     fn from_qualified_name_node(node: SyntaxNodeWithSourceFile, ctx: &mut LookupCtx) -> Self {
         debug_assert_eq!(node.kind(), SyntaxKind::QualifiedName);
@@ -365,7 +474,7 @@ impl Expression {
 
         if let Some(index) = ctx.arguments.iter().position(|x| x == first_str) {
             let ty = match &ctx.property_type {
-                Type::Signal { args } | Type::Function { args, .. } => args[index].clone(),
+                Type::Signal { args, .. } | Type::Function { args, .. } => args[index].clone(),
                 _ => panic!("There should only be argument within functions or signal"),
             };
             let e = Expression::FunctionParameterReference { index, ty };
@@ -377,7 +486,7 @@ impl Expression {
             "parent" => ctx.component_scope.last().and_then(find_parent_element),
             "true" => return Self::BoolLiteral(true),
             "false" => return Self::BoolLiteral(false),
-            _ => find_element_by_id(ctx.component_scope, first_str),
+            _ => find_element_by_id(ctx, first_str),
         };
 
         if let Some(elem) = elem_opt {
@@ -458,17 +567,15 @@ impl Expression {
                 }
             }
             Type::Easing => {
-                // These value are coming from CSSn with - replaced by _
-                let value = match first_str {
-                    "linear" => Some(EasingCurve::Linear),
-                    "ease" => Some(EasingCurve::CubicBezier(0.25, 0.1, 0.25, 1.0)),
-                    "ease_in" => Some(EasingCurve::CubicBezier(0.42, 0.0, 1.0, 1.0)),
-                    "ease_in_out" => Some(EasingCurve::CubicBezier(0.42, 0.0, 0.58, 1.0)),
-                    "ease_out" => Some(EasingCurve::CubicBezier(0.0, 0.0, 0.58, 1.0)),
-                    "cubic_bezier" => todo!("Not yet implemented"),
-                    _ => None,
-                };
-                if let Some(curve) = value {
+                if first_str == "cubic_bezier" {
+                    ctx.diag.push_error(
+                        "cubic_bezier must be called with the coordinates of the two control points"
+                            .into(),
+                        &node,
+                    );
+                    return Self::Invalid;
+                }
+                if let Some(curve) = EasingCurve::from_name(first_str) {
                     return Expression::EasingCurve(curve);
                 }
             }
@@ -481,8 +588,53 @@ impl Expression {
         }
 
         // Builtin functions  FIXME: handle that in a registery or something
-        if first_str == "debug" {
-            return Expression::BuiltinFunctionReference(BuiltinFunction::Debug);
+        match first_str {
+            "debug" => return Expression::BuiltinFunctionReference(BuiltinFunction::Debug),
+            "to_uppercase" => {
+                return Expression::BuiltinFunctionReference(BuiltinFunction::StringToUppercase)
+            }
+            "to_lowercase" => {
+                return Expression::BuiltinFunctionReference(BuiltinFunction::StringToLowercase)
+            }
+            "animation_tick" => {
+                return Expression::BuiltinFunctionReference(BuiltinFunction::AnimationTick)
+            }
+            "Math" => {
+                return match it.next() {
+                    Some(f) if f.text() == "clamp" => {
+                        Expression::BuiltinFunctionReference(BuiltinFunction::Clamp)
+                    }
+                    Some(f) => {
+                        ctx.diag.push_error(format!("Unknown function 'Math.{}'", f.text()), &f);
+                        Self::Invalid
+                    }
+                    None => {
+                        ctx.diag.push_error("Cannot take reference to a namespace".into(), &node);
+                        Self::Invalid
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        if first_str == "count" {
+            // The number of rows of the model of the innermost `for`, unless a property
+            // of that name was found above
+            let model = ctx.component_scope.iter().rev().find_map(|elem| {
+                elem.borrow()
+                    .repeated
+                    .as_ref()
+                    .filter(|r| !r.is_conditional_element)
+                    .map(|r| r.model.clone())
+            });
+            if let Some(model) = model {
+                return Expression::FunctionCall {
+                    function: Box::new(Expression::BuiltinFunctionReference(
+                        BuiltinFunction::ArrayLength,
+                    )),
+                    arguments: vec![model],
+                };
+            }
         }
 
         ctx.diag.push_error(format!("Unknown unqualified identifier '{}'", first_str), &node);
@@ -494,13 +646,33 @@ impl Expression {
         node: syntax_nodes::FunctionCallExpression,
         ctx: &mut LookupCtx,
     ) -> Expression {
+        if matches!(ctx.property_type, Type::Easing)
+            && node
+                .Expression()
+                .next()
+                .and_then(|f| f.QualifiedName())
+                .and_then(|f| f.child_text(SyntaxKind::Identifier))
+                .map_or(false, |f| f == "cubic_bezier")
+        {
+            return Self::from_cubic_bezier_call(node, ctx);
+        }
+        if node
+            .Expression()
+            .next()
+            .and_then(|f| f.QualifiedName())
+            .and_then(|f| f.child_text(SyntaxKind::Identifier))
+            .map_or(false, |f| f == "feature")
+        {
+            return Self::from_feature_call(node, ctx);
+        }
+
         let mut sub_expr =
             node.Expression().map(|n| (Self::from_expression_node(n.clone(), ctx), n));
         let function = Box::new(sub_expr.next().map_or(Expression::Invalid, |e| e.0));
         let arguments = sub_expr.collect::<Vec<_>>();
 
         let arguments = match function.ty() {
-            Type::Function { args, .. } | Type::Signal { args } => {
+            Type::Function { args, .. } | Type::Signal { args, .. } => {
                 if arguments.len() != args.len() {
                     ctx.diag.push_error(
                         format!(
@@ -528,6 +700,87 @@ impl Expression {
         Expression::FunctionCall { function, arguments }
     }
 
+    /// `cubic_bezier(x1, y1, x2, y2)` as the value of an easing property. The x coordinates
+    /// of the control points must be within [0, 1], otherwise the curve is not monotonic in time.
+    fn from_cubic_bezier_call(
+        node: syntax_nodes::FunctionCallExpression,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let args = node
+            .Expression()
+            .skip(1)
+            .map(|n| {
+                let value = match Self::from_expression_node(n.clone(), ctx) {
+                    Expression::NumberLiteral(value, Unit::None) => Some(value),
+                    Expression::UnaryOp { sub, op: '-' } => match *sub {
+                        Expression::NumberLiteral(value, Unit::None) => Some(-value),
+                        _ => None,
+                    },
+                    _ => None,
+                };
+                if value.is_none() {
+                    ctx.diag.push_error("Arguments of cubic_bezier must be numbers".into(), &n);
+                }
+                (value, n)
+            })
+            .collect::<Vec<_>>();
+        if args.len() != 4 {
+            ctx.diag.push_error(
+                format!("cubic_bezier expects 4 arguments, but {} are provided", args.len()),
+                &node,
+            );
+            return Expression::Invalid;
+        }
+        for (x, n) in args.iter().step_by(2) {
+            if let Some(x) = x {
+                if !(0. ..=1.).contains(x) {
+                    ctx.diag.push_error(
+                        "The x coordinates of the control points of cubic_bezier must be between 0 and 1".into(),
+                        n,
+                    );
+                }
+            }
+        }
+        match args.as_slice() {
+            [(Some(x1), _), (Some(y1), _), (Some(x2), _), (Some(y2), _)] => {
+                Expression::EasingCurve(EasingCurve::CubicBezier(
+                    *x1 as f32, *y1 as f32, *x2 as f32, *y2 as f32,
+                ))
+            }
+            _ => Expression::Invalid,
+        }
+    }
+
+    /// `feature("name")` is true if the feature is enabled in the compiler configuration, and
+    /// false otherwise. It is evaluated at compile time.
+    fn from_feature_call(
+        node: syntax_nodes::FunctionCallExpression,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let args = node.Expression().skip(1).collect::<Vec<_>>();
+        if args.len() != 1 {
+            ctx.diag.push_error(
+                format!("feature expects 1 argument, but {} are provided", args.len()),
+                &node,
+            );
+            return Expression::Invalid;
+        }
+        match Self::from_expression_node(args[0].clone(), ctx) {
+            Expression::StringLiteral(name) => {
+                Expression::BoolLiteral(ctx.features.iter().any(|f| *f == name))
+            }
+            Expression::Invalid => Expression::Invalid,
+            _ => {
+                ctx.diag.push_error(
+                    "The argument of feature must be the name of the feature as a string literal"
+                        .into(),
+                    &args[0],
+                );
+                Expression::Invalid
+            }
+        }
+    }
+
     fn from_self_assignement_node(
         node: syntax_nodes::SelfAssignment,
         ctx: &mut LookupCtx,
@@ -550,8 +803,13 @@ impl Expression {
                 &node,
             );
         }
+        // `x *= 2` and `x /= 2` scale a value with a unit by a plain number
+        let rhs_ty = match (op, lhs.ty()) {
+            ('*', ty) | ('/', ty) if ty.default_unit().is_some() => Type::Float32,
+            (_, ty) => ty,
+        };
         let rhs = Self::from_expression_node(rhs_n.clone().into(), ctx).maybe_convert_to(
-            lhs.ty(),
+            rhs_ty,
             &rhs_n,
             &mut ctx.diag,
         );
@@ -583,12 +841,25 @@ impl Expression {
 
         let expected_ty = match operator_class(op) {
             OperatorClass::ComparisonOp => {
-                let (lhs_ty, rhs_ty) = (lhs.ty(), rhs.ty());
-                if rhs_ty.can_convert(&lhs_ty) {
-                    lhs_ty
-                } else {
-                    rhs_ty
+                let ty = common_type(lhs.ty(), rhs.ty());
+                if matches!(op, '<' | '>' | '≤' | '≥')
+                    && !matches!(
+                        ty,
+                        Type::Int32
+                            | Type::Float32
+                            | Type::Duration
+                            | Type::Length
+                            | Type::LogicalLength
+                            | Type::String
+                            | Type::Invalid
+                    )
+                {
+                    ctx.diag.push_error(
+                        format!("Values of type {} cannot be compared with <, >, <= or >=", ty),
+                        &node,
+                    );
                 }
+                ty
             }
             OperatorClass::LogicalOp => Type::Bool,
             OperatorClass::ArithmeticOp => {
@@ -603,7 +874,7 @@ impl Expression {
                                         lhs: Box::new(lhs),
                                         rhs: Box::new(rhs.maybe_convert_to(
                                             Type::Float32,
-                                            &lhs_n,
+                                            &rhs_n,
                                             &mut ctx.diag,
                                         )),
                                         op,
@@ -632,13 +903,18 @@ impl Expression {
                                         lhs: Box::new(lhs),
                                         rhs: Box::new(rhs.maybe_convert_to(
                                             Type::Float32,
-                                            &lhs_n,
+                                            &rhs_n,
                                             &mut ctx.diag,
                                         )),
                                         op,
                                     }
                                 }
                             )*
+                            $(
+                                // The plain number on the left side is most likely missing its unit
+                                ('+', _, Type::$unit) => Type::$unit,
+                                ('-', _, Type::$unit) => Type::$unit,
+                            )*
                             _ => Type::Float32,
                         }
                     };
@@ -682,10 +958,11 @@ impl Expression {
         let mut false_expr = Self::from_expression_node(false_expr_n.clone().into(), ctx);
         let (true_ty, false_ty) = (true_expr.ty(), false_expr.ty());
         if true_ty != false_ty {
-            if false_ty.can_convert(&true_ty) {
-                false_expr = false_expr.maybe_convert_to(true_ty, &false_expr_n, &mut ctx.diag);
+            let ty = common_type(true_ty.clone(), false_ty);
+            if ty == true_ty {
+                false_expr = false_expr.maybe_convert_to(ty, &false_expr_n, &mut ctx.diag);
             } else {
-                true_expr = true_expr.maybe_convert_to(false_ty, &true_expr_n, &mut ctx.diag);
+                true_expr = true_expr.maybe_convert_to(ty, &true_expr_n, &mut ctx.diag);
             }
         }
         Expression::Condition {
@@ -699,7 +976,10 @@ impl Expression {
         node: syntax_nodes::ObjectLiteral,
         ctx: &mut LookupCtx,
     ) -> Expression {
-        let values: HashMap<String, Expression> = node
+        if let Some(spread) = node.ObjectSpread() {
+            return Self::from_object_spread_node(spread, node, ctx);
+        }
+        let values: BTreeMap<String, Expression> = node
             .ObjectMember()
             .map(|n| {
                 (
@@ -712,13 +992,93 @@ impl Expression {
         Expression::Object { ty, values }
     }
 
+    /// `{ ...base, a: b }`: a copy of `base` where some of the fields are overridden.
+    ///
+    /// The base is stored in a local variable so that it is only evaluated once, and the
+    /// resulting object has the type of the base.
+    fn from_object_spread_node(
+        spread: syntax_nodes::ObjectSpread,
+        node: syntax_nodes::ObjectLiteral,
+        ctx: &mut LookupCtx,
+    ) -> Expression {
+        let base = Expression::from_expression_node(spread.Expression(), ctx);
+        let fields = match base.ty() {
+            Type::Object(fields) => fields,
+            Type::Invalid => return Expression::Invalid,
+            ty => {
+                ctx.diag.push_error(
+                    format!("Cannot spread a value of type {}, only objects can be spread", ty),
+                    &spread,
+                );
+                return Expression::Invalid;
+            }
+        };
+
+        let mut values = BTreeMap::new();
+        for member in node.ObjectMember() {
+            let name = member.child_text(SyntaxKind::Identifier).unwrap_or_default();
+            let value = Expression::from_expression_node(member.Expression(), ctx);
+            match fields.get(&name) {
+                Some(field_ty) => {
+                    let value =
+                        value.maybe_convert_to(field_ty.clone(), &member.Expression(), ctx.diag);
+                    values.insert(name, value);
+                }
+                None => ctx.diag.push_error(
+                    format!("Cannot override '{}': the base object has no such field", name),
+                    &member,
+                ),
+            }
+        }
+
+        let var_name = format!("spread_base_{}", usize::from(node.text_range().start()));
+        for name in fields.keys() {
+            if !values.contains_key(name) {
+                values.insert(
+                    name.clone(),
+                    Expression::ObjectAccess {
+                        base: Box::new(Expression::ReadLocalVariable {
+                            name: var_name.clone(),
+                            ty: Type::Object(fields.clone()),
+                        }),
+                        name: name.clone(),
+                    },
+                );
+            }
+        }
+
+        Expression::CodeBlock(vec![
+            Expression::StoreLocalVariable { name: var_name, value: Box::new(base) },
+            Expression::Object { values, ty: Type::Object(fields) },
+        ])
+    }
+
     fn from_array_node(node: syntax_nodes::Array, ctx: &mut LookupCtx) -> Expression {
         let mut values: Vec<Expression> =
             node.Expression().map(|e| Expression::from_expression_node(e, ctx)).collect();
 
         // FIXME: what's the type of an empty array ?
         // Also, be smarter about finding a common type
-        let element_ty = values.first().map_or(Type::Invalid, |e| e.ty());
+        let mut element_ty = values.first().map_or(Type::Invalid, |e| e.ty());
+        if matches!(element_ty, Type::Int32 | Type::Float32) {
+            // Like in common_type, prefer the type with a unit over a plain number
+            if let Some(ty) = values.iter().map(|e| e.ty()).find(|ty| ty.default_unit().is_some()) {
+                element_ty = ty;
+            }
+        }
+        if !values.is_empty() && values.iter().all(|e| matches!(e, Expression::Object { .. })) {
+            // The object literals may each omit different fields: use all of them, so that the
+            // missing ones get their default value.
+            let mut fields = BTreeMap::new();
+            for ty in values.iter().map(|e| e.ty()) {
+                if let Type::Object(obj) = ty {
+                    for (name, field_ty) in obj {
+                        fields.entry(name).or_insert(field_ty);
+                    }
+                }
+            }
+            element_ty = Type::Object(fields);
+        }
 
         for e in values.iter_mut() {
             *e = core::mem::replace(e, Expression::Invalid).maybe_convert_to(
@@ -732,6 +1092,19 @@ impl Expression {
     }
 }
 
+/// The type to which both operands of a comparison, or both branches of a condition, are converted.
+///
+/// When one side has a unit and the other side is a plain number, the plain number is most likely
+/// missing its unit, so it is the one that is converted and that gets the diagnostic.
+fn common_type(a: Type, b: Type) -> Type {
+    if b.can_convert(&a) || (a.default_unit().is_some() && matches!(b, Type::Int32 | Type::Float32))
+    {
+        a
+    } else {
+        b
+    }
+}
+
 fn maybe_lookup_object(
     mut base: Expression,
     mut it: impl Iterator<Item = crate::parser::SyntaxTokenWithSourceFile>,
@@ -762,6 +1135,36 @@ fn maybe_lookup_object(
                     return Expression::Invalid;
                 }
             }
+            Type::Array(_) => {
+                if next.text() == "length" {
+                    base = Expression::FunctionCall {
+                        function: Box::new(Expression::BuiltinFunctionReference(
+                            BuiltinFunction::ArrayLength,
+                        )),
+                        arguments: vec![std::mem::replace(&mut base, Expression::Invalid)],
+                    }
+                } else {
+                    ctx.diag.push_error("Arrays only have a 'length' field".into(), &next);
+                    return Expression::Invalid;
+                }
+            }
+            Type::String => {
+                let function = match next.text().as_str() {
+                    "to_uppercase" => BuiltinFunction::StringToUppercase,
+                    "to_lowercase" => BuiltinFunction::StringToLowercase,
+                    _ => {
+                        ctx.diag.push_error(
+                            "Strings only have 'to_uppercase' and 'to_lowercase' fields".into(),
+                            &next,
+                        );
+                        return Expression::Invalid;
+                    }
+                };
+                base = Expression::FunctionCall {
+                    function: Box::new(Expression::BuiltinFunctionReference(function)),
+                    arguments: vec![std::mem::replace(&mut base, Expression::Invalid)],
+                }
+            }
             _ => {
                 ctx.diag.push_error("Cannot access fields of property".into(), &next);
                 return Expression::Invalid;
@@ -824,12 +1227,38 @@ fn test_parse_color_literal() {
 }
 
 fn unescape_string(string: &str) -> Option<String> {
-    if !string.starts_with('"') || !string.ends_with('"') {
+    if !string.starts_with('"') || !string.ends_with('"') || string.len() < 2 {
         return None;
     }
-    let string = &string[1..(string.len() - 1)];
-    // TODO: remove slashes
-    return Some(string.into());
+    unescape_string_content(&string[1..(string.len() - 1)])
+}
+
+/// Replace the escape sequences (`\"`, `\\` and `\n`) of the content of a string literal.
+/// Returns None if there is an unknown escape sequence.
+fn unescape_string_content(string: &str) -> Option<String> {
+    let mut result = String::with_capacity(string.len());
+    let mut chars = string.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '"' => result.push('"'),
+            '\\' => result.push('\\'),
+            'n' => result.push('\n'),
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+#[test]
+fn test_unescape_string() {
+    assert_eq!(unescape_string(r#""hello""#), Some("hello".into()));
+    assert_eq!(unescape_string(r#""a\"b\\c\nd""#), Some("a\"b\\c\nd".into()));
+    assert_eq!(unescape_string(r#""\x""#), None);
+    assert_eq!(unescape_string(r#"""#), None);
 }
 
 fn parse_number_literal(s: String) -> Result<Expression, String> {
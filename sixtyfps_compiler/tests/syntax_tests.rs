@@ -70,6 +70,15 @@ fn process_file_source(
             parse_diagnostics,
             &compiler_config,
         );
+        // Checking without compiling must report the same diagnostics
+        let (syntax_node, parse_diagnostics) =
+            sixtyfps_compilerlib::parser::parse(source.clone(), Some(path));
+        let check_diags = sixtyfps_compilerlib::check_syntax_node(
+            syntax_node,
+            parse_diagnostics,
+            &compiler_config,
+        );
+        assert_eq!(check_diags.to_string_vec(), build_diags.to_string_vec());
         build_diags.into_iter().collect()
     } else {
         vec![parse_diagnostics]
@@ -224,3 +233,62 @@ Foo := Rectangle foo { x:0px; }
 
     Ok(())
 }
+
+#[test]
+fn warning_categories() {
+    use sixtyfps_compilerlib::diagnostics::DiagnosticCategory;
+    let source = r#"
+Foo := Rectangle {
+    signal moved(int);
+    moved(width) => { }
+    Rectangle { animate x { duration: 100ms; } }
+    a := Rectangle { }
+    a := Rectangle { }
+}
+    "#;
+    let (syntax_node, parse_diagnostics) =
+        sixtyfps_compilerlib::parser::parse(source.into(), Some(std::path::Path::new("fake.60")));
+    let build_diags = sixtyfps_compilerlib::check_syntax_node(
+        syntax_node,
+        parse_diagnostics,
+        &sixtyfps_compilerlib::CompilerConfiguration::default(),
+    );
+    assert!(!build_diags.has_error());
+    let counts = build_diags.warning_count_by_category();
+    assert_eq!(counts.get(&DiagnosticCategory::Unused), Some(&1));
+    assert_eq!(counts.get(&DiagnosticCategory::Correctness), Some(&2));
+    assert_eq!(counts.get(&DiagnosticCategory::Style), Some(&1));
+}
+
+#[test]
+fn in_out_transition() {
+    let source = r#"
+Foo := Rectangle {
+    property<bool> checked;
+    states [ checked when checked: { color: blue; } ]
+    transitions [ in-out checked: { animate color { duration: 300ms; } } ]
+}
+    "#;
+    let (syntax_node, parse_diagnostics) =
+        sixtyfps_compilerlib::parser::parse(source.into(), Some(std::path::Path::new("fake.60")));
+    let (doc, build_diags) = sixtyfps_compilerlib::compile_syntax_node(
+        syntax_node,
+        parse_diagnostics,
+        &sixtyfps_compilerlib::CompilerConfiguration::default(),
+    );
+    assert!(!build_diags.has_error());
+    let root = doc.root_component.root_element.borrow();
+    // The transition is expanded into one transition for each direction
+    assert_eq!(root.transitions.iter().map(|t| t.is_out).collect::<Vec<_>>(), vec![false, true]);
+    for t in &root.transitions {
+        assert_eq!(t.state_id, "checked");
+        assert_eq!(t.property_animations.len(), 1);
+        let (prop, anim) = &t.property_animations[0];
+        assert_eq!(prop.name, "color");
+        assert!(anim.borrow().bindings.contains_key("duration"));
+    }
+    assert!(!std::rc::Rc::ptr_eq(
+        &root.transitions[0].property_animations[0].1,
+        &root.transitions[1].property_animations[0].1
+    ));
+}
@@ -18,7 +18,7 @@ use crate::parser::{
     syntax_nodes::{Document, ImportSpecifier},
     SyntaxKind, SyntaxTokenWithSourceFile,
 };
-use crate::typeregister::TypeRegister;
+use crate::typeregister::{Type, TypeRegister};
 use crate::CompilerConfiguration;
 
 pub struct OpenFile<'a> {
@@ -119,6 +119,8 @@ fn load_dependency<'a>(
 
     let exports = doc.exports();
 
+    // Only components can be exported: .60 files have no syntax to declare enumerations, and the
+    // built-in enumerations are already known to every type register, so they never need importing.
     for import_name in imported_types.type_names {
         let imported_type = exports.iter().find_map(|(export_name, component)| {
             if import_name.external_name == *export_name {
@@ -143,9 +145,41 @@ fn load_dependency<'a>(
             }
         };
 
+        if let Some(Type::Component(existing)) =
+            registry_to_populate.borrow().lookup_local(&import_name.internal_name)
+        {
+            if !Rc::ptr_eq(&existing, &imported_type) {
+                importer_diagnostics.push_error(
+                    format!(
+                        "Ambiguous import: {} is imported from several files. Use 'as' to rename it, or import the file with 'import * as'",
+                        import_name.internal_name
+                    ),
+                    &imported_types.import_token,
+                );
+                continue;
+            }
+        }
+
         registry_to_populate.borrow_mut().add_with_name(import_name.internal_name, imported_type);
     }
 
+    for namespace in imported_types.namespaces {
+        if registry_to_populate.borrow().lookup_local_namespace(&namespace).is_some() {
+            importer_diagnostics.push_error(
+                format!("Ambiguous import: the namespace {} is used by several imports", namespace),
+                &imported_types.import_token,
+            );
+            continue;
+        }
+        let mut namespace_registry = TypeRegister::default();
+        for (export_name, component) in exports.iter() {
+            namespace_registry.add_with_name(export_name.clone(), component.clone());
+        }
+        registry_to_populate
+            .borrow_mut()
+            .add_namespace(namespace, Rc::new(RefCell::new(namespace_registry)));
+    }
+
     build_diagnostics.add(dependency_diagnostics);
 }
 pub struct ImportedName {
@@ -157,21 +191,26 @@ pub struct ImportedName {
 
 impl ImportedName {
     pub fn extract_imported_names(import: &ImportSpecifier) -> impl Iterator<Item = ImportedName> {
-        import.ImportIdentifierList().ImportIdentifier().map(|importident| {
-            let external_name = importident.ExternalName().text().to_string().trim().to_string();
-
-            let internal_name = match importident.InternalName() {
-                Some(name_ident) => name_ident.text().to_string().trim().to_string(),
-                None => external_name.clone(),
-            };
-
-            ImportedName { internal_name, external_name }
-        })
+        import.ImportIdentifierList().into_iter().flat_map(|l| l.ImportIdentifier()).map(
+            |importident| {
+                let external_name =
+                    importident.ExternalName().text().to_string().trim().to_string();
+
+                let internal_name = match importident.InternalName() {
+                    Some(name_ident) => name_ident.text().to_string().trim().to_string(),
+                    None => external_name.clone(),
+                };
+
+                ImportedName { internal_name, external_name }
+            },
+        )
     }
 }
 
 struct ImportedTypes {
     pub type_names: Vec<ImportedName>,
+    /// Names of the namespaces under which all the exports of the file are imported
+    pub namespaces: Vec<String>,
     pub import_token: SyntaxTokenWithSourceFile,
     pub source_code: String,
 }
@@ -235,6 +274,7 @@ fn collect_dependencies<'a>(
                     }
                     vacant_entry.insert(ImportedTypes {
                         type_names: vec![],
+                        namespaces: vec![],
                         import_token: import_uri,
                         source_code,
                     })
@@ -245,6 +285,11 @@ fn collect_dependencies<'a>(
             };
 
             dependency_entry.type_names.extend(ImportedName::extract_imported_names(&import));
+            dependency_entry.namespaces.extend(
+                import
+                    .ImportNamespace()
+                    .map(|ns| ns.InternalName().text().to_string().trim().to_string()),
+            );
         } else {
             doc_diagnostics.push_error(
                 format!(
@@ -292,3 +337,65 @@ fn test_dependency_loading() {
     assert!(!test_diags.has_error());
     assert!(!build_diagnostics.has_error());
 }
+
+#[test]
+fn test_ambiguous_import() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let mut incdir = test_source_path.clone();
+    incdir.push("incpath");
+
+    let compiler_config = CompilerConfiguration { include_paths: &[incdir], ..Default::default() };
+
+    let mut main_test_path = test_source_path.clone();
+    main_test_path.push("ambiguous_import.60");
+
+    let (doc_node, mut test_diags) = crate::parser::parse_file(main_test_path.clone()).unwrap();
+
+    let doc_node: Document = doc_node.into();
+
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&TypeRegister::builtin())));
+
+    let mut build_diagnostics = BuildDiagnostics::default();
+
+    load_dependencies_recursively(
+        &doc_node,
+        &mut test_diags,
+        &registry,
+        &compiler_config,
+        None,
+        &mut build_diagnostics,
+    );
+
+    assert!(test_diags.has_error());
+    assert!(test_diags.inner.iter().any(|d| d.to_string().starts_with("Ambiguous import")));
+}
+
+#[test]
+fn test_imported_enum_property() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let compiler_config = CompilerConfiguration::default();
+
+    let mut main_test_path = test_source_path.clone();
+    main_test_path.push("imported_enum_property.60");
+
+    // The enumeration properties of an imported component can be set in the importing file
+    let (doc_node, test_diags) = crate::parser::parse_file(main_test_path.clone()).unwrap();
+    let build_diagnostics = crate::check_syntax_node(doc_node, test_diags, &compiler_config);
+    assert!(!build_diagnostics.has_error(), "{:?}", build_diagnostics.to_string_vec());
+
+    // ... but the enumerations themselves are not exported, so they cannot be imported
+    let (doc_node, test_diags) = crate::parser::parse(
+        "import { TextHorizontalAlignment } from \"./aligned_text.60\";\nMain := Rectangle {}\n"
+            .into(),
+        Some(&main_test_path),
+    );
+    let build_diagnostics = crate::check_syntax_node(doc_node, test_diags, &compiler_config);
+    assert!(build_diagnostics
+        .to_string_vec()
+        .iter()
+        .any(|d| d.contains("No exported type called TextHorizontalAlignment")));
+}
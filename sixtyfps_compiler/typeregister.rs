@@ -10,7 +10,7 @@ LICENSE END */
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::{cell::RefCell, fmt::Display, rc::Rc};
 
-use crate::expression_tree::{Expression, Unit};
+use crate::expression_tree::{BuiltinFunction, Expression, Unit};
 use crate::object_tree::Component;
 
 #[derive(Debug, Clone)]
@@ -24,6 +24,8 @@ pub enum Type {
     Native(Rc<NativeClass>),
 
     Signal {
+        /// The type returned by the handler, `None` for the signals that do not return a value
+        return_type: Option<Box<Type>>,
         args: Vec<Type>,
     },
     Function {
@@ -60,7 +62,10 @@ impl core::cmp::PartialEq for Type {
             (Type::Component(a), Type::Component(b)) => Rc::ptr_eq(a, b),
             (Type::Builtin(a), Type::Builtin(b)) => Rc::ptr_eq(a, b),
             (Type::Native(a), Type::Native(b)) => Rc::ptr_eq(a, b),
-            (Type::Signal { args: a }, Type::Signal { args: b }) => a == b,
+            (
+                Type::Signal { return_type: lhs_rt, args: lhs_args },
+                Type::Signal { return_type: rhs_rt, args: rhs_args },
+            ) => lhs_rt == rhs_rt && lhs_args == rhs_args,
             (
                 Type::Function { return_type: lhs_rt, args: lhs_args },
                 Type::Function { return_type: rhs_rt, args: rhs_args },
@@ -93,7 +98,7 @@ impl Display for Type {
             Type::Component(c) => c.id.fmt(f),
             Type::Builtin(b) => b.native_class.class_name.fmt(f),
             Type::Native(b) => b.class_name.fmt(f),
-            Type::Signal { args } => {
+            Type::Signal { return_type, args } => {
                 write!(f, "signal")?;
                 if !args.is_empty() {
                     write!(f, "(")?;
@@ -105,6 +110,9 @@ impl Display for Type {
                     }
                     write!(f, ")")?
                 }
+                if let Some(rt) = return_type {
+                    write!(f, " -> {}", rt)?;
+                }
                 Ok(())
             }
             Type::Function { return_type, args } => {
@@ -288,6 +296,8 @@ impl Type {
             | (Type::Array(_), Type::Model)
             | (Type::Float32, Type::Model)
             | (Type::Int32, Type::Model)
+            | (Type::Int32, Type::Color)
+            | (Type::Color, Type::Int32)
             | (Type::Length, Type::LogicalLength)
             | (Type::LogicalLength, Type::Length) => true,
             (Type::Object(a), Type::Object(b)) if can_convert_object(a, b) => true,
@@ -340,6 +350,9 @@ pub struct NativeClass {
     pub class_name: String,
     pub vtable_symbol: String,
     pub properties: HashMap<String, Type>,
+    /// The properties that are only written by the runtime, for example in response to input
+    /// events, so that setting them from outside has no lasting effect.
+    pub output_properties: HashSet<String>,
     pub cpp_type: Option<String>,
     pub rust_type_constructor: Option<String>,
 }
@@ -463,6 +476,7 @@ pub fn reserved_property(name: &str) -> Type {
         ("clip", Type::Bool),
         ("opacity", Type::Float32),
         ("visible", Type::Bool),
+        ("collapse_when_hidden", Type::Bool),
         ("enabled", Type::Bool),
         ("col", Type::Int32),
         ("row", Type::Int32),
@@ -487,6 +501,8 @@ pub struct TypeRegister {
     /// Map from a context restricted type to the list of contexts (parent type) it is allowed in. This is
     /// used to construct helpful error messages, such as "Row can only be within a GridLayout element".
     context_restricted_types: HashMap<String, HashSet<String>>,
+    /// The registries of the files imported with `import * as Name from "file.60";`, by name.
+    namespaces: HashMap<String, Rc<RefCell<TypeRegister>>>,
     parent_registry: Option<Rc<RefCell<TypeRegister>>>,
 }
 
@@ -514,24 +530,39 @@ impl TypeRegister {
             })
         };
 
+        let image_fit = declare_enum("ImageFit", &["fill", "contain", "cover", "none"]);
         let text_horizontal_alignment =
             declare_enum("TextHorizontalAlignment", &["align_left", "align_center", "align_right"]);
         let text_vertical_alignment =
             declare_enum("TextVerticalAlignment", &["align_top", "align_center", "align_bottom"]);
-
+        let text_direction = declare_enum("TextDirection", &["ltr", "rtl"]);
+        let text_decoration =
+            declare_enum("TextDecoration", &["none", "underline", "strikethrough"]);
+        let font_style = declare_enum("FontStyle", &["normal", "italic"]);
+
+        let native_class_with_output_properties =
+            |tr: &mut TypeRegister,
+             name: &str,
+             properties: &[(&str, Type)],
+             output_properties: &[&str],
+             default_bindings: &[(&str, Expression)]| {
+                let mut native = NativeClass::new_with_properties(
+                    name,
+                    properties.iter().map(|(n, t)| (n.to_string(), t.clone())),
+                );
+                native.output_properties =
+                    output_properties.iter().map(|p| p.to_string()).collect();
+                let mut builtin = BuiltinElement::new(Rc::new(native));
+                for (prop, expr) in default_bindings {
+                    builtin.default_bindings.insert(prop.to_string(), expr.clone());
+                }
+                tr.types.insert(name.to_string(), Type::Builtin(Rc::new(builtin)));
+            };
         let native_class = |tr: &mut TypeRegister,
                             name: &str,
                             properties: &[(&str, Type)],
                             default_bindings: &[(&str, Expression)]| {
-            let native = Rc::new(NativeClass::new_with_properties(
-                name,
-                properties.iter().map(|(n, t)| (n.to_string(), t.clone())),
-            ));
-            let mut builtin = BuiltinElement::new(native);
-            for (prop, expr) in default_bindings {
-                builtin.default_bindings.insert(prop.to_string(), expr.clone());
-            }
-            tr.types.insert(name.to_string(), Type::Builtin(Rc::new(builtin)));
+            native_class_with_output_properties(tr, name, properties, &[], default_bindings)
         };
 
         let mut rectangle = NativeClass::new("Rectangle");
@@ -563,6 +594,7 @@ impl TypeRegister {
                 ("y", Type::Length),
                 ("width", Type::Length),
                 ("height", Type::Length),
+                ("image_fit", Type::Enumeration(image_fit)),
             ],
             &[],
         );
@@ -574,24 +606,32 @@ impl TypeRegister {
                 ("text", Type::String),
                 ("font_family", Type::String),
                 ("font_size", Type::Length),
+                ("font_weight", Type::Int32),
+                ("font_style", Type::Enumeration(font_style)),
                 ("color", Type::Color),
                 ("horizontal_alignment", Type::Enumeration(text_horizontal_alignment.clone())),
                 ("vertical_alignment", Type::Enumeration(text_vertical_alignment.clone())),
+                ("direction", Type::Enumeration(text_direction)),
+                ("decoration", Type::Enumeration(text_decoration)),
+                ("letter_spacing", Type::Length),
                 ("x", Type::Length),
                 ("y", Type::Length),
                 ("width", Type::Length),
                 ("height", Type::Length),
             ],
-            &[(
-                "color",
-                Expression::Cast {
-                    from: Box::new(Expression::NumberLiteral(0xff000000u32 as _, Unit::None)),
-                    to: Type::Color,
-                },
-            )],
+            &[
+                (
+                    "color",
+                    Expression::Cast {
+                        from: Box::new(Expression::NumberLiteral(0xff000000u32 as _, Unit::None)),
+                        to: Type::Color,
+                    },
+                ),
+                ("font_weight", Expression::NumberLiteral(400., Unit::None)),
+            ],
         );
 
-        native_class(
+        native_class_with_output_properties(
             &mut r,
             "TouchArea",
             &[
@@ -604,8 +644,11 @@ impl TypeRegister {
                 ("mouse_y", Type::Length),
                 ("pressed_x", Type::Length),
                 ("pressed_y", Type::Length),
-                ("clicked", Type::Signal { args: vec![] }),
+                ("pinch_delta", Type::Length),
+                ("clicked", Type::Signal { return_type: None, args: vec![] }),
+                ("pinched", Type::Signal { return_type: None, args: vec![] }),
             ],
+            &["pressed", "mouse_x", "mouse_y", "pressed_x", "pressed_y", "pinch_delta"],
             &[],
         );
 
@@ -628,9 +671,48 @@ impl TypeRegister {
             &[("interactive", Expression::BoolLiteral(true))],
         );
 
-        native_class(&mut r, "Window", &[("width", Type::Length), ("height", Type::Length)], &[]);
+        native_class_with_output_properties(
+            &mut r,
+            "Window",
+            &[
+                ("width", Type::Length),
+                ("height", Type::Length),
+                ("background", Type::Color),
+                ("scale_factor", Type::Float32),
+                ("no_decorations", Type::Bool),
+                ("always_on_top", Type::Bool),
+                ("resizable", Type::Bool),
+                (
+                    "resized",
+                    Type::Signal { return_type: None, args: vec![Type::Length, Type::Length] },
+                ),
+                ("shown", Type::Signal { return_type: None, args: vec![] }),
+                ("file_dropped", Type::Signal { return_type: None, args: vec![Type::String] }),
+                ("files_dropped", Type::Signal { return_type: None, args: vec![] }),
+            ],
+            &["scale_factor"],
+            &[
+                (
+                    "background",
+                    Expression::Cast {
+                        from: Box::new(Expression::NumberLiteral(0xffffffffu32 as _, Unit::None)),
+                        to: Type::Color,
+                    },
+                ),
+                (
+                    "scale_factor",
+                    Expression::FunctionCall {
+                        function: Box::new(Expression::BuiltinFunctionReference(
+                            BuiltinFunction::GetWindowScaleFactor,
+                        )),
+                        arguments: vec![],
+                    },
+                ),
+                ("resizable", Expression::BoolLiteral(true)),
+            ],
+        );
 
-        native_class(
+        native_class_with_output_properties(
             &mut r,
             "TextInput",
             &[
@@ -650,8 +732,11 @@ impl TypeRegister {
                 ("cursor_position", Type::Int32),
                 ("anchor_position", Type::Int32),
                 ("has_focus", Type::Bool),
-                ("accepted", Type::Signal { args: vec![] }),
+                ("accepted", Type::Signal { return_type: None, args: vec![] }),
+                ("key_pressed", Type::Signal { return_type: None, args: vec![Type::String] }),
+                ("key_released", Type::Signal { return_type: None, args: vec![Type::String] }),
             ],
+            &["has_focus"],
             &[
                 (
                     "color",
@@ -695,9 +780,9 @@ impl TypeRegister {
         path_class.properties.insert("y".to_owned(), Type::Length);
         path_class.properties.insert("width".to_owned(), Type::Length);
         path_class.properties.insert("height".to_owned(), Type::Length);
-        path_class.properties.insert("fill_color".to_owned(), Type::Color);
-        path_class.properties.insert("stroke_color".to_owned(), Type::Color);
-        path_class.properties.insert("stroke_width".to_owned(), Type::Float32);
+        path_class.properties.insert("fill".to_owned(), Type::Color);
+        path_class.properties.insert("stroke".to_owned(), Type::Color);
+        path_class.properties.insert("stroke_width".to_owned(), Type::Length);
         let path = Rc::new(path_class);
         let mut path_elem = BuiltinElement::new(path);
         path_elem.properties.insert("commands".to_owned(), Type::String);
@@ -789,7 +874,7 @@ impl TypeRegister {
                 ("height", Type::Length),
                 ("text", Type::String),
                 ("pressed", Type::Bool),
-                ("clicked", Type::Signal { args: vec![] }),
+                ("clicked", Type::Signal { return_type: None, args: vec![] }),
             ],
             &[],
         );
@@ -803,7 +888,7 @@ impl TypeRegister {
                 ("height", Type::Length),
                 ("text", Type::String),
                 ("checked", Type::Bool),
-                ("toggled", Type::Signal { args: vec![] }),
+                ("toggled", Type::Signal { return_type: None, args: vec![] }),
             ],
             &[],
         );
@@ -896,10 +981,32 @@ impl TypeRegister {
             .unwrap_or_default()
     }
 
+    /// Lookup a type registered in this registry, without looking in the parent registries
+    pub fn lookup_local(&self, name: &str) -> Option<Type> {
+        self.types.get(name).cloned()
+    }
+
+    /// Lookup a namespace registered in this registry, without looking in the parent registries
+    pub fn lookup_local_namespace(&self, name: &str) -> Option<Rc<RefCell<TypeRegister>>> {
+        self.namespaces.get(name).cloned()
+    }
+
+    fn lookup_namespace(&self, name: &str) -> Option<Rc<RefCell<TypeRegister>>> {
+        self.namespaces.get(name).cloned().or_else(|| {
+            self.parent_registry.as_ref().and_then(|r| r.borrow().lookup_namespace(name))
+        })
+    }
+
     fn lookup_element_as_result(
         &self,
         name: &str,
     ) -> Result<Type, HashMap<String, HashSet<String>>> {
+        if let Some(pos) = name.find('.') {
+            return match self.lookup_namespace(&name[..pos]) {
+                Some(namespace) => namespace.borrow().lookup_element_as_result(&name[pos + 1..]),
+                None => Err(HashMap::new()),
+            };
+        }
         match self.types.get(name).cloned() {
             Some(ty) => Ok(ty),
             None => match &self.parent_registry {
@@ -935,11 +1042,16 @@ impl TypeRegister {
         })
     }
 
+    /// Lookup a type by its qualified name. All but the last members of the name are namespaces.
     pub fn lookup_qualified<Member: AsRef<str>>(&self, qualified: &[Member]) -> Type {
-        if qualified.len() != 1 {
-            return Type::Invalid;
+        match qualified {
+            [] => Type::Invalid,
+            [name] => self.lookup(name.as_ref()),
+            [namespace, rest @ ..] => self
+                .lookup_namespace(namespace.as_ref())
+                .map(|namespace| namespace.borrow().lookup_qualified(rest))
+                .unwrap_or_default(),
         }
-        self.lookup(qualified[0].as_ref())
     }
 
     pub fn add(&mut self, comp: Rc<crate::object_tree::Component>) {
@@ -950,6 +1062,10 @@ impl TypeRegister {
         self.types.insert(name, Type::Component(comp));
     }
 
+    pub fn add_namespace(&mut self, name: String, registry: Rc<RefCell<TypeRegister>>) {
+        self.namespaces.insert(name, registry);
+    }
+
     pub fn property_animation_type_for_property(&self, property_type: Type) -> Type {
         if self.supported_property_animation_types.contains(&property_type.to_string()) {
             self.property_animation_type.clone()
@@ -9,7 +9,7 @@
 LICENSE END */
 #![warn(missing_docs)]
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 
 /// The representation of an easing curve, for animations
 #[repr(C, u32)]
@@ -28,20 +28,37 @@ impl Default for EasingCurve {
     }
 }
 
+/// A callback called for every animation frame, with the time elapsed since the previous frame.
+/// It returns true if it needs more frames, even if there is no other active animation.
+type TickCallback = Box<dyn FnMut(instant::Duration) -> bool>;
+
 /// The AnimationDriver
 pub struct AnimationDriver {
     /// Indicate whether there are any active animations that require a future call to update_animations.
     active_animations: Cell<bool>,
     global_instant: core::pin::Pin<Box<crate::Property<instant::Instant>>>,
     initial_instant: instant::Instant,
+    tick_callbacks: RefCell<Vec<TickCallback>>,
+    /// When the animations are paused, the global instant is not advanced
+    paused: Cell<bool>,
+    /// The last tick given to update_animations. The global instant lags behind it by the
+    /// time spent while the animations were paused.
+    last_tick: Cell<instant::Instant>,
+    /// The time during which the animations were paused
+    paused_duration: Cell<instant::Duration>,
 }
 
 impl Default for AnimationDriver {
     fn default() -> Self {
+        let now = instant::Instant::now();
         AnimationDriver {
             active_animations: Cell::default(),
-            global_instant: Box::pin(crate::Property::new(instant::Instant::now())),
-            initial_instant: instant::Instant::now(),
+            global_instant: Box::pin(crate::Property::new(now)),
+            initial_instant: now,
+            tick_callbacks: Default::default(),
+            paused: Cell::new(false),
+            last_tick: Cell::new(now),
+            paused_duration: Cell::default(),
         }
     }
 }
@@ -49,15 +66,84 @@ impl Default for AnimationDriver {
 impl AnimationDriver {
     /// Iterates through all animations based on the new time tick and updates their state. This should be called by
     /// the windowing system driver for every frame.
+    ///
+    /// While the animations are paused, the time elapsed since the previous tick is not
+    /// applied to the animations.
     pub fn update_animations(&self, new_tick: instant::Instant) {
-        self.active_animations.set(false);
+        let last_tick = self.last_tick.get();
+        if new_tick > last_tick {
+            self.last_tick.set(new_tick);
+        }
+        if self.paused.get() {
+            if new_tick > last_tick {
+                self.paused_duration.set(self.paused_duration.get() + (new_tick - last_tick));
+            }
+            return;
+        }
+        let new_tick = new_tick - self.paused_duration.get();
+        let previous_tick = self.global_instant.as_ref().get_untracked();
+        let was_active = self.active_animations.replace(false);
         self.global_instant.as_ref().set(new_tick);
+        if was_active {
+            let elapsed = if new_tick > previous_tick {
+                new_tick - previous_tick
+            } else {
+                instant::Duration::default()
+            };
+            self.call_tick_callbacks(elapsed);
+        }
+    }
+
+    fn call_tick_callbacks(&self, elapsed: instant::Duration) {
+        // Take the callbacks out so that they can register new callbacks
+        let mut callbacks = self.tick_callbacks.take();
+        let mut keep_alive = false;
+        for callback in callbacks.iter_mut() {
+            keep_alive |= callback(elapsed);
+        }
+        callbacks.append(&mut self.tick_callbacks.borrow_mut());
+        self.tick_callbacks.replace(callbacks);
+        if keep_alive {
+            self.set_has_active_animations();
+        }
+    }
+
+    /// Register a callback that is called for every animation frame with the time elapsed since
+    /// the previous frame. It is only called while there are active animations, unless it keeps
+    /// the animations active by returning true.
+    pub fn add_tick_callback(&self, callback: impl FnMut(instant::Duration) -> bool + 'static) {
+        self.tick_callbacks.borrow_mut().push(Box::new(callback));
+        // Make sure the callback is called at least once
+        self.set_has_active_animations();
     }
 
     /// Returns true if there are any active or ready animations. This is used by the windowing system to determine
-    /// if a new animation frame is required or not. Returns false otherwise.
+    /// if a new animation frame is required or not. Returns false otherwise, or if the animations are paused.
     pub fn has_active_animations(&self) -> bool {
-        self.active_animations.get()
+        self.active_animations.get() && !self.paused.get()
+    }
+
+    /// Pause or resume all the animations. `tick` is the current time, as it would be given
+    /// to update_animations.
+    ///
+    /// While paused, the animations are frozen in their current state, and they continue from
+    /// where they were when resumed: the time spent paused is not taken into account.
+    pub fn set_paused(&self, paused: bool, tick: instant::Instant) {
+        if !paused && self.paused.get() {
+            // Account for the time elapsed since the last tick, which was spent paused
+            self.update_animations(tick);
+        }
+        self.paused.set(paused);
+    }
+
+    /// Returns true if the animations are paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// The last tick given to update_animations, which includes the time spent paused
+    pub(crate) fn last_tick(&self) -> instant::Instant {
+        self.last_tick.get()
     }
 
     /// Tell the driver that there are active animations
@@ -79,6 +165,45 @@ pub fn current_tick() -> instant::Instant {
     CURRENT_ANIMATION_DRIVER.with(|driver| driver.current_tick())
 }
 
+/// The time of the animations, in milliseconds since the start of the program, which is the value of
+/// the `animation_tick()` function of the `.60` language. It does not advance while the animations
+/// are paused.
+///
+/// Reading it registers the current binding as a dependency, and keeps the animations active, so
+/// that the binding is evaluated again for every frame for as long as it is used.
+pub fn animation_tick() -> u64 {
+    CURRENT_ANIMATION_DRIVER.with(|driver| {
+        driver.set_has_active_animations();
+        (driver.current_tick() - driver.initial_instant).as_millis() as u64
+    })
+}
+
+/// Same as [`animation_tick`], for the generated C++ code.
+#[no_mangle]
+pub extern "C" fn sixtyfps_animation_tick() -> u64 {
+    animation_tick()
+}
+
+/// Register a callback that is called for every animation frame with the time elapsed since
+/// the previous frame, while there are active animations.
+/// If the callback returns true, more frames are scheduled even if no other animation is active.
+/// This allows to drive custom animations in lockstep with the rendering.
+pub fn on_animation_tick(callback: impl FnMut(instant::Duration) -> bool + 'static) {
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.add_tick_callback(callback))
+}
+
+/// Pause or resume all the animations. While paused, the animations are frozen in their current
+/// state and no new animation frames are scheduled. Once resumed, they continue from where
+/// they were. This can be used for debugging, or to save battery while the window is hidden.
+pub fn set_animations_paused(paused: bool) {
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.set_paused(paused, now(driver)))
+}
+
+/// Returns true if the animations were paused with [`set_animations_paused`]
+pub fn animations_paused() -> bool {
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.is_paused())
+}
+
 /// map a value betwen 0 and 1 to another value between 0 and 1 according to the curve
 pub fn easing_curve(curve: &EasingCurve, value: f32) -> f32 {
     match curve {
@@ -130,18 +255,74 @@ fn easing_test() {
 }
 */
 
+/// The current time, slowed down if the SIXTYFPS_SLOW_ANIMATIONS environment variable is set
+fn now(driver: &AnimationDriver) -> instant::Instant {
+    match std::env::var("SIXTYFPS_SLOW_ANIMATIONS") {
+        Err(_) => instant::Instant::now(),
+        Ok(val) => {
+            let factor = val.parse().unwrap_or(2.);
+            driver.initial_instant
+                + (instant::Instant::now() - driver.initial_instant).div_f32(factor)
+        }
+    }
+}
+
 /// Update the glibal animation time to the current time
 pub(crate) fn update_animations() {
-    CURRENT_ANIMATION_DRIVER.with(|driver| {
-        match std::env::var("SIXTYFPS_SLOW_ANIMATIONS") {
-            Err(_) => driver.update_animations(instant::Instant::now()),
-            Ok(val) => {
-                let factor = val.parse().unwrap_or(2.);
-                driver.update_animations(
-                    driver.initial_instant
-                        + (instant::Instant::now() - driver.initial_instant).div_f32(factor),
-                )
-            }
-        };
+    CURRENT_ANIMATION_DRIVER.with(|driver| driver.update_animations(now(driver)));
+}
+
+#[test]
+fn tick_callback_test() {
+    use std::rc::Rc;
+    let driver = AnimationDriver::default();
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    let keep_alive = Rc::new(Cell::new(true));
+    driver.add_tick_callback({
+        let calls = calls.clone();
+        let keep_alive = keep_alive.clone();
+        move |elapsed| {
+            calls.borrow_mut().push(elapsed);
+            keep_alive.get()
+        }
     });
+    let ms = instant::Duration::from_millis;
+    let start = driver.current_tick();
+    driver.update_animations(start + ms(10));
+    assert_eq!(*calls.borrow(), vec![ms(10)]);
+    assert!(driver.has_active_animations());
+    keep_alive.set(false);
+    driver.update_animations(start + ms(25));
+    assert_eq!(*calls.borrow(), vec![ms(10), ms(15)]);
+    assert!(!driver.has_active_animations());
+    // Not called when there are no active animations
+    driver.update_animations(start + ms(40));
+    assert_eq!(calls.borrow().len(), 2);
+    // Called again when an animation is running
+    driver.set_has_active_animations();
+    driver.update_animations(start + ms(50));
+    assert_eq!(*calls.borrow(), vec![ms(10), ms(15), ms(10)]);
+}
+
+#[test]
+fn pause_test() {
+    let driver = AnimationDriver::default();
+    let ms = instant::Duration::from_millis;
+    let start = driver.current_tick();
+    driver.set_has_active_animations();
+    driver.update_animations(start + ms(10));
+    assert_eq!(driver.current_tick(), start + ms(10));
+
+    driver.set_has_active_animations();
+    driver.set_paused(true, start + ms(10));
+    assert!(!driver.has_active_animations());
+    driver.update_animations(start + ms(50));
+    assert_eq!(driver.current_tick(), start + ms(10));
+
+    // The time between the last tick and the resume was also spent paused
+    driver.set_paused(false, start + ms(70));
+    assert!(driver.has_active_animations());
+    assert_eq!(driver.current_tick(), start + ms(10));
+    driver.update_animations(start + ms(80));
+    assert_eq!(driver.current_tick(), start + ms(20));
 }
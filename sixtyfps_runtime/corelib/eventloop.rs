@@ -36,14 +36,15 @@ use winit::platform::desktop::EventLoopExtDesktop;
 ///
 /// [`crate::graphics`] provides an implementation of this trait for use with [`crate::graphics::GraphicsBackend`].
 pub trait GenericWindow {
-    /// Draw the items of the specified `component` in the given window.
+    /// Draw the items of the specified `component` in the given window. This does nothing if the window
+    /// is not mapped.
     fn draw(self: Rc<Self>, component: core::pin::Pin<crate::component::ComponentRef>);
     /// Receive a mouse event and pass it to the items of the component to
     /// change their state.
     ///
     /// Arguments:
     /// * `pos`: The position of the mouse event in window physical coordinates.
-    /// * `what`: The type of mouse event.
+    /// * `what`: The type of mouse event. For wheel events, the deltas are in physical pixels.
     /// * `component`: The SixtyFPS compiled component that provides the tree of items.
     fn process_mouse_input(
         self: Rc<Self>,
@@ -81,16 +82,40 @@ pub trait GenericWindow {
     /// Issue a request to the windowing system to re-render the contents of the window. This is typically an asynchronous
     /// request.
     fn request_redraw(&self);
+    /// Returns true if any of the properties that were used during the last call to [`GenericWindow::draw`]
+    /// changed since then, and the window therefore needs to be redrawn.
+    fn needs_redraw(&self) -> bool;
+    /// Returns a copy of the pixels rendered by the last call to [`GenericWindow::draw`], or None if
+    /// the window does not support reading back its contents.
+    fn grab_pixels(&self) -> Option<image::RgbaImage>;
+    /// Returns the number of items whose rendering primitives are cached by the window. The items
+    /// within a hidden item have no entry.
+    fn rendering_cache_len(&self) -> usize;
     /// Returns the scale factor set on the window, as provided by the windowing system.
     fn scale_factor(&self) -> f32;
     /// Sets an overriding scale factor for the window. This is typically only used for testing.
     fn set_scale_factor(&self, factor: f32);
+    /// Sets the scale factor reported by the windowing system. This is ignored while the scale factor
+    /// is forced with the `SIXTYFPS_SCALE_FACTOR` environment variable, until the windowing system
+    /// reports a different scale factor than when the window was mapped. It is always ignored if the
+    /// `SIXTYFPS_SCALE_FACTOR_LOCK` environment variable is also set.
+    fn set_platform_scale_factor(&self, factor: f32);
+    /// Enables or disables the debug overlay, which outlines the rectangle of every visible item.
+    fn set_debug_draw(&self, enabled: bool);
     /// Sets the size of the window to the specified `width`. This method is typically called in response to receiving a
     /// window resize event from the windowing system.
     fn set_width(&self, width: f32);
     /// Sets the size of the window to the specified `height`. This method is typically called in response to receiving a
     /// window resize event from the windowing system.
     fn set_height(&self, height: f32);
+    /// Returns the size of the window as (width, height), in physical pixels.
+    fn size(&self) -> (f32, f32);
+    /// Sets the minimum size of the window, in physical pixels. The windowing system doesn't let the user make
+    /// the window smaller than that.
+    fn set_min_size(&self, width: f32, height: f32);
+    /// Sets the maximum size of the window, in physical pixels. `f32::MAX` means that the size is not limited in
+    /// that direction.
+    fn set_max_size(&self, width: f32, height: f32);
     /// This function is called by the generated code when a component and therefore its tree of items are destroyed. The
     /// implementation typically uses this to free the underlying graphics resources cached via [`crate::graphics::RenderingCache`].
     fn free_graphics_resources(
@@ -145,7 +170,42 @@ impl ComponentWindow {
         self.0.clone().unmap_window();
     }
 
+    /// Renders the items of the provided component in this window.
+    pub fn draw(&self, component: Pin<VRef<ComponentVTable>>) {
+        self.0.clone().draw(component)
+    }
+
+    /// Recomputes the layout of the `component` and renders it right away, instead of waiting for the
+    /// event loop to process a redraw request. A window that is not shown is not rendered, so this can
+    /// be called without an event loop, for example before reading the geometry of the elements in a test.
+    pub fn render_now(&self, component: Pin<VRef<ComponentVTable>>) {
+        component.as_ref().compute_layout();
+        self.apply_layout_constraints(component);
+        self.0.clone().draw(component)
+    }
+
+    /// Returns a copy of the pixels rendered by the last call to [`ComponentWindow::draw`], or None if
+    /// the window does not support reading back its contents. This is typically only used for testing,
+    /// with a window created by [`crate::headless::create_headless_window`].
+    pub fn grab_pixels(&self) -> Option<image::RgbaImage> {
+        self.0.grab_pixels()
+    }
+
+    /// Returns the number of items whose rendering primitives are cached by the window, as of the
+    /// last call to [`ComponentWindow::draw`]. This is typically only used for testing.
+    pub fn rendering_cache_len(&self) -> usize {
+        self.0.rendering_cache_len()
+    }
+
     /// Returns the scale factor set on the window.
+    ///
+    /// It is the scale factor reported by the windowing system, unless the `SIXTYFPS_SCALE_FACTOR`
+    /// environment variable is set to a positive number, such as `SIXTYFPS_SCALE_FACTOR=2`. In that
+    /// case the window starts with that scale factor instead, until the windowing system reports a
+    /// new scale factor, for example when the window is moved to another screen. If the
+    /// `SIXTYFPS_SCALE_FACTOR_LOCK` environment variable is also set, the scale factor of the environment
+    /// variable is kept for the whole lifetime of the window. This is useful to get consistent screenshots,
+    /// or to test the rendering for high DPI screens on a low DPI screen.
     pub fn scale_factor(&self) -> f32 {
         self.0.scale_factor()
     }
@@ -155,6 +215,50 @@ impl ComponentWindow {
         self.0.set_scale_factor(factor)
     }
 
+    /// Enables or disables the debug overlay, which draws a one pixel wide outline around the rectangle
+    /// of every visible item on top of the rendered items: blue for the items that have children, like the
+    /// elements containing a layout, and red for the others. This helps diagnosing layout issues.
+    ///
+    /// The overlay is initially enabled if the `SIXTYFPS_DEBUG_DRAW` environment variable is set.
+    pub fn set_debug_draw(&self, enabled: bool) {
+        self.0.set_debug_draw(enabled)
+    }
+
+    /// Converts a position in logical pixels, the unit of the `px` lengths in the `.60` files, into
+    /// physical pixels of the window, with the current scale factor. This is the coordinate system of
+    /// the windowing system, for example to position a native window over an element. The result is
+    /// only valid until the scale factor changes, for example when the window moves to another screen.
+    pub fn logical_to_physical(&self, point: crate::graphics::Point) -> crate::graphics::Point {
+        point * self.scale_factor()
+    }
+
+    /// Converts a position in physical pixels of the window, as received from the windowing system,
+    /// into logical pixels with the current scale factor. This is the inverse of
+    /// [`ComponentWindow::logical_to_physical`].
+    pub fn physical_to_logical(&self, point: crate::graphics::Point) -> crate::graphics::Point {
+        point / self.scale_factor()
+    }
+
+    /// Returns the size of the window as (width, height), in physical pixels.
+    pub fn size(&self) -> (f32, f32) {
+        self.0.size()
+    }
+
+    /// Resizes the window, as if it was done by the windowing system. This is typically only used for testing.
+    pub fn set_size(&self, width: f32, height: f32) {
+        self.0.set_width(width);
+        self.0.set_height(height);
+    }
+
+    /// Limits the size of the window to the minimum and maximum size of the `component`, as computed
+    /// from its layouts, so that the window cannot be shrunk until its contents are clipped. This is
+    /// done by the event loop whenever the layout of the component changed.
+    pub fn apply_layout_constraints(&self, component: Pin<VRef<ComponentVTable>>) {
+        let constraints = component.as_ref().layout_info();
+        self.0.set_min_size(constraints.min_width, constraints.min_height);
+        self.0.set_max_size(constraints.max_width, constraints.max_height);
+    }
+
     /// This function is called by the generated code when a component and therefore its tree of items are destroyed. The
     /// implementation typically uses this to free the underlying graphics resources cached via [RenderingCache][`crate::graphics::RenderingCache`].
     pub fn free_graphics_resources(
@@ -191,7 +295,9 @@ impl ComponentWindow {
         self.0.clone().process_key_input(event, component)
     }
 
-    pub(crate) fn set_focus_item(
+    /// Sets the focus to the given item of the component. This removes the focus from
+    /// the item that currently has it.
+    pub fn set_focus_item(
         &self,
         component: core::pin::Pin<crate::component::ComponentRef>,
         item: Pin<VRef<crate::items::ItemVTable>>,
@@ -216,6 +322,232 @@ pub(crate) fn unregister_window(id: winit::window::WindowId) {
     })
 }
 
+/// Calls `f` with the root element of the `component`, if it is a `Window`.
+fn with_window_item(
+    component: crate::component::ComponentRefPin,
+    f: impl FnOnce(Pin<&crate::items::Window>),
+) {
+    let mut f = Some(f);
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, _| {
+            if let Some(window_item) = ItemRef::downcast_pin::<crate::items::Window>(item) {
+                if let Some(f) = f.take() {
+                    f(window_item);
+                }
+            }
+            crate::item_tree::ItemVisitorResult::Abort
+        },
+        (),
+    );
+}
+
+/// Emits the `resized` signal of the root element of the `component`, if it is a `Window`, with
+/// its new width and height. This is called after the size of the window changed and the layout
+/// was recomputed, so that the handler sees the new geometry of the elements.
+pub(crate) fn emit_resized(component: crate::component::ComponentRefPin) {
+    with_window_item(component, |window_item| {
+        use crate::items::Window;
+        let width = Window::FIELD_OFFSETS.width.apply_pin(window_item).get();
+        let height = Window::FIELD_OFFSETS.height.apply_pin(window_item).get();
+        Window::FIELD_OFFSETS.resized.apply_pin(window_item).emit(&(width, height))
+    });
+}
+
+/// Emits the `shown` signal of the root element of a component, if it is a `Window`, after the
+/// first frame was drawn. The layout was computed for that frame, so the handler sees the initial
+/// geometry of the elements.
+#[derive(Default)]
+pub struct ShownTracker {
+    shown: bool,
+}
+
+impl ShownTracker {
+    /// To be called after each frame of the `component` was drawn. Only the first call emits `shown`.
+    pub fn frame_drawn(&mut self, component: crate::component::ComponentRefPin) {
+        if !self.shown {
+            self.shown = true;
+            with_window_item(component, |window_item| {
+                crate::items::Window::FIELD_OFFSETS.shown.apply_pin(window_item).emit(&())
+            });
+        }
+    }
+}
+
+/// Emits the `file_dropped` signal of the root element of the `component`, if it is a `Window`,
+/// with the absolute path of each of the `files`, and then its `files_dropped` signal.
+pub(crate) fn emit_files_dropped(
+    component: crate::component::ComponentRefPin,
+    files: &[std::path::PathBuf],
+) {
+    let paths = files
+        .iter()
+        .map(|path| {
+            if path.is_absolute() {
+                path.clone()
+            } else {
+                std::env::current_dir().map(|dir| dir.join(path)).unwrap_or_else(|_| path.clone())
+            }
+        })
+        .map(|path| crate::SharedString::from(path.to_string_lossy().as_ref()))
+        .collect::<Vec<_>>();
+    with_window_item(component, |window_item| {
+        let file_dropped = crate::items::Window::FIELD_OFFSETS.file_dropped.apply_pin(window_item);
+        for path in paths {
+            file_dropped.emit(&(path,))
+        }
+        crate::items::Window::FIELD_OFFSETS.files_dropped.apply_pin(window_item).emit(&())
+    });
+}
+
+/// Accumulates the files dropped on a window. The windowing system reports each file of a drop
+/// with its own `DroppedFile` event, preceded by one `HoveredFile` event per file while they are
+/// dragged over the window, so the paths are collected until all the files of the drop arrived.
+#[derive(Default)]
+pub struct DroppedFiles {
+    window_id: Option<winit::window::WindowId>,
+    hovered: usize,
+    dropped: Vec<std::path::PathBuf>,
+}
+
+impl DroppedFiles {
+    /// Processes an event of the event loop. Returns the window and the paths of the dropped files
+    /// once the drop is complete: when as many files were dropped as were hovered, or when another
+    /// event arrives.
+    pub fn process_event(
+        &mut self,
+        event: &winit::event::Event<()>,
+    ) -> Option<(winit::window::WindowId, Vec<std::path::PathBuf>)> {
+        use winit::event::{Event, WindowEvent};
+        match event {
+            Event::WindowEvent { window_id, event: WindowEvent::HoveredFile(_) } => {
+                let previous = self.switch_window(*window_id);
+                self.hovered += 1;
+                previous
+            }
+            Event::WindowEvent { window_id, event: WindowEvent::HoveredFileCancelled } => {
+                let previous = self.switch_window(*window_id);
+                self.hovered = 0;
+                previous
+            }
+            Event::WindowEvent { window_id, event: WindowEvent::DroppedFile(path) } => {
+                let previous = self.switch_window(*window_id);
+                self.dropped.push(path.clone());
+                if previous.is_some() {
+                    previous
+                } else if self.dropped.len() >= self.hovered {
+                    self.take()
+                } else {
+                    None
+                }
+            }
+            _ => self.take(),
+        }
+    }
+
+    /// Collects the files of the given window from now on. Returns the files that were dropped
+    /// on another window, if any.
+    fn switch_window(
+        &mut self,
+        window_id: winit::window::WindowId,
+    ) -> Option<(winit::window::WindowId, Vec<std::path::PathBuf>)> {
+        if self.window_id == Some(window_id) {
+            return None;
+        }
+        let previous = self.take();
+        self.hovered = 0;
+        self.window_id = Some(window_id);
+        previous
+    }
+
+    fn take(&mut self) -> Option<(winit::window::WindowId, Vec<std::path::PathBuf>)> {
+        if self.dropped.is_empty() {
+            return None;
+        }
+        self.hovered = 0;
+        Some((self.window_id?, core::mem::take(&mut self.dropped)))
+    }
+}
+
+/// Coalesces the cursor moves. Mice can report their position much more often than the screen
+/// refreshes, so only the last position of consecutive `CursorMoved` events of a window is
+/// delivered to the items, before the next other event of the same window, or when all the pending
+/// events have been processed. The events of other windows and the device events, like the raw
+/// `MouseMotion` that is interleaved with the moves, don't interrupt the coalescing.
+#[derive(Default)]
+pub struct CursorMoves {
+    pending: Vec<(winit::window::WindowId, winit::dpi::PhysicalPosition<f64>)>,
+}
+
+impl CursorMoves {
+    /// Processes an event of the event loop. Returns the cursor moves to deliver before handling
+    /// the event, at most one per window.
+    pub fn process_event(
+        &mut self,
+        event: &winit::event::Event<()>,
+    ) -> Vec<(winit::window::WindowId, winit::dpi::PhysicalPosition<f64>)> {
+        use winit::event::{Event, WindowEvent};
+        match event {
+            Event::WindowEvent { window_id, event: WindowEvent::CursorMoved { position, .. } } => {
+                match self.pending.iter_mut().find(|(id, _)| id == window_id) {
+                    Some(pending) => pending.1 = *position,
+                    None => self.pending.push((*window_id, *position)),
+                }
+                Vec::new()
+            }
+            Event::WindowEvent { window_id, .. } => {
+                match self.pending.iter().position(|(id, _)| id == window_id) {
+                    Some(index) => vec![self.pending.remove(index)],
+                    None => Vec::new(),
+                }
+            }
+            Event::MainEventsCleared => core::mem::take(&mut self.pending),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Keeps track of the properties that the layout of a component depends on, so that the layout is
+/// only recomputed when one of them changed, and not when for example only a color changed.
+pub struct LayoutTracker {
+    tracker: Pin<Box<PropertyTracker>>,
+    compute_layout_count: std::cell::Cell<usize>,
+}
+
+impl Default for LayoutTracker {
+    fn default() -> Self {
+        Self {
+            tracker: Box::pin(PropertyTracker::default()),
+            compute_layout_count: Default::default(),
+        }
+    }
+}
+
+impl LayoutTracker {
+    /// Returns true if any of the properties used by the last layout computation changed, or if the
+    /// layout was never computed.
+    pub fn is_dirty(&self) -> bool {
+        self.tracker.is_dirty()
+    }
+
+    /// Recomputes the layout of the `component` if the tracker is dirty. Returns true if the layout
+    /// was recomputed.
+    pub fn relayout_if_needed(&self, component: crate::component::ComponentRefPin) -> bool {
+        if !self.is_dirty() {
+            return false;
+        }
+        self.tracker.as_ref().evaluate(|| component.as_ref().compute_layout());
+        self.compute_layout_count.set(self.compute_layout_count.get() + 1);
+        true
+    }
+
+    /// Returns how many times the layout was recomputed by [`LayoutTracker::relayout_if_needed`].
+    pub fn compute_layout_count(&self) -> usize {
+        self.compute_layout_count.get()
+    }
+}
+
 /// This is the main structure to hold the event loop responsible for delegating events from the
 /// windowing system to the individual windows managed by the run-time, and then subsequently to
 /// the items. These are typically rendering and input events.
@@ -235,15 +567,45 @@ impl EventLoop {
     pub fn run(mut self, component: core::pin::Pin<crate::component::ComponentRef>) {
         use winit::event::Event;
         use winit::event_loop::{ControlFlow, EventLoopWindowTarget};
-        let layout_listener = Rc::pin(PropertyTracker::default());
+        let layout_tracker = LayoutTracker::default();
+        // The `shown` signal is emitted after the first frame of each run of the event loop
+        let mut shown_tracker = ShownTracker::default();
 
         let mut cursor_pos = winit::dpi::PhysicalPosition::new(0., 0.);
-        let mut pressed = false;
+        let mut cursor_moves = CursorMoves::default();
+        let mut touch_points = crate::input::TouchPoints::default();
+        let mut dropped_files = DroppedFiles::default();
         let mut run_fn = move |event: Event<()>,
                                _: &EventLoopWindowTarget<()>,
                                control_flow: &mut ControlFlow| {
             *control_flow = ControlFlow::Wait;
 
+            for (window_id, position) in cursor_moves.process_event(&event) {
+                crate::animations::update_animations();
+                ALL_WINDOWS.with(|windows| {
+                    if let Some(Some(window)) =
+                        windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
+                    {
+                        window.clone().process_mouse_input(
+                            position,
+                            MouseEventType::MouseMoved,
+                            component,
+                        );
+                    }
+                });
+            }
+
+            if let Some((window_id, files)) = dropped_files.process_event(&event) {
+                ALL_WINDOWS.with(|windows| {
+                    // Only deliver the files dropped on a window that still exists
+                    if let Some(Some(_)) =
+                        windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
+                    {
+                        emit_files_dropped(component, &files);
+                    }
+                });
+            }
+
             match event {
                 winit::event::Event::WindowEvent {
                     event: winit::event::WindowEvent::CloseRequested,
@@ -255,12 +617,12 @@ impl EventLoop {
                         if let Some(Some(window)) =
                             windows.borrow().get(&id).map(|weakref| weakref.upgrade())
                         {
-                            if layout_listener.as_ref().is_dirty() {
-                                layout_listener
-                                    .as_ref()
-                                    .evaluate(|| component.as_ref().compute_layout())
+                            if layout_tracker.relayout_if_needed(component) {
+                                ComponentWindow::new(window.clone())
+                                    .apply_layout_constraints(component);
                             }
                             window.draw(component);
+                            shown_tracker.frame_drawn(component);
                         }
                     });
                 }
@@ -273,10 +635,17 @@ impl EventLoop {
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
                             window.with_platform_window(&|platform_window| {
-                                window.set_scale_factor(platform_window.scale_factor() as f32);
+                                window.set_platform_scale_factor(
+                                    platform_window.scale_factor() as f32
+                                );
                             });
                             window.set_width(size.width as f32);
                             window.set_height(size.height as f32);
+                            if layout_tracker.relayout_if_needed(component) {
+                                ComponentWindow::new(window.clone())
+                                    .apply_layout_constraints(component);
+                            }
+                            emit_resized(component);
                         }
                     });
                 }
@@ -292,9 +661,14 @@ impl EventLoop {
                         if let Some(Some(window)) =
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
-                            window.set_scale_factor(scale_factor as f32);
+                            window.set_platform_scale_factor(scale_factor as f32);
                             window.set_width(size.width as f32);
                             window.set_height(size.height as f32);
+                            if layout_tracker.relayout_if_needed(component) {
+                                ComponentWindow::new(window.clone())
+                                    .apply_layout_constraints(component);
+                            }
+                            emit_resized(component);
                         }
                     });
                 }
@@ -310,18 +684,12 @@ impl EventLoop {
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
                             let what = match state {
-                                winit::event::ElementState::Pressed => {
-                                    pressed = true;
-                                    MouseEventType::MousePressed
-                                }
+                                winit::event::ElementState::Pressed => MouseEventType::MousePressed,
                                 winit::event::ElementState::Released => {
-                                    pressed = false;
                                     MouseEventType::MouseReleased
                                 }
                             };
                             window.clone().process_mouse_input(cursor_pos, what, component);
-                            // FIXME: remove this, it should be based on actual changes rather than this
-                            window.request_redraw();
                         }
                     });
                 }
@@ -335,70 +703,90 @@ impl EventLoop {
                         if let Some(Some(window)) =
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
-                            let cursor_pos = touch.location;
-                            let what = match touch.phase {
+                            let pos = euclid::point2(touch.location.x as _, touch.location.y as _);
+                            let event = match touch.phase {
                                 winit::event::TouchPhase::Started => {
-                                    pressed = true;
-                                    MouseEventType::MousePressed
+                                    touch_points.touch_started(touch.id, pos)
                                 }
                                 winit::event::TouchPhase::Ended
                                 | winit::event::TouchPhase::Cancelled => {
-                                    pressed = false;
-                                    MouseEventType::MouseReleased
+                                    touch_points.touch_ended(touch.id, pos)
+                                }
+                                winit::event::TouchPhase::Moved => {
+                                    touch_points.touch_moved(touch.id, pos)
                                 }
-                                winit::event::TouchPhase::Moved => MouseEventType::MouseMoved,
                             };
-                            window.clone().process_mouse_input(cursor_pos, what, component);
-                            // FIXME: remove this, it should be based on actual changes rather than this
-                            window.request_redraw();
+                            if let Some(event) = event {
+                                let event_pos = winit::dpi::PhysicalPosition::new(
+                                    event.pos.x as f64,
+                                    event.pos.y as f64,
+                                );
+                                window
+                                    .clone()
+                                    .process_mouse_input(event_pos, event.what, component);
+                            }
                         }
                     });
                 }
                 winit::event::Event::WindowEvent {
-                    window_id,
                     event: winit::event::WindowEvent::CursorMoved { position, .. },
                     ..
                 } => {
+                    // Delivered by cursor_moves, with the next event
                     cursor_pos = position;
+                }
+                winit::event::Event::WindowEvent {
+                    ref window_id,
+                    event: winit::event::WindowEvent::MouseWheel { delta, .. },
+                    ..
+                } => {
                     crate::animations::update_animations();
                     ALL_WINDOWS.with(|windows| {
                         if let Some(Some(window)) =
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
+                            let scale_factor = window.scale_factor();
+                            let (delta_x, delta_y) = match delta {
+                                winit::event::MouseScrollDelta::LineDelta(lx, ly) => {
+                                    // One line is scrolled by this many logical pixels
+                                    const LINE_HEIGHT: f32 = 20.;
+                                    (
+                                        lx * LINE_HEIGHT * scale_factor,
+                                        ly * LINE_HEIGHT * scale_factor,
+                                    )
+                                }
+                                winit::event::MouseScrollDelta::PixelDelta(pos) => {
+                                    (pos.x as f32 * scale_factor, pos.y as f32 * scale_factor)
+                                }
+                            };
                             window.clone().process_mouse_input(
                                 cursor_pos,
-                                MouseEventType::MouseMoved,
+                                MouseEventType::MouseWheel { delta_x, delta_y },
                                 component,
                             );
-                            // FIXME: remove this, it should be based on actual changes rather than this
-                            window.request_redraw();
                         }
                     });
                 }
-                // On the html canvas, we don't get the mouse move or release event when outside the canvas. So we have no choice but canceling the event
-                #[cfg(target_arch = "wasm32")]
+                // Let the items reset their hover or pressed state when the cursor leaves the window.
+                // On the html canvas, we also don't get the mouse move or release event when outside the canvas,
+                // so we have no choice but canceling the event.
                 winit::event::Event::WindowEvent {
                     ref window_id,
                     event: winit::event::WindowEvent::CursorLeft { .. },
                     ..
                 } => {
-                    if pressed {
-                        crate::animations::update_animations();
-                        ALL_WINDOWS.with(|windows| {
-                            if let Some(Some(window)) =
-                                windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
-                            {
-                                pressed = false;
-                                window.clone().process_mouse_input(
-                                    cursor_pos,
-                                    MouseEventType::MouseExit,
-                                    component,
-                                );
-                                // FIXME: remove this, it should be based on actual changes rather than this
-                                window.request_redraw();
-                            }
-                        });
-                    }
+                    crate::animations::update_animations();
+                    ALL_WINDOWS.with(|windows| {
+                        if let Some(Some(window)) =
+                            windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
+                        {
+                            window.clone().process_mouse_input(
+                                cursor_pos,
+                                MouseEventType::MouseExit,
+                                component,
+                            );
+                        }
+                    });
                 }
 
                 winit::event::Event::WindowEvent {
@@ -414,8 +802,6 @@ impl EventLoop {
                                 (input, window.current_keyboard_modifiers()).try_into().ok()
                             {
                                 window.clone().process_key_input(key_event, component);
-                                // FIXME: remove this, it should be based on actual changes rather than this
-                                window.request_redraw();
                             }
                         }
                     });
@@ -438,8 +824,6 @@ impl EventLoop {
                                         modifiers,
                                     };
                                     window.clone().process_key_input(&key_event, component);
-                                    // FIXME: remove this, it should be based on actual changes rather than this
-                                    window.request_redraw();
                                 }
                             }
                         });
@@ -467,8 +851,6 @@ impl EventLoop {
                             windows.borrow().get(&window_id).map(|weakref| weakref.upgrade())
                         {
                             window.clone().set_focus(component, have_focus);
-                            // FIXME: remove this, it should be based on actual changes rather than this
-                            window.request_redraw();
                         }
                     });
                 }
@@ -476,6 +858,17 @@ impl EventLoop {
                 _ => (),
             }
 
+            // Only schedule a redraw if something the rendering or the layout depends on has changed.
+            ALL_WINDOWS.with(|windows| {
+                windows.borrow().values().for_each(|window| {
+                    if let Some(window) = window.upgrade() {
+                        if layout_tracker.is_dirty() || window.needs_redraw() {
+                            window.request_redraw();
+                        }
+                    }
+                })
+            });
+
             if *control_flow != winit::event_loop::ControlFlow::Exit {
                 crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
                     if !driver.has_active_animations() {
@@ -602,3 +995,100 @@ pub mod ffi {
         window.free_graphics_resources(component)
     }
 }
+
+#[test]
+fn test_cursor_moves() {
+    use winit::dpi::PhysicalPosition;
+    use winit::event::{Event, WindowEvent};
+    let window_id = unsafe { winit::window::WindowId::dummy() };
+    #[allow(deprecated)]
+    let cursor_moved = |x| Event::WindowEvent {
+        window_id,
+        event: WindowEvent::CursorMoved {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            position: PhysicalPosition::new(x, 10.),
+            modifiers: Default::default(),
+        },
+    };
+    let mut cursor_moves = CursorMoves::default();
+
+    // A burst of 100 moves is delivered once, with the last position, when the events are processed
+    let delivered =
+        (0..100).map(|x| cursor_moves.process_event(&cursor_moved(x as f64)).len()).sum::<usize>();
+    assert_eq!(delivered, 0);
+    assert_eq!(
+        cursor_moves.process_event(&Event::MainEventsCleared),
+        vec![(window_id, PhysicalPosition::new(99., 10.))]
+    );
+    assert_eq!(cursor_moves.process_event(&Event::MainEventsCleared), vec![]);
+
+    // The raw device events that come along with the moves don't interrupt the coalescing
+    let mouse_motion = Event::DeviceEvent {
+        device_id: unsafe { winit::event::DeviceId::dummy() },
+        event: winit::event::DeviceEvent::MouseMotion { delta: (1., 0.) },
+    };
+    let delivered = (0..100)
+        .map(|x| {
+            cursor_moves.process_event(&mouse_motion).len()
+                + cursor_moves.process_event(&cursor_moved(x as f64)).len()
+        })
+        .sum::<usize>();
+    assert_eq!(delivered, 0);
+    assert_eq!(
+        cursor_moves.process_event(&Event::MainEventsCleared),
+        vec![(window_id, PhysicalPosition::new(99., 10.))]
+    );
+
+    // The move is delivered before a click, so that the click happens at the right position
+    cursor_moves.process_event(&cursor_moved(5.));
+    #[allow(deprecated)]
+    let click = Event::WindowEvent {
+        window_id,
+        event: WindowEvent::MouseInput {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            state: winit::event::ElementState::Pressed,
+            button: winit::event::MouseButton::Left,
+            modifiers: Default::default(),
+        },
+    };
+    assert_eq!(
+        cursor_moves.process_event(&click),
+        vec![(window_id, PhysicalPosition::new(5., 10.))]
+    );
+    assert_eq!(cursor_moves.process_event(&click), vec![]);
+}
+
+#[test]
+fn test_dropped_files() {
+    use std::path::PathBuf;
+    use winit::event::{Event, WindowEvent};
+    let window_id = unsafe { winit::window::WindowId::dummy() };
+    let event = |event| Event::WindowEvent { window_id, event };
+    let mut dropped_files = DroppedFiles::default();
+    let (a, b) = (PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/b.txt"));
+
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::HoveredFile(a.clone()))), None);
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::HoveredFile(b.clone()))), None);
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::DroppedFile(a.clone()))), None);
+    assert_eq!(
+        dropped_files.process_event(&event(WindowEvent::DroppedFile(b.clone()))),
+        Some((window_id, vec![a.clone(), b.clone()]))
+    );
+
+    // A cancelled drag does not deliver anything
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::HoveredFile(a.clone()))), None);
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::HoveredFileCancelled)), None);
+    assert_eq!(dropped_files.process_event(&Event::MainEventsCleared), None);
+
+    // Without hover events, each file is delivered on its own
+    assert_eq!(
+        dropped_files.process_event(&event(WindowEvent::DroppedFile(a.clone()))),
+        Some((window_id, vec![a.clone()]))
+    );
+
+    // The files received so far are delivered when another event arrives
+    dropped_files.process_event(&event(WindowEvent::HoveredFile(a.clone())));
+    dropped_files.process_event(&event(WindowEvent::HoveredFile(b.clone())));
+    assert_eq!(dropped_files.process_event(&event(WindowEvent::DroppedFile(b.clone()))), None);
+    assert_eq!(dropped_files.process_event(&Event::MainEventsCleared), Some((window_id, vec![b])));
+}
@@ -85,6 +85,24 @@ impl FlickableData {
                         .set(new_pos.y);
                 }
             }
+            MouseEventType::MouseWheel { delta_x, delta_y } => {
+                let viewport_pos = Point::new(
+                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
+                        .apply_pin(flick)
+                        .get(),
+                    (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
+                        .apply_pin(flick)
+                        .get(),
+                );
+                let new_pos = ensure_in_bound(flick, viewport_pos + euclid::vec2(delta_x, delta_y));
+                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.x)
+                    .apply_pin(flick)
+                    .set(new_pos.x);
+                (Flickable::FIELD_OFFSETS.viewport + Rectangle::FIELD_OFFSETS.y)
+                    .apply_pin(flick)
+                    .set(new_pos.y);
+            }
+            MouseEventType::PinchGesture { .. } => {}
         }
     }
 }
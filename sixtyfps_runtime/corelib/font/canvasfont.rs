@@ -7,6 +7,7 @@
     This file is also available under commercial licensing terms.
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
+use super::FaceProperties;
 use std::hash::Hash;
 
 #[derive(Clone)]
@@ -17,6 +18,7 @@ struct GlyphMetrics {
 pub struct Font {
     pub pixel_size: f32,
     font_family: String,
+    properties: FaceProperties,
     text_canvas: web_sys::HtmlCanvasElement,
     canvas_context: web_sys::CanvasRenderingContext2d,
 }
@@ -27,6 +29,12 @@ impl Font {
         text_metrics.width() as _
     }
 
+    /// The letter spacing is added between the characters, not after the last one.
+    pub fn text_width_with_letter_spacing(&self, text: &str, letter_spacing: f32) -> f32 {
+        let spacing_count = text.chars().count().saturating_sub(1);
+        self.text_width(text) + letter_spacing * spacing_count as f32
+    }
+
     pub fn text_offset_for_x_position(&self, text: &str, x: f32) -> usize {
         // This is pretty cruel ...
         let mut last_width = 0.;
@@ -50,6 +58,24 @@ impl Font {
         self.pixel_size
     }
 
+    /// Returns the position of the top of the underline, relative to the top of the text, and
+    /// its thickness. The canvas doesn't provide the metrics of the font, so this is an estimate.
+    pub fn underline(&self) -> (f32, f32) {
+        let thickness = self.decoration_thickness();
+        (self.pixel_size * 0.9 - thickness / 2., thickness)
+    }
+
+    /// Returns the position of the top of the strikethrough line, relative to the top of the
+    /// text, and its thickness. This is an estimate, like for the underline.
+    pub fn strikethrough(&self) -> (f32, f32) {
+        let thickness = self.decoration_thickness();
+        (self.pixel_size * 0.55 - thickness / 2., thickness)
+    }
+
+    fn decoration_thickness(&self) -> f32 {
+        (self.pixel_size / 14.).max(1.)
+    }
+
     pub fn render_text<'a>(&'a self, text: &str) -> &'a web_sys::HtmlCanvasElement {
         let text_metrics = self.canvas_context.measure_text(text).unwrap();
 
@@ -62,7 +88,11 @@ impl Font {
         self.text_canvas.style().set_property("height", &format!("{}px", self.pixel_size)).unwrap();
 
         // Re-apply after resize :(
-        self.canvas_context.set_font(&format!("{}px \"{}\"", self.pixel_size, self.font_family));
+        self.canvas_context.set_font(&css_font(
+            &self.font_family,
+            self.properties,
+            self.pixel_size,
+        ));
 
         self.canvas_context.set_text_align("center");
         self.canvas_context.set_text_baseline("middle");
@@ -88,17 +118,18 @@ impl Font {
 }
 
 #[derive(Clone)]
-pub struct FontHandle(String);
+pub struct FontHandle(String, FaceProperties);
 
 impl Hash for FontHandle {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.0.hash(state)
+        self.0.hash(state);
+        self.1.hash(state)
     }
 }
 
 impl PartialEq for FontHandle {
     fn eq(&self, other: &Self) -> bool {
-        self.0.eq(&other.0)
+        self.0.eq(&other.0) && self.1.eq(&other.1)
     }
 }
 
@@ -107,6 +138,7 @@ impl Eq for FontHandle {}
 impl FontHandle {
     pub fn load(&self, pixel_size: f32) -> Result<Font, ()> {
         let font_family = &self.0;
+        let properties = self.1;
 
         let text_canvas = web_sys::window()
             .unwrap()
@@ -125,12 +157,29 @@ impl FontHandle {
             .dyn_into::<web_sys::CanvasRenderingContext2d>()
             .unwrap();
 
-        canvas_context.set_font(&format!("{}px \"{}\"", pixel_size, font_family));
+        canvas_context.set_font(&css_font(font_family, properties, pixel_size));
 
-        Ok(Font { pixel_size, font_family: font_family.clone(), text_canvas, canvas_context })
+        Ok(Font {
+            pixel_size,
+            font_family: font_family.clone(),
+            properties,
+            text_canvas,
+            canvas_context,
+        })
     }
 
-    pub fn new_from_match(family: &str) -> Self {
-        Self(family.to_owned())
+    /// The browser selects the face, and synthesizes bold and italic if the family doesn't have them.
+    pub fn new_from_match(family: &str, properties: FaceProperties) -> Self {
+        Self(family.to_owned(), properties)
     }
 }
+
+fn css_font(family: &str, properties: FaceProperties, pixel_size: f32) -> String {
+    format!(
+        "{} {} {}px \"{}\"",
+        if properties.italic { "italic" } else { "normal" },
+        properties.weight,
+        pixel_size,
+        family
+    )
+}
@@ -7,9 +7,9 @@
     This file is also available under commercial licensing terms.
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
-use image::{ImageBuffer, Pixel, Rgba};
+use super::FaceProperties;
 use pathfinder_geometry::{
-    transform2d::Transform2F,
+    transform2d::{Matrix2x2F, Transform2F},
     vector::{Vector2F, Vector2I},
 };
 use std::cell::RefCell;
@@ -19,13 +19,32 @@ use std::hash::Hash;
 #[derive(Clone)]
 pub struct GlyphMetrics {
     pub advance: f32,
+    /// The right edge of the ink of the glyph, relative to its origin
+    pub ink_right: f32,
 }
 
+impl GlyphMetrics {
+    /// Returns the distance from the origin of this glyph to the origin of the next one when
+    /// `letter_spacing` is added to the advance. A negative letter spacing brings the glyphs
+    /// closer together, but the next glyph never starts within the ink of this one.
+    pub fn advance_with_letter_spacing(&self, letter_spacing: f32) -> f32 {
+        if letter_spacing < 0. {
+            (self.advance + letter_spacing).max(self.ink_right.min(self.advance))
+        } else {
+            self.advance + letter_spacing
+        }
+    }
+}
+
+/// The horizontal shift of a synthesized italic glyph, per pixel above the baseline.
+const SYNTHETIC_ITALIC_SKEW: f32 = 0.2;
+
 pub struct Font {
     pub pixel_size: f32,
     font: font_kit::font::Font,
     metrics: font_kit::metrics::Metrics,
     glyph_metrics_cache: RefCell<HashMap<u32, GlyphMetrics>>,
+    handle: FontHandle,
 }
 
 impl Font {
@@ -47,9 +66,22 @@ impl Font {
     }
 
     pub fn text_width(&self, text: &str) -> f32 {
-        self.string_to_glyphs(text)
-            .map(|(_, _, glyph)| self.glyph_metrics(glyph))
-            .fold(0., |width, glyph| width + glyph.advance)
+        self.text_width_with_letter_spacing(text, 0.)
+    }
+
+    /// The letter spacing is added between the glyphs, not after the last one.
+    pub fn text_width_with_letter_spacing(&self, text: &str, letter_spacing: f32) -> f32 {
+        let mut glyphs =
+            self.string_to_glyphs(text).map(|(_, _, glyph)| self.glyph_metrics(glyph)).peekable();
+        let mut width = 0.;
+        while let Some(glyph) = glyphs.next() {
+            width += if glyphs.peek().is_some() {
+                glyph.advance_with_letter_spacing(letter_spacing)
+            } else {
+                glyph.advance
+            };
+        }
+        width
     }
 
     pub fn text_offset_for_x_position<'a>(&self, text: &'a str, x: f32) -> usize {
@@ -72,9 +104,14 @@ impl Font {
             .borrow_mut()
             .entry(glyph)
             .or_insert_with(|| {
-                let advance =
-                    self.font.advance(glyph).unwrap().x() * self.font_units_to_pixel_size();
-                GlyphMetrics { advance }
+                let bold_offset = self.synthetic_bold_offset() as f32;
+                let advance = self.font.advance(glyph).unwrap().x()
+                    * self.font_units_to_pixel_size()
+                    + bold_offset;
+                let ink_right = self.font.typographic_bounds(glyph).map_or(0., |bounds| {
+                    bounds.max_x() * self.font_units_to_pixel_size() + bold_offset
+                });
+                GlyphMetrics { advance, ink_right }
             })
             .clone()
     }
@@ -84,6 +121,16 @@ impl Font {
         self.pixel_size / self.metrics.units_per_em as f32
     }
 
+    /// The number of pixels by which the strokes of the glyphs are thickened to the right, when
+    /// the face is not bold but a bold one was requested.
+    fn synthetic_bold_offset(&self) -> u32 {
+        if self.handle.synthesized.bold {
+            (self.pixel_size / 24.).round().max(1.) as u32
+        } else {
+            0
+        }
+    }
+
     pub fn ascent(&self) -> f32 {
         self.metrics.ascent * self.font_units_to_pixel_size()
     }
@@ -96,47 +143,94 @@ impl Font {
         (self.metrics.ascent - self.metrics.descent + 1.) * self.font_units_to_pixel_size()
     }
 
-    pub fn rasterize_glyph(&self, glyph_id: u32) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    /// Returns the position of the top of the underline, relative to the top of the text, and
+    /// its thickness.
+    pub fn underline(&self) -> (f32, f32) {
+        let thickness = self.decoration_thickness();
+        let center =
+            self.ascent() - self.metrics.underline_position * self.font_units_to_pixel_size();
+        (center - thickness / 2., thickness)
+    }
+
+    /// Returns the position of the top of the strikethrough line, relative to the top of the
+    /// text, and its thickness. The line goes through the middle of the lowercase letters.
+    pub fn strikethrough(&self) -> (f32, f32) {
+        let thickness = self.decoration_thickness();
+        let center = self.ascent() - self.metrics.x_height * self.font_units_to_pixel_size() / 2.;
+        (center - thickness / 2., thickness)
+    }
+
+    fn decoration_thickness(&self) -> f32 {
+        (self.metrics.underline_thickness * self.font_units_to_pixel_size()).max(1.)
+    }
+
+    /// Returns the coverage of the glyph. The image doesn't contain any color, it is up to
+    /// the renderer to tint it with the color of the text.
+    pub fn rasterize_glyph(&self, glyph_id: u32) -> image::GrayImage {
         let baseline_y = self.ascent();
         let hinting = font_kit::hinting::HintingOptions::None;
         let raster_opts = font_kit::canvas::RasterizationOptions::GrayscaleAa;
 
+        let skew = if self.handle.synthesized.italic { SYNTHETIC_ITALIC_SKEW } else { 0. };
+
         // ### TODO: #8 use tight bounding box for glyphs stored in texture atlas
         let glyph_height = self.height();
-        let glyph_width = self.glyph_metrics(glyph_id).advance;
+        let glyph_width = self.glyph_metrics(glyph_id).advance + skew * baseline_y;
         let mut canvas = font_kit::canvas::Canvas::new(
             Vector2I::new(glyph_width.ceil() as i32, glyph_height.ceil() as i32),
             font_kit::canvas::Format::A8,
         );
+        // The outline is slanted around the baseline for a synthesized italic
+        let slant = Transform2F {
+            matrix: Matrix2x2F::row_major(1., -skew, 0., 1.),
+            vector: Vector2F::zero(),
+        };
         self.font
             .rasterize_glyph(
                 &mut canvas,
                 glyph_id,
                 self.pixel_size,
-                Transform2F::from_translation(Vector2F::new(0., baseline_y)),
+                Transform2F::from_translation(Vector2F::new(0., baseline_y)) * slant,
                 hinting,
                 raster_opts,
             )
             .unwrap();
 
+        // For a synthesized bold, each pixel is covered as much as the most covered pixel
+        // on its left, within the bold offset.
+        let bold_offset = self.synthetic_bold_offset();
         image::ImageBuffer::from_fn(canvas.size.x() as u32, canvas.size.y() as u32, |x, y| {
-            let idx = (x as usize) + (y as usize) * canvas.stride;
-            let alpha = canvas.pixels[idx];
-            image::Rgba::<u8>::from_channels(0, 0, 0, alpha)
+            let coverage = (0..=bold_offset.min(x))
+                .map(|dx| canvas.pixels[((x - dx) as usize) + (y as usize) * canvas.stride])
+                .max()
+                .unwrap_or(0);
+            image::Luma([coverage])
         })
     }
 
     pub fn handle(&self) -> FontHandle {
-        FontHandle(self.font.handle().unwrap())
+        self.handle.clone()
     }
 }
 
+/// The bold and italic styles that are emulated when rasterizing the glyphs, because the family
+/// doesn't have a face with these styles. This is only a fallback, it doesn't look as good as a real
+/// bold or italic face.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
+struct SynthesizedStyle {
+    bold: bool,
+    italic: bool,
+}
+
 #[derive(Clone)]
-pub struct FontHandle(font_kit::handle::Handle);
+pub struct FontHandle {
+    handle: font_kit::handle::Handle,
+    synthesized: SynthesizedStyle,
+}
 
 impl Hash for FontHandle {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        match &self.0 {
+        match &self.handle {
             font_kit::handle::Handle::Path { path, font_index } => {
                 path.hash(state);
                 font_index.hash(state);
@@ -146,27 +240,29 @@ impl Hash for FontHandle {
                 font_index.hash(state);
             }
         }
+        self.synthesized.hash(state);
     }
 }
 
 impl PartialEq for FontHandle {
     fn eq(&self, other: &Self) -> bool {
-        match &self.0 {
-            font_kit::handle::Handle::Path { path, font_index } => match &other.0 {
+        let same_face = match &self.handle {
+            font_kit::handle::Handle::Path { path, font_index } => match &other.handle {
                 font_kit::handle::Handle::Path {
                     path: other_path,
                     font_index: other_font_index,
                 } => path.eq(other_path) && font_index.eq(other_font_index),
                 _ => false,
             },
-            font_kit::handle::Handle::Memory { bytes, font_index } => match &other.0 {
+            font_kit::handle::Handle::Memory { bytes, font_index } => match &other.handle {
                 font_kit::handle::Handle::Memory {
                     bytes: other_bytes,
                     font_index: other_font_index,
                 } => bytes.eq(other_bytes) && font_index.eq(other_font_index),
                 _ => false,
             },
-        }
+        };
+        same_face && self.synthesized == other.synthesized
     }
 }
 
@@ -174,30 +270,90 @@ impl Eq for FontHandle {}
 
 impl FontHandle {
     pub fn load(&self, pixel_size: f32) -> Result<Font, font_kit::error::FontLoadingError> {
-        let font = self.0.load()?;
+        let font = self.handle.load()?;
         let metrics = font.metrics();
-        Ok(Font { pixel_size, font, metrics, glyph_metrics_cache: Default::default() })
+        Ok(Font {
+            pixel_size,
+            font,
+            metrics,
+            glyph_metrics_cache: Default::default(),
+            handle: self.clone(),
+        })
     }
 
-    pub fn new_from_match(family: &str) -> Self {
+    /// Creates a handle for the first font in the TrueType or OpenType file at `path`. Returns an
+    /// error if the file cannot be read or doesn't contain a font.
+    pub fn new_from_path(
+        path: &std::path::Path,
+    ) -> Result<Self, font_kit::error::FontLoadingError> {
+        let handle = font_kit::handle::Handle::from_path(path.to_owned(), 0);
+        handle.load()?;
+        Ok(handle.into())
+    }
+
+    /// Creates a handle for the first font in `data`, the contents of a TrueType or OpenType file.
+    pub fn new_from_memory(data: Vec<u8>) -> Result<Self, font_kit::error::FontLoadingError> {
+        let handle = font_kit::handle::Handle::from_memory(std::sync::Arc::new(data), 0);
+        handle.load()?;
+        Ok(handle.into())
+    }
+
+    /// Selects the face among the system fonts of the `family` that best matches the `properties`.
+    pub fn new_from_match(family: &str, properties: FaceProperties) -> Self {
         let family_name = if family.len() == 0 {
             font_kit::family_name::FamilyName::SansSerif
         } else {
             font_kit::family_name::FamilyName::Title(family.into())
         };
 
-        font_kit::source::SystemSource::new()
+        let handle = font_kit::source::SystemSource::new()
             .select_best_match(
                 &[family_name, font_kit::family_name::FamilyName::SansSerif],
-                &font_kit::properties::Properties::new(),
+                &to_font_kit_properties(properties),
             )
-            .unwrap()
-            .into()
+            .unwrap();
+        Self::new_with_synthesized_style(handle, properties)
+    }
+
+    /// Selects the face among the registered `faces` of a family that best matches the `properties`.
+    pub fn new_from_registered(faces: &[FontHandle], properties: FaceProperties) -> Option<Self> {
+        let candidates = faces
+            .iter()
+            .map(|face| face.handle.load().map(|font| font.properties()).unwrap_or_default())
+            .collect::<Vec<_>>();
+        let index =
+            font_kit::matching::find_best_match(&candidates, &to_font_kit_properties(properties))
+                .ok()?;
+        Some(Self::new_with_synthesized_style(faces[index].handle.clone(), properties))
+    }
+
+    /// Bold or italic are synthesized if they were requested but the selected face doesn't have them.
+    fn new_with_synthesized_style(
+        handle: font_kit::handle::Handle,
+        properties: FaceProperties,
+    ) -> Self {
+        let face = handle.load().map(|font| font.properties()).unwrap_or_default();
+        let synthesized = SynthesizedStyle {
+            bold: properties.weight >= 600 && face.weight.0 < 600.,
+            italic: properties.italic && face.style == font_kit::properties::Style::Normal,
+        };
+        Self { handle, synthesized }
     }
 }
 
 impl From<font_kit::handle::Handle> for FontHandle {
-    fn from(h: font_kit::handle::Handle) -> Self {
-        Self(h)
+    fn from(handle: font_kit::handle::Handle) -> Self {
+        Self { handle, synthesized: SynthesizedStyle::default() }
     }
 }
+
+fn to_font_kit_properties(properties: FaceProperties) -> font_kit::properties::Properties {
+    let style = if properties.italic {
+        font_kit::properties::Style::Italic
+    } else {
+        font_kit::properties::Style::Normal
+    };
+    *font_kit::properties::Properties::new()
+        .weight(font_kit::properties::Weight(properties.weight as f32))
+        .style(style)
+}
@@ -21,6 +21,20 @@ mod canvasfont;
 #[cfg(target_arch = "wasm32")]
 pub use canvasfont::*;
 
+/// The weight and the slant of the face to select within a font family.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FaceProperties {
+    /// The weight, as in CSS: 400 is normal and 700 is bold.
+    pub weight: i32,
+    pub italic: bool,
+}
+
+impl Default for FaceProperties {
+    fn default() -> Self {
+        Self { weight: 400, italic: false }
+    }
+}
+
 struct FontMatch {
     handle: FontHandle,
     fonts_per_pixel_size: Vec<Rc<Font>>,
@@ -28,19 +42,80 @@ struct FontMatch {
 
 #[derive(Default)]
 pub struct FontCache {
-    // index by family name
-    loaded_fonts: RefCell<HashMap<String, FontMatch>>,
+    // index by family name and face properties
+    loaded_fonts: RefCell<HashMap<(String, FaceProperties), FontMatch>>,
+    // the faces registered by the application, by family name
+    #[cfg(not(target_arch = "wasm32"))]
+    registered_fonts: RefCell<HashMap<String, Vec<FontHandle>>>,
 }
 
 impl FontCache {
-    pub fn find_font(&self, family: &str, pixel_size: f32) -> Rc<Font> {
+    /// Registers the font in the file at `path` under the `family` name, so that text with that
+    /// font family uses it instead of looking for a system font. If the file cannot be loaded,
+    /// a warning is printed, the error is returned and the family keeps on resolving to the
+    /// system fonts.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn register_font_from_path(
+        &self,
+        family: &str,
+        path: &std::path::Path,
+    ) -> Result<(), font_kit::error::FontLoadingError> {
+        FontHandle::new_from_path(path).map(|handle| self.register_font(family, handle)).map_err(
+            |err| {
+                eprintln!(
+                    "Warning: could not load font {} for the family \"{}\": {}",
+                    path.display(),
+                    family,
+                    err
+                );
+                err
+            },
+        )
+    }
+
+    /// Registers the font in `data`, the contents of a TrueType or OpenType file, under the
+    /// `family` name, so that text with that font family uses it instead of looking for a
+    /// system font.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn register_font_from_memory(
+        &self,
+        family: &str,
+        data: Vec<u8>,
+    ) -> Result<(), font_kit::error::FontLoadingError> {
+        FontHandle::new_from_memory(data).map(|handle| self.register_font(family, handle))
+    }
+
+    /// Several faces can be registered for the same family, for example a regular and a bold one.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn register_font(&self, family: &str, handle: FontHandle) {
+        self.registered_fonts.borrow_mut().entry(family.to_owned()).or_default().push(handle);
+        // The faces that were selected before may not be the best match anymore
+        self.loaded_fonts.borrow_mut().retain(|(loaded_family, _), _| loaded_family != family);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn find_registered_font(&self, family: &str, properties: FaceProperties) -> Option<FontHandle> {
+        FontHandle::new_from_registered(self.registered_fonts.borrow().get(family)?, properties)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn find_registered_font(&self, _: &str, _: FaceProperties) -> Option<FontHandle> {
+        None
+    }
+
+    /// Returns the font of the `family` whose face best matches the `properties`. If the family
+    /// doesn't have a bold or an italic face, the glyphs are emboldened or slanted when rendered.
+    pub fn find_font(&self, family: &str, properties: FaceProperties, pixel_size: f32) -> Rc<Font> {
         assert_ne!(pixel_size, 0.0);
 
         let mut loaded_fonts = self.loaded_fonts.borrow_mut();
-        let font_match = loaded_fonts.entry(family.to_owned()).or_insert_with(|| FontMatch {
-            handle: FontHandle::new_from_match(family),
-            fonts_per_pixel_size: Vec::new(),
-        });
+        let font_match =
+            loaded_fonts.entry((family.to_owned(), properties)).or_insert_with(|| FontMatch {
+                handle: self
+                    .find_registered_font(family, properties)
+                    .unwrap_or_else(|| FontHandle::new_from_match(family, properties)),
+                fonts_per_pixel_size: Vec::new(),
+            });
 
         font_match
             .fonts_per_pixel_size
@@ -57,3 +132,36 @@ impl FontCache {
 thread_local! {
     pub static FONT_CACHE: FontCache = Default::default();
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_invalid_font() {
+        let cache = FontCache::default();
+        assert!(cache
+            .register_font_from_path("Missing", std::path::Path::new("/does/not/exist.ttf"))
+            .is_err());
+        assert!(cache.register_font_from_memory("Garbage", vec![0; 64]).is_err());
+        assert!(cache.registered_fonts.borrow().is_empty());
+    }
+
+    #[test]
+    fn register_font_file() {
+        // Any font file of the system will do. The test is skipped on systems without fonts.
+        let path = match font_kit::source::SystemSource::new().select_best_match(
+            &[font_kit::family_name::FamilyName::SansSerif],
+            &font_kit::properties::Properties::new(),
+        ) {
+            Ok(font_kit::handle::Handle::Path { path, font_index: 0 }) => path,
+            // Only the first font of a file can be registered from its path
+            _ => return,
+        };
+
+        let cache = FontCache::default();
+        cache.register_font_from_path("Registered Family", &path).unwrap();
+        let font = cache.find_font("Registered Family", FaceProperties::default(), 12.);
+        assert!(font.handle() == FontHandle::new_from_path(&path).unwrap());
+    }
+}
@@ -22,7 +22,7 @@ LICENSE END */
 extern crate alloc;
 use crate::input::{KeyEvent, KeyboardModifiers, MouseEvent, MouseEventType};
 use crate::items::ItemRef;
-use crate::properties::{InterpolatedPropertyValue, Property};
+use crate::properties::{InterpolatedPropertyValue, Property, PropertyTracker};
 #[cfg(feature = "rtti")]
 use crate::rtti::{BuiltinItem, FieldInfo, PropertyInfo, ValueType};
 use crate::SharedArray;
@@ -272,19 +272,30 @@ pub enum HighLevelRenderingPrimitive {
     /// * [`RenderingVariable::Color`]: The color to fill the rectangle with.
     /// * [`RenderingVariable::Color`]: The color to use for stroking the border of the rectangle.
     BorderRectangle { width: f32, height: f32, border_width: f32, border_radius: f32 },
-    /// Renders a image referenced by the specified `source`.
+    /// Renders a image referenced by the specified `source`, fitted into its box as specified by `image_fit`.
     ///
     /// Optional rendering variables:
-    /// * [`RenderingVariable::ScaledWidth`]: The image will be scaled to the specified width.
-    /// * [`RenderingVariable::ScaledHeight`]: The image will be scaled to the specified height.
-    Image { source: crate::Resource },
-    /// Renders the specified `text` with a font that matches the specified family (`font_family`) and the given
-    /// pixel size (`font_size`).
+    /// * [`RenderingVariable::ScaledWidth`]: The width of the box the image is fitted into.
+    /// * [`RenderingVariable::ScaledHeight`]: The height of the box the image is fitted into.
+    ///
+    /// The size of the image is used for the width or the height of the box when they are not specified.
+    Image { source: crate::Resource, image_fit: crate::items::ImageFit },
+    /// Renders the specified `text` with a font that matches the specified family (`font_family`), weight and
+    /// style (`font_properties`) and the given pixel size (`font_size`). If `right_to_left` is true, the glyphs are laid out from right to left, in the
+    /// reverse order of the characters. The `letter_spacing` is added between the glyphs.
     ///
     /// Expected rendering variables:
     /// * [`RenderingVariable::Color`]: The color to use for rendering the glyphs.
+    /// * [`RenderingVariable::TextDecoration`]: Draw an underline or a strikethrough line.
     /// * [`RenderingVariable::TextCursor`]: Draw a text cursor.
-    Text { text: crate::SharedString, font_family: crate::SharedString, font_size: f32 },
+    Text {
+        text: crate::SharedString,
+        font_family: crate::SharedString,
+        font_size: f32,
+        font_properties: crate::font::FaceProperties,
+        right_to_left: bool,
+        letter_spacing: f32,
+    },
     /// Renders a path specified by the `elements` parameter. The path will be scaled to fit into the given
     /// `width` and `height`. If the `stroke_width` is greater than zero, then path will also be outlined.
     ///
@@ -325,6 +336,9 @@ pub enum RenderingVariable {
     /// Draw a text selection. The parameters provide the starting x coordinate, the width and the height. This variable
     /// must be followed by two colors, foreground and background.
     TextSelection(f32, f32, f32),
+    /// Draw a line across the text, such as an underline, with the color of the text. The parameters provide the y
+    /// coordinate of the top of the line, its width and its thickness as (y, width, thickness) tuple.
+    TextDecoration(f32, f32, f32),
 }
 
 impl RenderingVariable {
@@ -351,6 +365,82 @@ impl RenderingVariable {
     }
 }
 
+/// Computes how an image of size `image_size` is drawn into a box of size `box_size` with the given `image_fit`.
+///
+/// Returns the part of the image that is visible, in image pixels, and the rectangle it is drawn into, relative
+/// to the top-left corner of the box. The image is centered in the box, except with [`ImageFit::fill`](crate::items::ImageFit::fill),
+/// where it covers the box exactly.
+pub fn fit_image(
+    image_fit: crate::items::ImageFit,
+    image_size: Size,
+    box_size: Size,
+) -> (Rect, Rect) {
+    use crate::items::ImageFit;
+    let scale = |image_len: f32, box_len: f32| box_len / image_len;
+    let (scale_x, scale_y) = match image_fit {
+        ImageFit::fill => {
+            (scale(image_size.width, box_size.width), scale(image_size.height, box_size.height))
+        }
+        ImageFit::contain => {
+            let s = scale(image_size.width, box_size.width)
+                .min(scale(image_size.height, box_size.height));
+            (s, s)
+        }
+        ImageFit::cover => {
+            let s = scale(image_size.width, box_size.width)
+                .max(scale(image_size.height, box_size.height));
+            (s, s)
+        }
+        ImageFit::none => (1., 1.),
+    };
+    // Returns the (source offset, source length, target offset, target length) along one axis
+    let fit_axis = |image_len: f32, box_len: f32, scale: f32| {
+        let scaled_len = image_len * scale;
+        if scaled_len <= box_len {
+            (0., image_len, (box_len - scaled_len) / 2., scaled_len)
+        } else {
+            let visible_len = box_len / scale;
+            ((image_len - visible_len) / 2., visible_len, 0., box_len)
+        }
+    };
+    let (source_x, source_width, target_x, target_width) =
+        fit_axis(image_size.width, box_size.width, scale_x);
+    let (source_y, source_height, target_y, target_height) =
+        fit_axis(image_size.height, box_size.height, scale_y);
+    (
+        Rect::new(Point::new(source_x, source_y), Size::new(source_width, source_height)),
+        Rect::new(Point::new(target_x, target_y), Size::new(target_width, target_height)),
+    )
+}
+
+#[test]
+fn test_fit_image() {
+    use crate::items::ImageFit;
+    let image = Size::new(200., 100.);
+    let square = Size::new(100., 100.);
+    let rect = |x, y, w, h| Rect::new(Point::new(x, y), Size::new(w, h));
+    assert_eq!(
+        fit_image(ImageFit::fill, image, square),
+        (rect(0., 0., 200., 100.), rect(0., 0., 100., 100.))
+    );
+    assert_eq!(
+        fit_image(ImageFit::contain, image, square),
+        (rect(0., 0., 200., 100.), rect(0., 25., 100., 50.))
+    );
+    assert_eq!(
+        fit_image(ImageFit::cover, image, square),
+        (rect(50., 0., 100., 100.), rect(0., 0., 100., 100.))
+    );
+    assert_eq!(
+        fit_image(ImageFit::none, image, square),
+        (rect(50., 0., 100., 100.), rect(0., 0., 100., 100.))
+    );
+    assert_eq!(
+        fit_image(ImageFit::none, Size::new(50., 20.), square),
+        (rect(0., 0., 50., 20.), rect(25., 40., 50., 20.))
+    );
+}
+
 /// Frame is used to render previously created [GraphicsBackend::LowLevelRenderingPrimitive] instances
 /// to the back-buffer of the window.
 pub trait Frame {
@@ -446,6 +536,17 @@ pub trait GraphicsBackend: Sized {
     fn window(&self) -> &winit::window::Window;
 }
 
+/// The handler of the dependency trackers of the primitives of a [RenderingCache]. It marks the
+/// whole cache as dirty when the properties needed to create one of its primitives changed.
+#[derive(Clone, Default)]
+pub struct RenderingCacheDirtyHandler(Rc<core::cell::Cell<bool>>);
+
+impl crate::properties::PropertyDirtyHandler for RenderingCacheDirtyHandler {
+    fn notify(&self) {
+        self.0.set(true)
+    }
+}
+
 /// Holds a GraphicBackend's rendering primitive as well as a PropertyTracker that allows lazily re-creating
 /// the primitive if the properties needed to create it have changed.
 pub struct TrackingRenderingPrimitive<Backend: GraphicsBackend> {
@@ -453,14 +554,19 @@ pub struct TrackingRenderingPrimitive<Backend: GraphicsBackend> {
     pub primitive: Backend::LowLevelRenderingPrimitive,
     /// The property tracker that should be used to evaluate whether the primitive needs to be re-created
     /// or not.
-    pub dependency_tracker: core::pin::Pin<Box<crate::properties::PropertyTracker>>,
+    pub dependency_tracker:
+        core::pin::Pin<Box<crate::properties::PropertyTracker<RenderingCacheDirtyHandler>>>,
 }
 
 impl<Backend: GraphicsBackend> TrackingRenderingPrimitive<Backend> {
     /// Creates a new TrackingRenderingPrimitive by evaluating the provided update_fn once, storing the returned
-    /// rendering primitive and initializing the dependency tracker.
-    pub fn new(update_fn: impl FnOnce() -> Backend::LowLevelRenderingPrimitive) -> Self {
-        let dependency_tracker = Box::pin(crate::properties::PropertyTracker::default());
+    /// rendering primitive and initializing the dependency tracker, which notifies the `dirty_handler`.
+    pub fn new(
+        update_fn: impl FnOnce() -> Backend::LowLevelRenderingPrimitive,
+        dirty_handler: RenderingCacheDirtyHandler,
+    ) -> Self {
+        let dependency_tracker =
+            Box::pin(crate::properties::PropertyTracker::new_with_dirty_handler(dirty_handler));
         let primitive = dependency_tracker.as_ref().evaluate(update_fn);
         Self { primitive, dependency_tracker }
     }
@@ -471,7 +577,41 @@ impl<Backend: GraphicsBackend> TrackingRenderingPrimitive<Backend> {
 /// [Items][`crate::items`]. Instead it allows mapping them to a usize
 /// handle, and it also allows tracking whenever any of the properties used to
 /// create the primitive changed.
-pub type RenderingCache<Backend> = vec_arena::Arena<TrackingRenderingPrimitive<Backend>>;
+#[derive(derive_more::Deref, derive_more::DerefMut)]
+pub struct RenderingCache<Backend: GraphicsBackend> {
+    #[deref]
+    #[deref_mut]
+    entries: vec_arena::Arena<TrackingRenderingPrimitive<Backend>>,
+    dirty_handler: RenderingCacheDirtyHandler,
+}
+
+impl<Backend: GraphicsBackend> Default for RenderingCache<Backend> {
+    fn default() -> Self {
+        Self { entries: Default::default(), dirty_handler: Default::default() }
+    }
+}
+
+impl<Backend: GraphicsBackend> RenderingCache<Backend> {
+    /// Creates an entry for the primitive returned by `update_fn` and returns its index.
+    pub fn insert_tracked(
+        &mut self,
+        update_fn: impl FnOnce() -> Backend::LowLevelRenderingPrimitive,
+    ) -> usize {
+        let entry = TrackingRenderingPrimitive::new(update_fn, self.dirty_handler.clone());
+        self.entries.insert(entry)
+    }
+
+    /// Returns true if the properties needed to create any of the primitives changed since the
+    /// last call to [RenderingCache::clear_dirty], without going through all the entries.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_handler.0.get()
+    }
+
+    /// Resets the dirty flag of the cache. This is done before the dirty primitives are re-created.
+    pub fn clear_dirty(&self) {
+        self.dirty_handler.0.set(false)
+    }
+}
 
 type WindowFactoryFn<Backend> =
     dyn Fn(&crate::eventloop::EventLoop, winit::window::WindowBuilder) -> Backend;
@@ -504,6 +644,7 @@ struct WindowProperties {
     scale_factor: Property<f32>,
     width: Property<f32>,
     height: Property<f32>,
+    debug_draw: Property<bool>,
 }
 
 impl Default for WindowProperties {
@@ -512,10 +653,144 @@ impl Default for WindowProperties {
             scale_factor: Property::new(1.0),
             width: Property::new(800.),
             height: Property::new(600.),
+            debug_draw: Property::new(crate::item_rendering::debug_draw_from_env()),
         }
     }
 }
 
+/// A scale factor forced with the `SIXTYFPS_SCALE_FACTOR` environment variable, which replaces the
+/// scale factor reported by the windowing system.
+#[derive(Clone, Copy)]
+struct ForcedScaleFactor {
+    /// The scale factor reported by the windowing system when the window was mapped. The forced
+    /// scale factor is dropped when the windowing system reports a different one.
+    platform_scale_factor: f32,
+    /// Set with the `SIXTYFPS_SCALE_FACTOR_LOCK` environment variable: the forced scale factor is
+    /// kept even when the windowing system reports a different one.
+    locked: bool,
+}
+
+impl ForcedScaleFactor {
+    /// Returns whether the forced scale factor still applies once the windowing system reports
+    /// `platform_scale_factor`.
+    fn is_kept_for(&self, platform_scale_factor: f32) -> bool {
+        self.locked || self.platform_scale_factor == platform_scale_factor
+    }
+}
+
+/// Returns the scale factor of the `SIXTYFPS_SCALE_FACTOR` environment variable, if it is set to a
+/// positive number.
+fn scale_factor_from_env() -> Option<f32> {
+    parse_scale_factor(&std::env::var("SIXTYFPS_SCALE_FACTOR").ok()?)
+}
+
+fn parse_scale_factor(value: &str) -> Option<f32> {
+    let factor = value.trim().parse::<f32>().ok()?;
+    if factor > 0. && factor.is_finite() {
+        Some(factor)
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_forced_scale_factor() {
+    assert_eq!(parse_scale_factor("2"), Some(2.));
+    assert_eq!(parse_scale_factor(" 1.5\n"), Some(1.5));
+    assert_eq!(parse_scale_factor("0"), None);
+    assert_eq!(parse_scale_factor("-1"), None);
+    assert_eq!(parse_scale_factor("inf"), None);
+    assert_eq!(parse_scale_factor("NaN"), None);
+    assert_eq!(parse_scale_factor("two"), None);
+
+    let forced = ForcedScaleFactor { platform_scale_factor: 1., locked: false };
+    // The windowing system repeats the scale factor it reported when the window was mapped
+    assert!(forced.is_kept_for(1.));
+    // The window moved to a screen with another scale factor
+    assert!(!forced.is_kept_for(2.));
+
+    let locked = ForcedScaleFactor { platform_scale_factor: 1., locked: true };
+    assert!(locked.is_kept_for(1.));
+    assert!(locked.is_kept_for(2.));
+}
+
+/// The attributes of the platform window that are controlled by properties of the `Window` element
+#[derive(Clone, Copy, PartialEq)]
+struct WindowAttributes {
+    decorations: bool,
+    always_on_top: bool,
+    resizable: bool,
+}
+
+impl WindowAttributes {
+    fn from_window_item(window_item: Pin<&crate::items::Window>) -> Self {
+        use crate::items::Window;
+        Self {
+            decorations: !Window::FIELD_OFFSETS.no_decorations.apply_pin(window_item).get(),
+            always_on_top: Window::FIELD_OFFSETS.always_on_top.apply_pin(window_item).get(),
+            resizable: Window::FIELD_OFFSETS.resizable.apply_pin(window_item).get(),
+        }
+    }
+
+    fn apply_to_builder(
+        self,
+        builder: winit::window::WindowBuilder,
+    ) -> winit::window::WindowBuilder {
+        builder
+            .with_decorations(self.decorations)
+            .with_always_on_top(self.always_on_top)
+            .with_resizable(self.resizable)
+    }
+
+    fn apply_to_window(self, window: &winit::window::Window) {
+        window.set_decorations(self.decorations);
+        window.set_always_on_top(self.always_on_top);
+        window.set_resizable(self.resizable);
+    }
+}
+
+/// Evaluates the attributes of the platform window within `tracker`, so that it becomes dirty when
+/// they change. Returns None if the root item is not a `Window`: the tracker is then clean, as
+/// nothing controls the attributes.
+fn evaluate_window_attributes(
+    tracker: Pin<&PropertyTracker>,
+    root_item: Pin<ItemRef>,
+) -> Option<WindowAttributes> {
+    tracker.evaluate(|| ItemRef::downcast_pin(root_item).map(WindowAttributes::from_window_item))
+}
+
+#[test]
+fn test_window_attributes_tracker() {
+    use vtable::VRef;
+
+    let tracker = Box::pin(PropertyTracker::default());
+    let rectangle = Box::pin(crate::items::Rectangle::default());
+    // A root that is not a Window has no attributes, but the tracker must not stay dirty,
+    // otherwise the window would be redrawn continuously.
+    assert!(
+        evaluate_window_attributes(tracker.as_ref(), VRef::new_pin(rectangle.as_ref())).is_none()
+    );
+    assert!(!tracker.is_dirty());
+
+    let window = Box::pin(crate::items::Window::default());
+    let attributes =
+        evaluate_window_attributes(tracker.as_ref(), VRef::new_pin(window.as_ref())).unwrap();
+    assert!(attributes.decorations);
+    assert!(!tracker.is_dirty());
+    crate::items::Window::FIELD_OFFSETS.always_on_top.apply_pin(window.as_ref()).set(true);
+    assert!(tracker.is_dirty());
+    let attributes =
+        evaluate_window_attributes(tracker.as_ref(), VRef::new_pin(window.as_ref())).unwrap();
+    assert!(attributes.always_on_top);
+    assert!(!tracker.is_dirty());
+}
+
+/// Converts a size that may be unbounded (`f32::MAX`) to a size that the windowing system accepts.
+fn to_physical_size(width: f32, height: f32) -> winit::dpi::PhysicalSize<u32> {
+    let clamp = |value: f32| value.ceil().min(i32::MAX as f32) as u32;
+    winit::dpi::PhysicalSize::new(clamp(width), clamp(height))
+}
+
 /// GraphicsWindow is an implementation of the [GenericWindow][`crate::eventloop::GenericWindow`] trait. This is
 /// typically instantiated by entry factory functions of the different graphics backends.
 pub struct GraphicsWindow<Backend: GraphicsBackend + 'static> {
@@ -524,6 +799,13 @@ pub struct GraphicsWindow<Backend: GraphicsBackend + 'static> {
     properties: Pin<Box<WindowProperties>>,
     cursor_blinker: std::cell::RefCell<pin_weak::rc::PinWeak<TextCursorBlinker>>,
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
+    /// Tracks the properties read while drawing, so that we know when the window needs to be redrawn.
+    redraw_tracker: Pin<Box<PropertyTracker>>,
+    /// Tracks the properties of the `Window` element that map to attributes of the platform window,
+    /// so that they are applied again when they change.
+    window_attributes_tracker: Pin<Box<PropertyTracker>>,
+    /// Set while the scale factor is forced with the `SIXTYFPS_SCALE_FACTOR` environment variable.
+    forced_scale_factor: std::cell::Cell<Option<ForcedScaleFactor>>,
 }
 
 impl<Backend: GraphicsBackend + 'static> GraphicsWindow<Backend> {
@@ -543,9 +825,34 @@ impl<Backend: GraphicsBackend + 'static> GraphicsWindow<Backend> {
             properties: Box::pin(WindowProperties::default()),
             cursor_blinker: Default::default(),
             keyboard_modifiers: Default::default(),
+            redraw_tracker: Box::pin(PropertyTracker::default()),
+            window_attributes_tracker: Box::pin(PropertyTracker::default()),
+            forced_scale_factor: Default::default(),
         })
     }
 
+    /// Applies the attributes of the platform window if the properties of the `Window` element
+    /// they depend on have changed.
+    fn update_window_attributes(&self, component: crate::component::ComponentRefPin) {
+        if !self.window_attributes_tracker.is_dirty() {
+            return;
+        }
+        crate::item_tree::visit_items(
+            component,
+            crate::item_tree::TraversalOrder::BackToFront,
+            |_, item, _| {
+                if let Some(attributes) =
+                    evaluate_window_attributes(self.window_attributes_tracker.as_ref(), item)
+                {
+                    let map_state = self.map_state.borrow();
+                    attributes.apply_to_window(map_state.as_mapped().backend.borrow().window());
+                }
+                crate::item_tree::ItemVisitorResult::Abort
+            },
+            (),
+        );
+    }
+
     /// Returns the window id of the window if it is mapped, None otherwise.
     pub fn id(&self) -> Option<winit::window::WindowId> {
         Some(self.map_state.borrow().as_mapped().backend.borrow().window().id())
@@ -568,47 +875,97 @@ impl<Backend: GraphicsBackend> Drop for GraphicsWindow<Backend> {
 
 impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindow<Backend> {
     fn draw(self: Rc<Self>, component: crate::component::ComponentRefPin) {
-        {
+        if matches!(&*self.map_state.borrow(), GraphicsWindowBackendState::Unmapped) {
+            // Nothing to render into until the window is shown
+            return;
+        }
+        self.update_window_attributes(component);
+        self.redraw_tracker.as_ref().evaluate(|| {
+            let debug_outlines = {
+                let map_state = self.map_state.borrow();
+                let window = map_state.as_mapped();
+                let mut backend = window.backend.borrow_mut();
+                let mut rendering_primitives_builder = backend.new_rendering_primitives_builder();
+
+                // Generate cached rendering data once
+                crate::item_rendering::update_rendering_cache(
+                    component,
+                    &window.rendering_cache,
+                    &mut rendering_primitives_builder,
+                    &crate::eventloop::ComponentWindow::new(self.clone()),
+                );
+
+                let debug_outlines = if WindowProperties::FIELD_OFFSETS
+                    .debug_draw
+                    .apply_pin(self.properties.as_ref())
+                    .get()
+                {
+                    crate::item_rendering::debug_outlines(component)
+                        .iter()
+                        .map(|outline| {
+                            let (primitive, variables) =
+                                crate::item_rendering::debug_outline_primitive(outline);
+                            let origin = outline.rect.origin;
+                            (origin, rendering_primitives_builder.create(primitive), variables)
+                        })
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
+                backend.finish_primitives(rendering_primitives_builder);
+                debug_outlines
+            };
+
             let map_state = self.map_state.borrow();
             let window = map_state.as_mapped();
             let mut backend = window.backend.borrow_mut();
-            let mut rendering_primitives_builder = backend.new_rendering_primitives_builder();
-
-            // Generate cached rendering data once
-            crate::item_tree::visit_items(
+            let size = backend.window().inner_size();
+            let mut frame = backend.new_frame(
+                size.width,
+                size.height,
+                &crate::item_rendering::window_background_color(component),
+            );
+            crate::item_rendering::render_component_items(
                 component,
-                crate::item_tree::TraversalOrder::BackToFront,
-                |_, item, _| {
-                    crate::item_rendering::update_item_rendering_data(
-                        item,
-                        &window.rendering_cache,
-                        &mut rendering_primitives_builder,
-                        &self,
-                    );
-                    crate::item_tree::ItemVisitorResult::Continue(())
-                },
-                (),
+                &mut frame,
+                &mut window.rendering_cache.borrow_mut(),
+                &self,
             );
+            // The debug overlay is drawn on top of all the items
+            for (origin, primitive, variables) in debug_outlines {
+                let transform =
+                    Matrix4::from_translation(cgmath::Vector3::new(origin.x, origin.y, 0.));
+                frame.render_primitive(&primitive, &transform, variables);
+            }
+            backend.present_frame(frame);
+        });
+    }
 
-            backend.finish_primitives(rendering_primitives_builder);
+    fn needs_redraw(&self) -> bool {
+        if self.redraw_tracker.is_dirty() || self.window_attributes_tracker.is_dirty() {
+            return true;
+        }
+        match &*self.map_state.borrow() {
+            GraphicsWindowBackendState::Unmapped => false,
+            // The cached rendering primitives track their dependencies on their own, so they
+            // don't show up in the redraw tracker.
+            GraphicsWindowBackendState::Mapped(window) => {
+                window.rendering_cache.borrow().is_dirty()
+            }
         }
+    }
 
-        let map_state = self.map_state.borrow();
-        let window = map_state.as_mapped();
-        let mut backend = window.backend.borrow_mut();
-        let size = backend.window().inner_size();
-        let mut frame = backend.new_frame(
-            size.width,
-            size.height,
-            &ARGBColor { red: 255 as u8, green: 255, blue: 255, alpha: 255 }.into(),
-        );
-        crate::item_rendering::render_component_items(
-            component,
-            &mut frame,
-            &mut window.rendering_cache.borrow_mut(),
-            &self,
-        );
-        backend.present_frame(frame);
+    fn grab_pixels(&self) -> Option<image::RgbaImage> {
+        // Reading back the contents of an on-screen window is not supported.
+        None
+    }
+
+    fn rendering_cache_len(&self) -> usize {
+        match &*self.map_state.borrow() {
+            GraphicsWindowBackendState::Unmapped => 0,
+            GraphicsWindowBackendState::Mapped(window) => window.rendering_cache.borrow().len(),
+        }
     }
 
     fn process_mouse_input(
@@ -629,7 +986,13 @@ impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindo
         event: &KeyEvent,
         component: core::pin::Pin<crate::component::ComponentRef>,
     ) {
-        component.as_ref().key_event(event, &crate::eventloop::ComponentWindow::new(self.clone()));
+        let window = crate::eventloop::ComponentWindow::new(self.clone());
+        let result = component.as_ref().key_event(event, &window);
+        if matches!(result, crate::input::KeyEventResult::EventIgnored) {
+            if let Some(item_ptr) = crate::input::tab_focus_item(component, event) {
+                crate::eventloop::GenericWindow::set_focus_item(self, component, item_ptr);
+            }
+        }
     }
 
     fn with_platform_window(&self, callback: &dyn Fn(&winit::window::Window)) {
@@ -650,7 +1013,12 @@ impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindo
         }
 
         let id = {
-            let window_builder = winit::window::WindowBuilder::new();
+            let mut window_builder = winit::window::WindowBuilder::new();
+            if let Some(attributes) =
+                evaluate_window_attributes(self.window_attributes_tracker.as_ref(), root_item)
+            {
+                window_builder = attributes.apply_to_builder(window_builder);
+            }
 
             let backend = self.window_factory.as_ref()(&event_loop, window_builder);
 
@@ -664,7 +1032,18 @@ impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindo
             // the scale factor.
             // We could pass the logical requested size at window builder time, *if* we knew what the values are.
             {
-                self.properties.as_ref().scale_factor.set(platform_window.scale_factor() as _);
+                let platform_scale_factor = platform_window.scale_factor() as f32;
+                let scale_factor = match scale_factor_from_env() {
+                    Some(factor) => {
+                        self.forced_scale_factor.set(Some(ForcedScaleFactor {
+                            platform_scale_factor,
+                            locked: std::env::var_os("SIXTYFPS_SCALE_FACTOR_LOCK").is_some(),
+                        }));
+                        factor
+                    }
+                    None => platform_scale_factor,
+                };
+                self.properties.as_ref().scale_factor.set(scale_factor);
                 let existing_size = platform_window.inner_size();
 
                 let mut new_size = existing_size;
@@ -747,6 +1126,20 @@ impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindo
         self.properties.as_ref().scale_factor.set(factor);
     }
 
+    fn set_platform_scale_factor(&self, factor: f32) {
+        if let Some(forced) = self.forced_scale_factor.get() {
+            if forced.is_kept_for(factor) {
+                return;
+            }
+            self.forced_scale_factor.set(None);
+        }
+        self.properties.as_ref().scale_factor.set(factor);
+    }
+
+    fn set_debug_draw(&self, enabled: bool) {
+        self.properties.as_ref().debug_draw.set(enabled);
+    }
+
     fn set_width(&self, width: f32) {
         self.properties.as_ref().width.set(width);
     }
@@ -755,6 +1148,36 @@ impl<Backend: GraphicsBackend> crate::eventloop::GenericWindow for GraphicsWindo
         self.properties.as_ref().height.set(height);
     }
 
+    fn size(&self) -> (f32, f32) {
+        let properties = self.properties.as_ref();
+        (
+            WindowProperties::FIELD_OFFSETS.width.apply_pin(properties).get(),
+            WindowProperties::FIELD_OFFSETS.height.apply_pin(properties).get(),
+        )
+    }
+
+    fn set_min_size(&self, width: f32, height: f32) {
+        let size = if width > 0. || height > 0. {
+            Some(to_physical_size(width.max(0.), height.max(0.)))
+        } else {
+            None
+        };
+        if let GraphicsWindowBackendState::Mapped(window) = &*self.map_state.borrow() {
+            window.backend.borrow().window().set_min_inner_size(size);
+        }
+    }
+
+    fn set_max_size(&self, width: f32, height: f32) {
+        let size = if width < f32::MAX || height < f32::MAX {
+            Some(to_physical_size(width, height))
+        } else {
+            None
+        };
+        if let GraphicsWindowBackendState::Mapped(window) = &*self.map_state.borrow() {
+            window.backend.borrow().window().set_max_inner_size(size);
+        }
+    }
+
     fn free_graphics_resources(
         self: Rc<Self>,
         component: core::pin::Pin<crate::component::ComponentRef>,
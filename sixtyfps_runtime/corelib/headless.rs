@@ -0,0 +1,845 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+#![warn(missing_docs)]
+/*!
+    This module contains a window implementation that renders the items into an in-memory buffer
+    with a simple software rasterizer, without requiring a display or an OpenGL context.
+
+    It is meant to be used for testing: after calling [`ComponentWindow::draw`], the rendered pixels
+    can be retrieved with [`ComponentWindow::grab_pixels`].
+*/
+
+use crate::eventloop::ComponentWindow;
+use crate::graphics::{
+    ARGBColor, Color, HighLevelRenderingPrimitive, Point, Rect, RenderingCache, RenderingVariable,
+    Size,
+};
+use crate::input::{KeyEvent, KeyboardModifiers, MouseEvent, MouseEventType};
+use crate::item_tree::ItemVisitorResult;
+use crate::items::ItemRef;
+use crate::properties::{Property, PropertyTracker};
+use crate::{Resource, SharedArray};
+use core::pin::Pin;
+use lyon::tessellation::geometry_builder::{BuffersBuilder, VertexBuffers};
+use lyon::tessellation::{
+    FillAttributes, FillOptions, FillTessellator, StrokeAttributes, StrokeOptions,
+    StrokeTessellator,
+};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// HeadlessWindow is an implementation of the [GenericWindow][`crate::eventloop::GenericWindow`] trait that
+/// renders into an RGBA buffer in memory instead of a window on the screen.
+pub struct HeadlessWindow {
+    scale_factor: Pin<Box<Property<f32>>>,
+    width: Pin<Box<Property<f32>>>,
+    height: Pin<Box<Property<f32>>>,
+    /// The size constraints, which are enforced like a windowing system would do.
+    min_size: Cell<(f32, f32)>,
+    max_size: Cell<(f32, f32)>,
+    keyboard_modifiers: Cell<KeyboardModifiers>,
+    redraw_tracker: Pin<Box<PropertyTracker>>,
+    /// The rendering primitives of the visible items, maintained like in the GL backend.
+    rendering_cache: RefCell<RenderingCache<HeadlessBackend>>,
+    pixels: RefCell<image::RgbaImage>,
+    debug_draw: Pin<Box<Property<bool>>>,
+}
+
+impl HeadlessWindow {
+    /// Creates a new reference-counted instance that renders into a buffer of `width` x `height` physical pixels.
+    pub fn new(width: u32, height: u32) -> Rc<Self> {
+        Rc::new(Self {
+            scale_factor: Box::pin(Property::new(1.0)),
+            width: Box::pin(Property::new(width as f32)),
+            height: Box::pin(Property::new(height as f32)),
+            min_size: Cell::new((0., 0.)),
+            max_size: Cell::new((f32::MAX, f32::MAX)),
+            keyboard_modifiers: Default::default(),
+            redraw_tracker: Box::pin(PropertyTracker::default()),
+            rendering_cache: Default::default(),
+            pixels: RefCell::new(image::RgbaImage::new(width, height)),
+            debug_draw: Box::pin(Property::new(crate::item_rendering::debug_draw_from_env())),
+        })
+    }
+}
+
+/// Creates a new window that renders into a buffer of `width` x `height` physical pixels, without
+/// requiring a display.
+pub fn create_headless_window(width: u32, height: u32) -> ComponentWindow {
+    ComponentWindow::new(HeadlessWindow::new(width, height))
+}
+
+impl crate::eventloop::GenericWindow for HeadlessWindow {
+    fn draw(self: Rc<Self>, component: crate::component::ComponentRefPin) {
+        let window = ComponentWindow::new(self.clone());
+        let canvas = self.redraw_tracker.as_ref().evaluate(|| {
+            crate::item_rendering::update_rendering_cache(
+                component,
+                &self.rendering_cache,
+                &mut HeadlessPrimitivesBuilder,
+                &window,
+            );
+            let rendering_cache = self.rendering_cache.borrow();
+
+            let canvas = RefCell::new(Canvas::new(
+                self.width.as_ref().get() as u32,
+                self.height.as_ref().get() as u32,
+                crate::item_rendering::window_background_color(component),
+            ));
+
+            crate::item_tree::visit_items_with_post_visit(
+                component,
+                crate::item_tree::TraversalOrder::BackToFront,
+                |_, item, state| {
+                    // The state is None within a hidden item
+                    let (offset, opacity) = match state {
+                        Some((offset, opacity)) if !crate::item_rendering::is_hidden(item) => (
+                            *offset + item.as_ref().geometry().origin.to_vector(),
+                            opacity * crate::item_rendering::opacity_factor(item),
+                        ),
+                        _ => return (ItemVisitorResult::Continue(None), false),
+                    };
+                    let cached_rendering_data = item.cached_rendering_data_offset();
+                    let pushed_clip = canvas.borrow_mut().render_primitive(
+                        &rendering_cache
+                            .get(cached_rendering_data.cache_index.get())
+                            .unwrap()
+                            .primitive,
+                        offset,
+                        crate::item_rendering::apply_opacity(
+                            item.as_ref().rendering_variables(&window),
+                            opacity,
+                        ),
+                    );
+                    (ItemVisitorResult::Continue(Some((offset, opacity))), pushed_clip)
+                },
+                |_, _, pushed_clip| {
+                    if pushed_clip {
+                        canvas.borrow_mut().clip_stack.pop();
+                    }
+                },
+                Some((Point::default(), 1.)),
+            );
+
+            if self.debug_draw.as_ref().get() {
+                for outline in crate::item_rendering::debug_outlines(component) {
+                    let (primitive, variables) =
+                        crate::item_rendering::debug_outline_primitive(&outline);
+                    canvas.borrow_mut().render_primitive(
+                        &primitive,
+                        outline.rect.origin,
+                        variables,
+                    );
+                }
+            }
+
+            canvas.into_inner()
+        });
+        *self.pixels.borrow_mut() = canvas.image;
+    }
+
+    fn needs_redraw(&self) -> bool {
+        // The cached rendering primitives track their dependencies on their own, so they
+        // don't show up in the redraw tracker.
+        self.redraw_tracker.is_dirty() || self.rendering_cache.borrow().is_dirty()
+    }
+
+    fn grab_pixels(&self) -> Option<image::RgbaImage> {
+        Some(self.pixels.borrow().clone())
+    }
+
+    fn rendering_cache_len(&self) -> usize {
+        self.rendering_cache.borrow().len()
+    }
+
+    fn process_mouse_input(
+        self: Rc<Self>,
+        pos: winit::dpi::PhysicalPosition<f64>,
+        what: MouseEventType,
+        component: crate::component::ComponentRefPin,
+    ) {
+        component.as_ref().input_event(
+            MouseEvent { pos: euclid::point2(pos.x as _, pos.y as _), what },
+            &ComponentWindow::new(self.clone()),
+            &component,
+        );
+    }
+
+    fn process_key_input(
+        self: Rc<Self>,
+        event: &KeyEvent,
+        component: core::pin::Pin<crate::component::ComponentRef>,
+    ) {
+        let window = ComponentWindow::new(self.clone());
+        let result = component.as_ref().key_event(event, &window);
+        if matches!(result, crate::input::KeyEventResult::EventIgnored) {
+            if let Some(item_ptr) = crate::input::tab_focus_item(component, event) {
+                crate::eventloop::GenericWindow::set_focus_item(self, component, item_ptr);
+            }
+        }
+    }
+
+    fn with_platform_window(&self, _callback: &dyn Fn(&winit::window::Window)) {
+        // There is no platform window backing this window.
+    }
+
+    fn map_window(
+        self: Rc<Self>,
+        _event_loop: &crate::eventloop::EventLoop,
+        root_item: Pin<ItemRef>,
+    ) {
+        if let Some(window_item) = ItemRef::downcast_pin(root_item) {
+            let width = crate::items::Window::FIELD_OFFSETS.width.apply_pin(window_item).get();
+            if width > 0. {
+                self.width.as_ref().set(width);
+            }
+            let height = crate::items::Window::FIELD_OFFSETS.height.apply_pin(window_item).get();
+            if height > 0. {
+                self.height.as_ref().set(height);
+            }
+
+            {
+                let window = self.clone();
+                window_item.as_ref().width.set_binding(move || window.width.as_ref().get());
+            }
+            {
+                let window = self.clone();
+                window_item.as_ref().height.set_binding(move || window.height.as_ref().get());
+            }
+        }
+    }
+
+    fn unmap_window(self: Rc<Self>) {}
+
+    fn request_redraw(&self) {
+        // Drawing is triggered explicitly by calling draw().
+    }
+
+    fn scale_factor(&self) -> f32 {
+        self.scale_factor.as_ref().get()
+    }
+
+    fn set_scale_factor(&self, factor: f32) {
+        self.scale_factor.as_ref().set(factor);
+    }
+
+    fn set_platform_scale_factor(&self, factor: f32) {
+        self.set_scale_factor(factor)
+    }
+
+    fn set_debug_draw(&self, enabled: bool) {
+        self.debug_draw.as_ref().set(enabled);
+    }
+
+    fn set_width(&self, width: f32) {
+        self.width.as_ref().set(width.max(self.min_size.get().0).min(self.max_size.get().0));
+    }
+
+    fn set_height(&self, height: f32) {
+        self.height.as_ref().set(height.max(self.min_size.get().1).min(self.max_size.get().1));
+    }
+
+    fn size(&self) -> (f32, f32) {
+        (self.width.as_ref().get(), self.height.as_ref().get())
+    }
+
+    fn set_min_size(&self, width: f32, height: f32) {
+        self.min_size.set((width, height));
+        let (current_width, current_height) = self.size();
+        self.set_width(current_width);
+        self.set_height(current_height);
+    }
+
+    fn set_max_size(&self, width: f32, height: f32) {
+        self.max_size.set((width, height));
+        let (current_width, current_height) = self.size();
+        self.set_width(current_width);
+        self.set_height(current_height);
+    }
+
+    fn free_graphics_resources(
+        self: Rc<Self>,
+        component: core::pin::Pin<crate::component::ComponentRef>,
+    ) {
+        crate::item_rendering::free_item_rendering_data(component, &self.rendering_cache)
+    }
+
+    fn set_cursor_blink_binding(&self, prop: &crate::properties::Property<bool>) {
+        // Keep the cursor visible, so that the rendering is deterministic.
+        prop.set(true);
+    }
+
+    fn current_keyboard_modifiers(&self) -> KeyboardModifiers {
+        self.keyboard_modifiers.get()
+    }
+
+    fn set_current_keyboard_modifiers(&self, state: KeyboardModifiers) {
+        self.keyboard_modifiers.set(state)
+    }
+
+    fn set_focus_item(
+        self: Rc<Self>,
+        component: core::pin::Pin<crate::component::ComponentRef>,
+        item_ptr: *const u8,
+    ) {
+        let window = ComponentWindow::new(self.clone());
+        component.as_ref().focus_event(&crate::input::FocusEvent::FocusOut, &window);
+        component.as_ref().focus_event(&crate::input::FocusEvent::FocusIn(item_ptr), &window);
+    }
+
+    fn set_focus(
+        self: Rc<Self>,
+        component: core::pin::Pin<crate::component::ComponentRef>,
+        have_focus: bool,
+    ) {
+        let window = ComponentWindow::new(self.clone());
+        let event = if have_focus {
+            crate::input::FocusEvent::WindowReceivedFocus
+        } else {
+            crate::input::FocusEvent::WindowLostFocus
+        };
+        component.as_ref().focus_event(&event, &window);
+    }
+}
+
+/// The graphics backend that names the types of the rendering cache of the [`HeadlessWindow`]. The
+/// primitives are not lowered, the [`Canvas`] renders the high-level primitives directly, so this
+/// backend is never instantiated.
+enum HeadlessBackend {}
+
+impl crate::graphics::GraphicsBackend for HeadlessBackend {
+    type LowLevelRenderingPrimitive = HighLevelRenderingPrimitive;
+    type Frame = HeadlessBackend;
+    type RenderingPrimitivesBuilder = HeadlessPrimitivesBuilder;
+
+    fn new_rendering_primitives_builder(&mut self) -> Self::RenderingPrimitivesBuilder {
+        match *self {}
+    }
+
+    fn finish_primitives(&mut self, _builder: Self::RenderingPrimitivesBuilder) {
+        match *self {}
+    }
+
+    fn new_frame(&mut self, _width: u32, _height: u32, _clear_color: &Color) -> Self::Frame {
+        match *self {}
+    }
+
+    fn present_frame(&mut self, _frame: Self::Frame) {
+        match *self {}
+    }
+
+    fn window(&self) -> &winit::window::Window {
+        match *self {}
+    }
+}
+
+impl crate::graphics::Frame for HeadlessBackend {
+    type LowLevelRenderingPrimitive = HighLevelRenderingPrimitive;
+
+    fn render_primitive(
+        &mut self,
+        _primitive: &Self::LowLevelRenderingPrimitive,
+        _transform: &cgmath::Matrix4<f32>,
+        _variables: SharedArray<RenderingVariable>,
+    ) -> Vec<Self::LowLevelRenderingPrimitive> {
+        match *self {}
+    }
+}
+
+/// Stores the high-level primitives as they are in the rendering cache.
+struct HeadlessPrimitivesBuilder;
+
+impl crate::graphics::RenderingPrimitivesBuilder for HeadlessPrimitivesBuilder {
+    type LowLevelRenderingPrimitive = HighLevelRenderingPrimitive;
+
+    fn create(&mut self, primitive: HighLevelRenderingPrimitive) -> HighLevelRenderingPrimitive {
+        primitive
+    }
+}
+
+/// The buffer the items are rendered into, together with the stack of clip rectangles
+/// applied by [`HighLevelRenderingPrimitive::ClipRect`].
+struct Canvas {
+    image: image::RgbaImage,
+    clip_stack: Vec<Rect>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, background: Color) -> Self {
+        let background = background.to_argb_u8();
+        Self {
+            image: image::RgbaImage::from_pixel(
+                width,
+                height,
+                image::Rgba([background.red, background.green, background.blue, background.alpha]),
+            ),
+            clip_stack: Vec::new(),
+        }
+    }
+
+    fn clip_rect(&self) -> Rect {
+        self.clip_stack.last().cloned().unwrap_or_else(|| {
+            Rect::new(
+                Point::default(),
+                Size::new(self.image.width() as f32, self.image.height() as f32),
+            )
+        })
+    }
+
+    /// Renders the primitive at the given offset. Returns true if a clip rectangle was pushed,
+    /// which must be popped once the children of the item were rendered.
+    fn render_primitive(
+        &mut self,
+        primitive: &HighLevelRenderingPrimitive,
+        offset: Point,
+        variables: SharedArray<RenderingVariable>,
+    ) -> bool {
+        let mut rendering_var = variables.iter().peekable();
+
+        let offset = match rendering_var.peek() {
+            Some(RenderingVariable::Translate(x_offset, y_offset)) => {
+                rendering_var.next();
+                offset + euclid::vec2(*x_offset, *y_offset)
+            }
+            _ => offset,
+        };
+
+        match primitive {
+            HighLevelRenderingPrimitive::NoContents => {}
+            HighLevelRenderingPrimitive::Rectangle { width, height } => {
+                let rect = Rect::new(Point::default(), Size::new(*width, *height));
+                if let Some(color) = next_color(&mut rendering_var) {
+                    self.fill_triangles(&fill_rectangle(&rect, 0.), offset, color);
+                }
+            }
+            HighLevelRenderingPrimitive::BorderRectangle {
+                width,
+                height,
+                border_width,
+                border_radius,
+            } => {
+                let border_offset = *border_width / 2.;
+                let rect = Rect::new(
+                    Point::new(border_offset, border_offset),
+                    Size::new(*width - border_width, *height - *border_width),
+                );
+                if let Some(color) = next_color(&mut rendering_var) {
+                    self.fill_triangles(&fill_rectangle(&rect, *border_radius), offset, color);
+                }
+                if let Some(color) = next_color(&mut rendering_var) {
+                    if *border_width > 0. {
+                        self.fill_triangles(
+                            &stroke_rectangle(&rect, *border_width, *border_radius),
+                            offset,
+                            color,
+                        );
+                    }
+                }
+            }
+            HighLevelRenderingPrimitive::Image { source, image_fit } => {
+                if let Some(image) = load_image(source) {
+                    let image_size = Size::new(image.width() as f32, image.height() as f32);
+                    let box_width = match rendering_var.peek() {
+                        Some(RenderingVariable::ScaledWidth(width)) => {
+                            rendering_var.next();
+                            *width
+                        }
+                        _ => image_size.width,
+                    };
+                    let box_height = match rendering_var.peek() {
+                        Some(RenderingVariable::ScaledHeight(height)) => {
+                            rendering_var.next();
+                            *height
+                        }
+                        _ => image_size.height,
+                    };
+                    let (source, target) = crate::graphics::fit_image(
+                        *image_fit,
+                        image_size,
+                        Size::new(box_width, box_height),
+                    );
+                    let image = if source.size != image_size {
+                        image::imageops::crop_imm(
+                            &image,
+                            source.origin.x.round() as u32,
+                            source.origin.y.round() as u32,
+                            source.size.width.round() as u32,
+                            source.size.height.round() as u32,
+                        )
+                        .to_image()
+                    } else {
+                        image
+                    };
+                    let (width, height) =
+                        (target.size.width.round() as u32, target.size.height.round() as u32);
+                    let image = if (width, height) != image.dimensions() {
+                        image::imageops::resize(
+                            &image,
+                            width,
+                            height,
+                            image::imageops::FilterType::Nearest,
+                        )
+                    } else {
+                        image
+                    };
+                    self.draw_image(&image, offset + target.origin.to_vector());
+                }
+            }
+            HighLevelRenderingPrimitive::Text {
+                text,
+                font_family,
+                font_size,
+                font_properties,
+                right_to_left,
+                letter_spacing,
+            } => {
+                let color = match next_color(&mut rendering_var) {
+                    Some(color) => color,
+                    None => return false,
+                };
+
+                let mut selection = None;
+                if let Some(RenderingVariable::TextSelection(x, width, height)) =
+                    rendering_var.peek()
+                {
+                    let (x, width, height) = (*x, *width, *height);
+                    rendering_var.next();
+                    let foreground_color = rendering_var.next().unwrap().as_color().to_argb_f32();
+                    let background_color = rendering_var.next().unwrap().as_color().to_argb_f32();
+                    let rect = Rect::new(Point::new(x, 0.), Size::new(width, height));
+                    self.fill_triangles(&fill_rectangle(&rect, 0.), offset, background_color);
+                    selection = Some((x..x + width, foreground_color));
+                }
+
+                let font = crate::font::FONT_CACHE
+                    .with(|fc| fc.find_font(font_family, *font_properties, *font_size));
+                let mut glyphs = font
+                    .string_to_glyphs(text)
+                    .map(|(_, _, glyph_id)| glyph_id)
+                    .collect::<Vec<_>>();
+                if *right_to_left {
+                    glyphs.reverse();
+                }
+                let mut x = 0.;
+                for glyph_id in glyphs {
+                    let metrics = font.glyph_metrics(glyph_id);
+                    let advance = metrics.advance;
+                    let glyph_color = match &selection {
+                        Some((range, foreground_color)) if range.contains(&(x + advance / 2.)) => {
+                            *foreground_color
+                        }
+                        _ => color,
+                    };
+                    self.draw_glyph(
+                        &font.rasterize_glyph(glyph_id),
+                        offset + euclid::vec2(x, 0.),
+                        glyph_color,
+                    );
+                    x += metrics.advance_with_letter_spacing(*letter_spacing);
+                }
+
+                if let Some(RenderingVariable::TextDecoration(y, width, thickness)) =
+                    rendering_var.peek()
+                {
+                    let rect = Rect::new(Point::new(0., *y), Size::new(*width, *thickness));
+                    self.fill_triangles(&fill_rectangle(&rect, 0.), offset, color);
+                    rendering_var.next();
+                }
+
+                if let Some(RenderingVariable::TextCursor(x, width, height)) = rendering_var.peek()
+                {
+                    let rect = Rect::new(Point::new(*x, 0.), Size::new(*width, *height));
+                    self.fill_triangles(&fill_rectangle(&rect, 0.), offset, color);
+                }
+            }
+            HighLevelRenderingPrimitive::Path { width, height, elements, stroke_width } => {
+                let path_iter = elements.iter_fitted(*width, *height);
+                if let Some(color) = next_color(&mut rendering_var) {
+                    self.fill_triangles(&fill_path(path_iter.iter()), offset, color);
+                }
+                if let Some(color) = next_color(&mut rendering_var) {
+                    if *stroke_width > 0. {
+                        self.fill_triangles(
+                            &stroke_path(path_iter.iter(), *stroke_width),
+                            offset,
+                            color,
+                        );
+                    }
+                }
+            }
+            HighLevelRenderingPrimitive::ClipRect { width, height } => {
+                let clip = self
+                    .clip_rect()
+                    .intersection(&Rect::new(offset, Size::new(*width, *height)))
+                    .unwrap_or_else(Rect::zero);
+                self.clip_stack.push(clip);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Blends `color` with the given `coverage` over the pixel at (x, y).
+    fn blend_pixel(&mut self, x: u32, y: u32, color: ARGBColor<f32>, coverage: f32) {
+        let alpha = color.alpha * coverage;
+        if alpha <= 0. {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x, y);
+        let blend = |src: f32, dst: u8| {
+            ((src * alpha + (dst as f32 / 255.) * (1. - alpha)) * 255.).round() as u8
+        };
+        pixel.0 = [
+            blend(color.red, pixel.0[0]),
+            blend(color.green, pixel.0[1]),
+            blend(color.blue, pixel.0[2]),
+            ((alpha + (pixel.0[3] as f32 / 255.) * (1. - alpha)) * 255.).round() as u8,
+        ];
+    }
+
+    /// Returns the range of pixels covered by the rectangle, limited to the current clip.
+    fn pixel_bounds(&self, rect: Rect) -> Option<(u32, u32, u32, u32)> {
+        let rect = rect.intersection(&self.clip_rect())?;
+        Some((
+            rect.min_x().round().max(0.) as u32,
+            rect.min_y().round().max(0.) as u32,
+            (rect.max_x().round().max(0.) as u32).min(self.image.width()),
+            (rect.max_y().round().max(0.) as u32).min(self.image.height()),
+        ))
+    }
+
+    /// Fills the triangles of the tessellated geometry, sampling at the center of each pixel.
+    fn fill_triangles(
+        &mut self,
+        geometry: &VertexBuffers<Point, u16>,
+        offset: Point,
+        color: ARGBColor<f32>,
+    ) {
+        let edge =
+            |a: Point, b: Point, p: Point| (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+
+        for triangle in geometry.indices.chunks_exact(3) {
+            let vertex = |i: usize| geometry.vertices[triangle[i] as usize] + offset.to_vector();
+            let (a, b, c) = (vertex(0), vertex(1), vertex(2));
+            let area = edge(a, b, c);
+            if area == 0. {
+                continue;
+            }
+
+            let (x0, y0, x1, y1) = match self.pixel_bounds(Rect::from_points(&[a, b, c])) {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let p = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+                    let (w0, w1, w2) = (edge(b, c, p), edge(c, a, p), edge(a, b, p));
+                    let inside = if area > 0. {
+                        w0 >= 0. && w1 >= 0. && w2 >= 0.
+                    } else {
+                        w0 <= 0. && w1 <= 0. && w2 <= 0.
+                    };
+                    if inside {
+                        self.blend_pixel(x, y, color, 1.);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the image with its top-left corner at `offset`.
+    fn draw_image(&mut self, image: &image::RgbaImage, offset: Point) {
+        self.blit(image.dimensions(), offset, |x, y| {
+            let source = image.get_pixel(x, y).0;
+            let color = ARGBColor {
+                alpha: 1.,
+                red: source[0] as f32 / 255.,
+                green: source[1] as f32 / 255.,
+                blue: source[2] as f32 / 255.,
+            };
+            (color, source[3] as f32 / 255.)
+        });
+    }
+
+    /// Draws the glyph coverage with its top-left corner at `offset`, tinted with `color`.
+    fn draw_glyph(&mut self, coverage: &image::GrayImage, offset: Point, color: ARGBColor<f32>) {
+        self.blit(coverage.dimensions(), offset, |x, y| {
+            (color, coverage.get_pixel(x, y).0[0] as f32 / 255.)
+        });
+    }
+
+    /// Blends a `width` x `height` source at `offset`. `source` returns the color and the
+    /// coverage at the given position within the source.
+    fn blit(
+        &mut self,
+        (width, height): (u32, u32),
+        offset: Point,
+        source: impl Fn(u32, u32) -> (ARGBColor<f32>, f32),
+    ) {
+        let offset = offset.round();
+        let target = Rect::new(offset, Size::new(width as f32, height as f32));
+        let (x0, y0, x1, y1) = match self.pixel_bounds(target) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let (color, coverage) = source(
+                    (x as i32 - offset.x as i32) as u32,
+                    (y as i32 - offset.y as i32) as u32,
+                );
+                self.blend_pixel(x, y, color, coverage);
+            }
+        }
+    }
+}
+
+fn next_color<'a>(
+    rendering_var: &mut impl Iterator<Item = &'a RenderingVariable>,
+) -> Option<ARGBColor<f32>> {
+    rendering_var.next().map(|var| var.as_color().to_argb_f32())
+}
+
+fn load_image(source: &Resource) -> Option<image::RgbaImage> {
+    match source {
+        Resource::AbsoluteFilePath(path) => image::open(path.as_str()).ok().map(|i| i.into_rgba()),
+        Resource::EmbeddedData(slice) => {
+            image::load_from_memory(slice.as_slice()).ok().map(|i| i.into_rgba())
+        }
+        Resource::EmbeddedRgbaImage { width, height, data } => {
+            image::RgbaImage::from_raw(*width, *height, data.as_slice().to_vec())
+        }
+        Resource::None => None,
+    }
+}
+
+fn fill_rectangle(rect: &Rect, radius: f32) -> VertexBuffers<Point, u16> {
+    let mut geometry = VertexBuffers::new();
+    let mut geometry_builder =
+        BuffersBuilder::new(&mut geometry, |pos: lyon::math::Point| Point::new(pos.x, pos.y));
+
+    if radius > 0. {
+        lyon::tessellation::basic_shapes::fill_rounded_rectangle(
+            rect,
+            &lyon::tessellation::basic_shapes::BorderRadii {
+                top_left: radius,
+                top_right: radius,
+                bottom_left: radius,
+                bottom_right: radius,
+            },
+            &FillOptions::DEFAULT,
+            &mut geometry_builder,
+        )
+        .unwrap();
+    } else {
+        lyon::tessellation::basic_shapes::fill_rectangle(
+            rect,
+            &FillOptions::DEFAULT,
+            &mut geometry_builder,
+        )
+        .unwrap();
+    }
+
+    geometry
+}
+
+fn stroke_rectangle(rect: &Rect, stroke_width: f32, radius: f32) -> VertexBuffers<Point, u16> {
+    let mut geometry = VertexBuffers::new();
+    let stroke_opts = StrokeOptions::DEFAULT.with_line_width(stroke_width);
+    let mut geometry_builder =
+        BuffersBuilder::new(&mut geometry, |pos: lyon::math::Point, _: StrokeAttributes| {
+            Point::new(pos.x, pos.y)
+        });
+
+    if radius > 0. {
+        lyon::tessellation::basic_shapes::stroke_rounded_rectangle(
+            rect,
+            &lyon::tessellation::basic_shapes::BorderRadii {
+                top_left: radius,
+                top_right: radius,
+                bottom_left: radius,
+                bottom_right: radius,
+            },
+            &stroke_opts,
+            &mut geometry_builder,
+        )
+        .unwrap();
+    } else {
+        lyon::tessellation::basic_shapes::stroke_rectangle(
+            rect,
+            &stroke_opts,
+            &mut geometry_builder,
+        )
+        .unwrap();
+    }
+
+    geometry
+}
+
+fn fill_path(path: impl IntoIterator<Item = lyon::path::PathEvent>) -> VertexBuffers<Point, u16> {
+    let mut geometry = VertexBuffers::new();
+    FillTessellator::new()
+        .tessellate(
+            path,
+            &FillOptions::default(),
+            &mut BuffersBuilder::new(&mut geometry, |pos: lyon::math::Point, _: FillAttributes| {
+                Point::new(pos.x, pos.y)
+            }),
+        )
+        .unwrap();
+    geometry
+}
+
+fn stroke_path(
+    path: impl IntoIterator<Item = lyon::path::PathEvent>,
+    stroke_width: f32,
+) -> VertexBuffers<Point, u16> {
+    let mut geometry = VertexBuffers::new();
+    StrokeTessellator::new()
+        .tessellate(
+            path,
+            &StrokeOptions::DEFAULT.with_line_width(stroke_width),
+            &mut BuffersBuilder::new(
+                &mut geometry,
+                |pos: lyon::math::Point, _: StrokeAttributes| Point::new(pos.x, pos.y),
+            ),
+        )
+        .unwrap();
+    geometry
+}
+
+#[test]
+fn test_rendering_cache_dirty() {
+    let width = Box::pin(Property::new(10.));
+    let mut cache = RenderingCache::<HeadlessBackend>::default();
+    let index = cache.insert_tracked(|| HighLevelRenderingPrimitive::Rectangle {
+        width: width.as_ref().get(),
+        height: 10.,
+    });
+    let other = cache.insert_tracked(|| HighLevelRenderingPrimitive::NoContents);
+    assert!(!cache.is_dirty());
+
+    // A change of a property used by any of the primitives marks the whole cache as dirty
+    width.as_ref().set(20.);
+    assert!(cache.is_dirty());
+    assert!(cache[index].dependency_tracker.is_dirty());
+    assert!(!cache[other].dependency_tracker.is_dirty());
+
+    cache.clear_dirty();
+    let entry = &mut cache[index];
+    entry.primitive = entry.dependency_tracker.as_ref().evaluate(|| {
+        HighLevelRenderingPrimitive::Rectangle { width: width.as_ref().get(), height: 10. }
+    });
+    assert!(!cache.is_dirty());
+    width.as_ref().set(30.);
+    assert!(cache.is_dirty());
+}
@@ -30,6 +30,21 @@ pub enum MouseEventType {
     MouseMoved,
     /// The mouse exited the item or component
     MouseExit,
+    /// The mouse wheel was turned, or a scroll gesture was done on a touchpad.
+    /// The deltas are in the same coordinate system as the position of the event.
+    MouseWheel {
+        /// The horizontal scroll distance
+        delta_x: f32,
+        /// The vertical scroll distance
+        delta_y: f32,
+    },
+    /// Two fingers on a touch screen moved closer or further apart. The position of the event
+    /// is the point in the middle of the two fingers.
+    PinchGesture {
+        /// How much the distance between the two fingers changed since the previous event.
+        /// Positive when the fingers move apart.
+        distance_delta: f32,
+    },
 }
 
 /// Structur representing a mouse event
@@ -38,10 +53,108 @@ pub enum MouseEventType {
 pub struct MouseEvent {
     /// The position of the cursor
     pub pos: Point,
-    /// The action performed (pressed/released/moced)
+    /// The action performed (pressed/released/moved/wheel)
     pub what: MouseEventType,
 }
 
+/// Keeps track of the fingers on a touch screen, and turns their movements into mouse events.
+///
+/// The first finger acts as the mouse cursor: it produces the press, move and release events.
+/// When a second finger is put on the screen, the press is cancelled with a `MouseExit` event, so
+/// that lifting the fingers doesn't produce a click, and the movement of either finger produces
+/// `PinchGesture` events. No mouse event is produced until all the fingers are lifted. Any further
+/// finger is ignored.
+#[derive(Debug, Default)]
+pub struct TouchPoints {
+    primary: Option<(u64, Point)>,
+    secondary: Option<(u64, Point)>,
+    /// Set when a second finger was put on the screen, until all fingers are lifted
+    gesture: bool,
+}
+
+impl TouchPoints {
+    /// A finger with the given id was put on the screen at `pos`
+    pub fn touch_started(&mut self, id: u64, pos: Point) -> Option<MouseEvent> {
+        match (self.primary, self.secondary) {
+            (None, None) => {
+                self.primary = Some((id, pos));
+                self.gesture = false;
+                Some(MouseEvent { pos, what: MouseEventType::MousePressed })
+            }
+            (Some((_, primary_pos)), None) => {
+                self.secondary = Some((id, pos));
+                if self.gesture {
+                    None
+                } else {
+                    self.gesture = true;
+                    Some(MouseEvent { pos: primary_pos, what: MouseEventType::MouseExit })
+                }
+            }
+            (None, Some(_)) => {
+                // Only possible during a gesture, after the first finger was lifted
+                self.primary = Some((id, pos));
+                None
+            }
+            (Some(_), Some(_)) => None,
+        }
+    }
+
+    /// The finger with the given id moved to `pos`
+    pub fn touch_moved(&mut self, id: u64, pos: Point) -> Option<MouseEvent> {
+        let old_distance = self.distance();
+        match (&mut self.primary, &mut self.secondary) {
+            (Some((primary_id, p)), _) if *primary_id == id => *p = pos,
+            (_, Some((secondary_id, p))) if *secondary_id == id => *p = pos,
+            _ => return None,
+        }
+        match (self.primary, self.secondary, old_distance, self.distance()) {
+            (Some((_, a)), Some((_, b)), Some(old_distance), Some(new_distance)) => {
+                Some(MouseEvent {
+                    pos: a.lerp(b, 0.5),
+                    what: MouseEventType::PinchGesture {
+                        distance_delta: new_distance - old_distance,
+                    },
+                })
+            }
+            (Some(_), None, ..) if !self.gesture => {
+                Some(MouseEvent { pos, what: MouseEventType::MouseMoved })
+            }
+            _ => None,
+        }
+    }
+
+    /// The finger with the given id was lifted from the screen at `pos`, or the touch
+    /// was cancelled
+    pub fn touch_ended(&mut self, id: u64, pos: Point) -> Option<MouseEvent> {
+        let event = match (self.primary, self.secondary) {
+            (Some((primary_id, _)), _) if primary_id == id => {
+                self.primary = None;
+                if self.gesture {
+                    None
+                } else {
+                    Some(MouseEvent { pos, what: MouseEventType::MouseReleased })
+                }
+            }
+            (_, Some((secondary_id, _))) if secondary_id == id => {
+                self.secondary = None;
+                None
+            }
+            _ => None,
+        };
+        if self.primary.is_none() && self.secondary.is_none() {
+            self.gesture = false;
+        }
+        event
+    }
+
+    fn distance(&self) -> Option<f32> {
+        match (self.primary, self.secondary) {
+            (Some((_, a)), Some((_, b))) => Some((a - b).length()),
+            _ => None,
+        }
+    }
+}
+
 /// This value is returned by the input handler of a component
 /// to notify the run-time about how the event was handled and
 /// what the next steps are.
@@ -78,7 +191,7 @@ impl Default for InputEventResult {
 /// key mappings, different keys may produce different key codes.
 /// Key codes are typically produced when pressing or releasing a key.
 #[repr(C)]
-#[derive(Debug, Clone, Copy, PartialEq, MappedKeyCode)]
+#[derive(Debug, Clone, Copy, PartialEq, MappedKeyCode, strum_macros::EnumString)]
 #[allow(missing_docs)]
 pub enum KeyCode {
     Key1,
@@ -436,6 +549,26 @@ impl TryFrom<(&winit::event::KeyboardInput, KeyboardModifiers)> for KeyEvent {
     }
 }
 
+#[test]
+fn key_event_from_winit() {
+    #[allow(deprecated)]
+    let input = |state| winit::event::KeyboardInput {
+        scancode: 0,
+        state,
+        virtual_keycode: Some(winit::event::VirtualKeyCode::Space),
+        modifiers: Default::default(),
+    };
+    let modifiers: KeyboardModifiers = SHIFT_MODIFIER.into();
+
+    let pressed: KeyEvent =
+        TryFrom::try_from((&input(winit::event::ElementState::Pressed), modifiers)).unwrap();
+    assert_eq!(pressed, KeyEvent::KeyPressed { code: KeyCode::Space, modifiers });
+
+    let released: KeyEvent =
+        TryFrom::try_from((&input(winit::event::ElementState::Released), modifiers)).unwrap();
+    assert_eq!(released, KeyEvent::KeyReleased { code: KeyCode::Space, modifiers });
+}
+
 /// Represents how an item's key_event handler dealt with a key event.
 /// An accepted event results in no further event propagation.
 #[repr(C)]
@@ -509,6 +642,55 @@ pub fn locate_and_activate_focus_item(
     )
 }
 
+/// Returns None if the item cannot receive the keyboard focus, otherwise whether it currently
+/// has the focus. Only the `TextInput` accepts the focus.
+fn focus_state(item: core::pin::Pin<crate::items::ItemRef>) -> Option<bool> {
+    crate::items::ItemRef::downcast_pin::<crate::items::TextInput>(item).map(|text_input| {
+        crate::items::TextInput::FIELD_OFFSETS.has_focus.apply_pin(text_input).get()
+    })
+}
+
+/// Returns the item that receives the focus when the given key event was not handled by the
+/// item that has the focus: Tab moves the focus to the next focusable item of the component
+/// in the order of the element tree, and Shift+Tab to the previous one. The traversal wraps
+/// around at both ends, and the items inside an invisible element are skipped.
+///
+/// Returns None for the other key events, or if the component has no focusable item.
+pub fn tab_focus_item(component: ComponentRefPin, event: &KeyEvent) -> Option<*const u8> {
+    let backward = match event {
+        KeyEvent::KeyPressed { code: KeyCode::Tab, modifiers } => modifiers.shift(),
+        _ => return None,
+    };
+
+    let mut focusable = Vec::new();
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, visible| -> ItemVisitorResult<bool> {
+            if !*visible || crate::item_rendering::is_hidden(item) {
+                return ItemVisitorResult::Continue(false);
+            }
+            if let Some(has_focus) = focus_state(item) {
+                focusable.push((item.as_ptr(), has_focus));
+            }
+            ItemVisitorResult::Continue(true)
+        },
+        true,
+    );
+
+    let count = focusable.len();
+    if count == 0 {
+        return None;
+    }
+    let next = match (focusable.iter().position(|(_, has_focus)| *has_focus), backward) {
+        (Some(current), false) => (current + 1) % count,
+        (Some(current), true) => (current + count - 1) % count,
+        (None, false) => 0,
+        (None, true) => count - 1,
+    };
+    Some(focusable[next].0)
+}
+
 /// Feed the given mouse event into the tree of items that component holds. The
 /// event will be delivered to items in front first.
 ///
@@ -525,13 +707,18 @@ pub fn process_ungrabbed_mouse_event(
     window: &crate::eventloop::ComponentWindow,
     app_component: ComponentRefPin,
 ) -> (InputEventResult, VisitChildrenResult) {
-    let offset = Vector2D::new(0., 0.);
+    let offset = Some(Vector2D::new(0., 0.));
 
     let mut result = InputEventResult::EventIgnored;
     let item_index = crate::item_tree::visit_items(
         component,
         crate::item_tree::TraversalOrder::FrontToBack,
-        |_, item, offset| -> ItemVisitorResult<Vector2D<f32>> {
+        |_, item, offset| -> ItemVisitorResult<Option<Vector2D<f32>>> {
+            // The offset is None within a hidden item, which doesn't receive events
+            let offset = match offset {
+                Some(offset) if !crate::item_rendering::is_hidden(item) => offset,
+                _ => return ItemVisitorResult::Continue(None),
+            };
             let geom = item.as_ref().geometry();
             let geom = geom.translate(*offset);
 
@@ -551,7 +738,7 @@ pub fn process_ungrabbed_mouse_event(
                 };
             }
 
-            ItemVisitorResult::Continue(geom.origin.to_vector())
+            ItemVisitorResult::Continue(Some(geom.origin.to_vector()))
         },
         offset,
     );
@@ -628,3 +815,61 @@ pub(crate) mod ffi {
         result
     }
 }
+
+#[cfg(test)]
+mod touch_tests {
+    use super::*;
+
+    #[test]
+    fn single_finger_acts_as_mouse() {
+        let mut touch = TouchPoints::default();
+        let e = touch.touch_started(1, euclid::point2(10., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MousePressed);
+        let e = touch.touch_moved(1, euclid::point2(20., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MouseMoved);
+        assert_eq!(e.pos, euclid::point2(20., 10.));
+        let e = touch.touch_ended(1, euclid::point2(20., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MouseReleased);
+    }
+
+    #[test]
+    fn two_fingers_pinch() {
+        let mut touch = TouchPoints::default();
+        touch.touch_started(1, euclid::point2(10., 10.)).unwrap();
+        // The second finger cancels the press of the first one
+        let e = touch.touch_started(2, euclid::point2(30., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MouseExit);
+        assert_eq!(e.pos, euclid::point2(10., 10.));
+        // A third finger is ignored
+        assert!(touch.touch_started(3, euclid::point2(50., 50.)).is_none());
+        assert!(touch.touch_moved(3, euclid::point2(60., 50.)).is_none());
+
+        let e = touch.touch_moved(2, euclid::point2(40., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::PinchGesture { distance_delta: 10. });
+        assert_eq!(e.pos, euclid::point2(25., 10.));
+        let e = touch.touch_moved(1, euclid::point2(20., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::PinchGesture { distance_delta: -10. });
+
+        // The remaining finger is ignored until all the fingers are lifted, so that lifting the
+        // first finger doesn't release the mouse and produce a click
+        assert!(touch.touch_ended(1, euclid::point2(20., 10.)).is_none());
+        assert!(touch.touch_moved(2, euclid::point2(45., 10.)).is_none());
+        assert!(touch.touch_ended(2, euclid::point2(45., 10.)).is_none());
+
+        // The next touch acts as the mouse again
+        let e = touch.touch_started(4, euclid::point2(25., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MousePressed);
+        let e = touch.touch_ended(4, euclid::point2(25., 10.)).unwrap();
+        assert_eq!(e.what, MouseEventType::MouseReleased);
+    }
+
+    #[test]
+    fn second_finger_lifted_first() {
+        let mut touch = TouchPoints::default();
+        touch.touch_started(1, euclid::point2(10., 10.)).unwrap();
+        touch.touch_started(2, euclid::point2(30., 10.)).unwrap();
+        assert!(touch.touch_ended(2, euclid::point2(30., 10.)).is_none());
+        assert!(touch.touch_moved(1, euclid::point2(25., 10.)).is_none());
+        assert!(touch.touch_ended(1, euclid::point2(25., 10.)).is_none());
+    }
+}
@@ -11,11 +11,13 @@ LICENSE END */
 //! module for rendering the tree of items
 
 use super::graphics::{
-    Frame, GraphicsBackend, GraphicsWindow, RenderingCache, RenderingPrimitivesBuilder,
+    Frame, GraphicsBackend, GraphicsWindow, HighLevelRenderingPrimitive, Rect, RenderingCache,
+    RenderingPrimitivesBuilder, RenderingVariable,
 };
 use super::items::ItemRef;
 use crate::eventloop::ComponentWindow;
 use crate::item_tree::ItemVisitorResult;
+use crate::{Color, SharedArray};
 use cgmath::{Matrix4, SquareMatrix, Vector3};
 use std::cell::{Cell, RefCell};
 
@@ -36,12 +38,10 @@ impl CachedRenderingData {
         cache: &RefCell<RenderingCache<Backend>>,
         item: core::pin::Pin<ItemRef>,
         rendering_primitives_builder: &mut Backend::RenderingPrimitivesBuilder,
-        window: &std::rc::Rc<GraphicsWindow<Backend>>,
+        window: &ComponentWindow,
     ) {
-        let update_fn = || {
-            rendering_primitives_builder
-                .create(item.as_ref().rendering_primitive(&ComponentWindow::new(window.clone())))
-        };
+        let update_fn =
+            || rendering_primitives_builder.create(item.as_ref().rendering_primitive(window));
 
         if self.cache_ok.get() {
             let index = self.cache_index.get();
@@ -52,11 +52,7 @@ impl CachedRenderingData {
                     existing_entry.dependency_tracker.as_ref().evaluate(update_fn)
             }
         } else {
-            self.cache_index.set(
-                cache
-                    .borrow_mut()
-                    .insert(crate::graphics::TrackingRenderingPrimitive::new(update_fn)),
-            );
+            self.cache_index.set(cache.borrow_mut().insert_tracked(update_fn));
             self.cache_ok.set(true);
         }
     }
@@ -65,20 +61,187 @@ impl CachedRenderingData {
         if self.cache_ok.get() {
             let index = self.cache_index.get();
             cache.borrow_mut().remove(index);
+            // The item may be rendered again later, for example when it becomes visible again
+            self.cache_ok.set(false);
         }
     }
 }
 
+/// Returns true if the item is a `Visibility` item whose `visible` property is false.
+/// Such an item and all its children must not be rendered.
+pub(crate) fn is_hidden(item: core::pin::Pin<ItemRef>) -> bool {
+    ItemRef::downcast_pin::<crate::items::Visibility>(item)
+        .map_or(false, |v| !crate::items::Visibility::FIELD_OFFSETS.visible.apply_pin(v).get())
+}
+
+/// Returns the factor by which the opacity of the children of the item is multiplied: the
+/// `opacity` of an `Opacity` item, between 0 and 1, and 1 for the other items.
+pub(crate) fn opacity_factor(item: core::pin::Pin<ItemRef>) -> f32 {
+    ItemRef::downcast_pin::<crate::items::Opacity>(item).map_or(1., |o| {
+        crate::items::Opacity::FIELD_OFFSETS.opacity.apply_pin(o).get().max(0.).min(1.)
+    })
+}
+
+/// Multiplies the alpha of the colors in the rendering variables by `opacity`.
+///
+/// FIXME: This is not a real group opacity, as the children are not composited together before
+/// the opacity is applied: overlapping children show through each other. Images are not affected.
+pub(crate) fn apply_opacity(
+    variables: SharedArray<RenderingVariable>,
+    opacity: f32,
+) -> SharedArray<RenderingVariable> {
+    if opacity >= 1. {
+        return variables;
+    }
+    variables
+        .into_iter()
+        .map(|variable| match variable {
+            RenderingVariable::Color(c) => RenderingVariable::Color(Color::from_argb_u8(
+                (c.alpha() as f32 * opacity).round() as u8,
+                c.red(),
+                c.green(),
+                c.blue(),
+            )),
+            variable => variable,
+        })
+        .collect()
+}
+
+/// Returns the color the window should be cleared with before rendering the items of the component:
+/// the `background` of the root element if it is a `Window`, otherwise white.
+pub(crate) fn window_background_color(component: crate::component::ComponentRefPin) -> Color {
+    let mut color = Color::from_argb_encoded(0xffffffff);
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, _| {
+            if let Some(window_item) = ItemRef::downcast_pin::<crate::items::Window>(item) {
+                color = crate::items::Window::FIELD_OFFSETS.background.apply_pin(window_item).get();
+            }
+            ItemVisitorResult::Abort
+        },
+        (),
+    );
+    color
+}
+
+/// The rectangle of an item that the debug overlay outlines, relative to the window.
+pub(crate) struct DebugOutline {
+    pub(crate) rect: Rect,
+    /// Whether the item has children, like the elements that contain a layout.
+    pub(crate) has_children: bool,
+}
+
+/// Returns whether the windows start with the debug overlay enabled, which is the case when the
+/// `SIXTYFPS_DEBUG_DRAW` environment variable is set.
+pub(crate) fn debug_draw_from_env() -> bool {
+    std::env::var_os("SIXTYFPS_DEBUG_DRAW").is_some()
+}
+
+/// Collects the rectangles of the visible items of the component for the debug overlay, in the
+/// order in which the items are rendered. The items without a size are skipped.
+pub(crate) fn debug_outlines(component: crate::component::ComponentRefPin) -> Vec<DebugOutline> {
+    let mut outlines: Vec<DebugOutline> = Vec::new();
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, state| {
+            // The state is None within a hidden item, otherwise it contains the offset of the
+            // item and the index of the outline of its closest outlined ancestor.
+            let (offset, parent) = match state {
+                Some(state) if !is_hidden(item) => *state,
+                _ => return ItemVisitorResult::Continue(None),
+            };
+            let rect = item.as_ref().geometry().translate(offset);
+            if rect.is_empty() {
+                return ItemVisitorResult::Continue(Some((rect.origin.to_vector(), parent)));
+            }
+            if let Some(parent) = parent {
+                outlines[parent].has_children = true;
+            }
+            outlines.push(DebugOutline { rect, has_children: false });
+            ItemVisitorResult::Continue(Some((rect.origin.to_vector(), Some(outlines.len() - 1))))
+        },
+        Some((euclid::default::Vector2D::zero(), None)),
+    );
+    outlines
+}
+
+/// Returns the primitive and the rendering variables that draw a one pixel wide outline of the size
+/// of the rectangle, blue for the items with children and red for the others.
+pub(crate) fn debug_outline_primitive(
+    outline: &DebugOutline,
+) -> (HighLevelRenderingPrimitive, SharedArray<RenderingVariable>) {
+    let color = if outline.has_children {
+        Color::from_rgb_u8(0, 0, 255)
+    } else {
+        Color::from_rgb_u8(255, 0, 0)
+    };
+    (
+        HighLevelRenderingPrimitive::BorderRectangle {
+            width: outline.rect.width(),
+            height: outline.rect.height(),
+            border_width: 1.,
+            border_radius: 0.,
+        },
+        SharedArray::from([
+            RenderingVariable::Color(Color::from_argb_encoded(0)),
+            RenderingVariable::Color(color),
+        ]),
+    )
+}
+
+/// Free the graphics resources of an item that is hidden. They are created again by
+/// [`update_item_rendering_data`] when the item becomes visible.
+pub(crate) fn release_item_rendering_data<Backend: GraphicsBackend>(
+    item: core::pin::Pin<ItemRef>,
+    rendering_cache: &RefCell<RenderingCache<Backend>>,
+) {
+    item.cached_rendering_data_offset().release(rendering_cache);
+}
+
 pub(crate) fn update_item_rendering_data<Backend: GraphicsBackend>(
     item: core::pin::Pin<ItemRef>,
     rendering_cache: &RefCell<RenderingCache<Backend>>,
     rendering_primitives_builder: &mut Backend::RenderingPrimitivesBuilder,
-    window: &std::rc::Rc<GraphicsWindow<Backend>>,
+    window: &ComponentWindow,
 ) {
     let rendering_data = item.cached_rendering_data_offset();
     rendering_data.ensure_up_to_date(rendering_cache, item, rendering_primitives_builder, window);
 }
 
+/// Generates the cached rendering data of the items of the component. The items within a hidden
+/// item free their rendering data instead, so they are not rendered.
+pub(crate) fn update_rendering_cache<Backend: GraphicsBackend>(
+    component: crate::component::ComponentRefPin,
+    rendering_cache: &RefCell<RenderingCache<Backend>>,
+    rendering_primitives_builder: &mut Backend::RenderingPrimitivesBuilder,
+    window: &ComponentWindow,
+) {
+    // All the visible items are brought up to date, so the changes that happen from now on
+    // dirty the cache again.
+    rendering_cache.borrow().clear_dirty();
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, parent_hidden| {
+            let hidden = *parent_hidden || is_hidden(item);
+            if hidden {
+                release_item_rendering_data(item, rendering_cache);
+            } else {
+                update_item_rendering_data(
+                    item,
+                    rendering_cache,
+                    rendering_primitives_builder,
+                    window,
+                );
+            }
+            ItemVisitorResult::Continue(hidden)
+        },
+        false,
+    );
+}
+
 pub(crate) fn render_component_items<Backend: GraphicsBackend>(
     component: crate::component::ComponentRefPin,
     frame: &mut Backend::Frame,
@@ -93,10 +256,11 @@ pub(crate) fn render_component_items<Backend: GraphicsBackend>(
     crate::item_tree::visit_items_with_post_visit(
         component,
         crate::item_tree::TraversalOrder::BackToFront,
-        |_, item, transform| {
+        |_, item, (transform, opacity)| {
             let origin = item.as_ref().geometry().origin;
             let transform =
                 transform * Matrix4::from_translation(Vector3::new(origin.x, origin.y, 0.));
+            let opacity = opacity * opacity_factor(item);
 
             let cached_rendering_data = item.cached_rendering_data_offset();
             let cleanup_primitives = if cached_rendering_data.cache_ok.get() {
@@ -107,20 +271,20 @@ pub(crate) fn render_component_items<Backend: GraphicsBackend>(
                 frame.borrow_mut().render_primitive(
                     &primitive,
                     &transform,
-                    item.as_ref().rendering_variables(&window),
+                    apply_opacity(item.as_ref().rendering_variables(&window), opacity),
                 )
             } else {
                 Vec::new()
             };
 
-            (ItemVisitorResult::Continue(transform), (transform, cleanup_primitives))
+            (ItemVisitorResult::Continue((transform, opacity)), (transform, cleanup_primitives))
         },
         |_, _, (transform, cleanup_primitives)| {
             cleanup_primitives.into_iter().for_each(|primitive| {
                 frame.borrow_mut().render_primitive(&primitive, &transform, Default::default());
             })
         },
-        transform,
+        (transform, 1.),
     );
 }
 
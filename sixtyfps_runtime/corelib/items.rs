@@ -259,6 +259,28 @@ ItemVTable_static! {
     pub static BorderRectangleVTable for BorderRectangle
 }
 
+/// How the source of an `Image` is scaled into the geometry of the element.
+#[derive(Copy, Clone, Debug, PartialEq, strum_macros::EnumString, strum_macros::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum ImageFit {
+    /// The image is stretched to the size of the element.
+    fill,
+    /// The image is scaled to fit within the element, preserving its aspect ratio.
+    contain,
+    /// The image is scaled to cover the element, preserving its aspect ratio. The parts that
+    /// extend beyond the element are cropped.
+    cover,
+    /// The image keeps its size, centered in the element and cropped to it.
+    none,
+}
+
+impl Default for ImageFit {
+    fn default() -> Self {
+        Self::fill
+    }
+}
+
 #[repr(C)]
 #[derive(FieldOffsets, Default, BuiltinItem)]
 #[pin]
@@ -269,6 +291,7 @@ pub struct Image {
     pub y: Property<f32>,
     pub width: Property<f32>,
     pub height: Property<f32>,
+    pub image_fit: Property<ImageFit>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -287,6 +310,7 @@ impl Item for Image {
     ) -> HighLevelRenderingPrimitive {
         HighLevelRenderingPrimitive::Image {
             source: Self::FIELD_OFFSETS.source.apply_pin(self).get(),
+            image_fit: Self::FIELD_OFFSETS.image_fit.apply_pin(self).get(),
         }
     }
 
@@ -373,6 +397,52 @@ impl Default for TextVerticalAlignment {
     }
 }
 
+/// The direction in which the glyphs of a text are laid out.
+#[derive(Copy, Clone, Debug, PartialEq, strum_macros::EnumString, strum_macros::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum TextDirection {
+    ltr,
+    rtl,
+}
+
+impl Default for TextDirection {
+    fn default() -> Self {
+        Self::ltr
+    }
+}
+
+/// A line drawn across the text.
+#[derive(Copy, Clone, Debug, PartialEq, strum_macros::EnumString, strum_macros::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum TextDecoration {
+    none,
+    underline,
+    strikethrough,
+}
+
+impl Default for TextDecoration {
+    fn default() -> Self {
+        Self::none
+    }
+}
+
+/// Whether the glyphs of the text are upright or slanted.
+#[derive(Copy, Clone, Debug, PartialEq, strum_macros::EnumString, strum_macros::Display)]
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub enum FontStyle {
+    normal,
+    italic,
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        Self::normal
+    }
+}
+
 /// The implementation of the `Text` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, BuiltinItem)]
@@ -381,9 +451,14 @@ pub struct Text {
     pub text: Property<SharedString>,
     pub font_family: Property<SharedString>,
     pub font_size: Property<f32>,
+    pub font_weight: Property<i32>,
+    pub font_style: Property<FontStyle>,
     pub color: Property<Color>,
     pub horizontal_alignment: Property<TextHorizontalAlignment>,
     pub vertical_alignment: Property<TextVerticalAlignment>,
+    pub direction: Property<TextDirection>,
+    pub decoration: Property<TextDecoration>,
+    pub letter_spacing: Property<f32>,
     pub x: Property<f32>,
     pub y: Property<f32>,
     pub width: Property<f32>,
@@ -409,6 +484,10 @@ impl Item for Text {
             text: Self::FIELD_OFFSETS.text.apply_pin(self).get(),
             font_family: Self::FIELD_OFFSETS.font_family.apply_pin(self).get(),
             font_size: Text::font_pixel_size(self, window),
+            font_properties: Text::face_properties(self),
+            right_to_left: Self::FIELD_OFFSETS.direction.apply_pin(self).get()
+                == TextDirection::rtl,
+            letter_spacing: Self::FIELD_OFFSETS.letter_spacing.apply_pin(self).get(),
         }
     }
 
@@ -419,7 +498,15 @@ impl Item for Text {
         let layout_info = self.layouting_info(window);
         let rect = self.geometry();
 
-        let hor_alignment = Self::FIELD_OFFSETS.horizontal_alignment.apply_pin(self).get();
+        let mut hor_alignment = Self::FIELD_OFFSETS.horizontal_alignment.apply_pin(self).get();
+        if Self::FIELD_OFFSETS.direction.apply_pin(self).get() == TextDirection::rtl {
+            // The alignment is relative to the start of the line, which is the right edge
+            hor_alignment = match hor_alignment {
+                TextHorizontalAlignment::align_left => TextHorizontalAlignment::align_right,
+                TextHorizontalAlignment::align_center => TextHorizontalAlignment::align_center,
+                TextHorizontalAlignment::align_right => TextHorizontalAlignment::align_left,
+            };
+        }
         let translate_x = match hor_alignment {
             TextHorizontalAlignment::align_left => 0.,
             TextHorizontalAlignment::align_center => rect.width() / 2. - layout_info.min_width / 2.,
@@ -433,20 +520,37 @@ impl Item for Text {
             TextVerticalAlignment::align_bottom => rect.height() - layout_info.min_height,
         };
 
-        SharedArray::from([
+        let mut variables = SharedArray::from([
             RenderingVariable::Translate(translate_x, translate_y),
             RenderingVariable::Color(Self::FIELD_OFFSETS.color.apply_pin(self).get()),
-        ])
+        ]);
+
+        let decoration = Self::FIELD_OFFSETS.decoration.apply_pin(self).get();
+        if decoration != TextDecoration::none {
+            let font_family = Self::FIELD_OFFSETS.font_family.apply_pin(self).get();
+            let font_size = Text::font_pixel_size(self, window);
+            let (y, thickness) = crate::font::FONT_CACHE.with(|fc| {
+                let font = fc.find_font(&font_family, Text::face_properties(self), font_size);
+                match decoration {
+                    TextDecoration::underline => font.underline(),
+                    _ => font.strikethrough(),
+                }
+            });
+            variables.push(RenderingVariable::TextDecoration(y, layout_info.min_width, thickness));
+        }
+
+        variables
     }
 
     fn layouting_info(self: Pin<&Self>, window: &ComponentWindow) -> LayoutInfo {
         let font_family = Self::FIELD_OFFSETS.font_family.apply_pin(self).get();
         let font_size = Text::font_pixel_size(self, window);
         let text = Self::FIELD_OFFSETS.text.apply_pin(self).get();
+        let letter_spacing = Self::FIELD_OFFSETS.letter_spacing.apply_pin(self).get();
 
         crate::font::FONT_CACHE.with(|fc| {
-            let font = fc.find_font(&font_family, font_size);
-            let width = font.text_width(&text);
+            let font = fc.find_font(&font_family, Text::face_properties(self), font_size);
+            let width = font.text_width_with_letter_spacing(&text, letter_spacing);
             let height = font.height();
             LayoutInfo {
                 min_width: width,
@@ -487,6 +591,13 @@ impl Text {
             font_size
         }
     }
+
+    fn face_properties(self: Pin<&Self>) -> crate::font::FaceProperties {
+        crate::font::FaceProperties {
+            weight: Self::FIELD_OFFSETS.font_weight.apply_pin(self).get(),
+            italic: Self::FIELD_OFFSETS.font_style.apply_pin(self).get() == FontStyle::italic,
+        }
+    }
 }
 
 ItemVTable_static! {
@@ -514,7 +625,10 @@ pub struct TouchArea {
     /// FIXME: should maybe be as parameter to the mouse event instead. Or at least just one property
     pub mouse_x: Property<f32>,
     pub mouse_y: Property<f32>,
+    /// How much the distance between two fingers changed during the last pinch gesture
+    pub pinch_delta: Property<f32>,
     pub clicked: Signal<()>,
+    pub pinched: Signal<()>,
     /// FIXME: remove this
     pub cached_rendering_data: CachedRenderingData,
 }
@@ -555,6 +669,12 @@ impl Item for TouchArea {
         Self::FIELD_OFFSETS.mouse_x.apply_pin(self).set(event.pos.x);
         Self::FIELD_OFFSETS.mouse_y.apply_pin(self).set(event.pos.y);
 
+        if let MouseEventType::PinchGesture { distance_delta } = event.what {
+            Self::FIELD_OFFSETS.pinch_delta.apply_pin(self).set(distance_delta);
+            Self::FIELD_OFFSETS.pinched.apply_pin(self).emit(&());
+            return InputEventResult::GrabMouse;
+        }
+
         let result = if matches!(event.what, MouseEventType::MouseReleased) {
             Self::FIELD_OFFSETS.clicked.apply_pin(self).emit(&());
             InputEventResult::EventAccepted
@@ -576,6 +696,9 @@ impl Item for TouchArea {
                     InputEventResult::EventIgnored
                 }
             }
+            MouseEventType::MouseWheel { .. } | MouseEventType::PinchGesture { .. } => {
+                return InputEventResult::EventIgnored
+            }
         });
         result
     }
@@ -610,8 +733,8 @@ pub struct Path {
     pub width: Property<f32>,
     pub height: Property<f32>,
     pub elements: Property<PathData>,
-    pub fill_color: Property<Color>,
-    pub stroke_color: Property<Color>,
+    pub fill: Property<Color>,
+    pub stroke: Property<Color>,
     pub stroke_width: Property<f32>,
     pub cached_rendering_data: CachedRenderingData,
 }
@@ -642,8 +765,8 @@ impl Item for Path {
         _window: &ComponentWindow,
     ) -> SharedArray<RenderingVariable> {
         SharedArray::from([
-            RenderingVariable::Color(Self::FIELD_OFFSETS.fill_color.apply_pin(self).get()),
-            RenderingVariable::Color(Self::FIELD_OFFSETS.stroke_color.apply_pin(self).get()),
+            RenderingVariable::Color(Self::FIELD_OFFSETS.fill.apply_pin(self).get()),
+            RenderingVariable::Color(Self::FIELD_OFFSETS.stroke.apply_pin(self).get()),
         ])
     }
 
@@ -811,6 +934,14 @@ pub struct PropertyAnimation {
     pub easing: crate::animations::EasingCurve,
 }
 
+/// A signal with one string argument. cbindgen does not support tuples, so the C++ bindings
+/// declare this alias themselves.
+pub type StringSignal = Signal<(SharedString,)>;
+
+/// A signal with a width and a height as arguments. Like for `StringSignal`, the C++ bindings
+/// declare this alias themselves.
+pub type SizeSignal = Signal<(f32, f32)>;
+
 /// The implementation of the `Window` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, BuiltinItem)]
@@ -818,6 +949,15 @@ pub struct PropertyAnimation {
 pub struct Window {
     pub width: Property<f32>,
     pub height: Property<f32>,
+    pub background: Property<Color>,
+    pub scale_factor: Property<f32>,
+    pub no_decorations: Property<bool>,
+    pub always_on_top: Property<bool>,
+    pub resizable: Property<bool>,
+    pub resized: SizeSignal,
+    pub shown: Signal<()>,
+    pub file_dropped: StringSignal,
+    pub files_dropped: Signal<()>,
     pub cached_rendering_data: CachedRenderingData,
 }
 
@@ -875,6 +1015,152 @@ ItemVTable_static! {
     pub static WindowVTable for Window
 }
 
+/// Implements `Item` and `ItemConsts` for the items that the compiler inserts around elements,
+/// which have no layout and don't handle events. Unless a geometry is given, the children are
+/// positioned relative to the parent of the item, and unless a rendering primitive is given,
+/// the item draws nothing itself.
+macro_rules! impl_helper_item {
+    ($item:ident) => {
+        impl_helper_item!(
+            $item,
+            // The children are positioned relative to the parent of this item
+            geometry: |_this| Rect::default(),
+            rendering_primitive: |_this| HighLevelRenderingPrimitive::NoContents
+        );
+    };
+    ($item:ident, geometry: |$g:ident| $geometry:expr, rendering_primitive: |$r:ident| $primitive:expr) => {
+        impl Item for $item {
+            fn geometry(self: Pin<&Self>) -> Rect {
+                let $g = self;
+                $geometry
+            }
+            fn rendering_primitive(
+                self: Pin<&Self>,
+                _window: &ComponentWindow,
+            ) -> HighLevelRenderingPrimitive {
+                let $r = self;
+                $primitive
+            }
+
+            fn rendering_variables(
+                self: Pin<&Self>,
+                _window: &ComponentWindow,
+            ) -> SharedArray<RenderingVariable> {
+                SharedArray::default()
+            }
+
+            fn layouting_info(self: Pin<&Self>, _window: &ComponentWindow) -> LayoutInfo {
+                LayoutInfo::default()
+            }
+
+            fn input_event(
+                self: Pin<&Self>,
+                _event: MouseEvent,
+                _window: &ComponentWindow,
+                _app_component: ComponentRefPin,
+            ) -> InputEventResult {
+                InputEventResult::EventIgnored
+            }
+
+            fn key_event(
+                self: Pin<&Self>,
+                _: &KeyEvent,
+                _window: &ComponentWindow,
+            ) -> KeyEventResult {
+                KeyEventResult::EventIgnored
+            }
+
+            fn focus_event(self: Pin<&Self>, _: &FocusEvent, _window: &ComponentWindow) {}
+        }
+
+        impl ItemConsts for $item {
+            const cached_rendering_data_offset: const_field_offset::FieldOffset<
+                Self,
+                CachedRenderingData,
+            > = Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+        }
+    };
+}
+
+/// The item that the compiler inserts as the parent of the elements that have a binding on
+/// their `visible` property. When `visible` is false, the children are not rendered and don't
+/// receive input events, and their graphics resources are freed.
+#[repr(C)]
+#[derive(FieldOffsets, Default, BuiltinItem)]
+#[pin]
+pub struct Visibility {
+    pub visible: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_helper_item!(Visibility);
+
+ItemVTable_static! {
+    /// The VTable for `Visibility`
+    #[no_mangle]
+    pub static VisibilityVTable for Visibility
+}
+
+/// The item that the compiler inserts as the parent of the elements that have a binding on
+/// their `opacity` property. The alpha of the colors of the children is multiplied by the opacity.
+#[repr(C)]
+#[derive(FieldOffsets, Default, BuiltinItem)]
+#[pin]
+pub struct Opacity {
+    pub opacity: Property<f32>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_helper_item!(Opacity);
+
+ItemVTable_static! {
+    /// The VTable for `Opacity`
+    #[no_mangle]
+    pub static OpacityVTable for Opacity
+}
+
+/// The item that the compiler inserts as the only child of the elements that have a binding on
+/// their `clip` property, and whose children are the children of the element. When `clip` is true,
+/// the rendering of the children is restricted to the rectangle of the element.
+#[repr(C)]
+#[derive(FieldOffsets, Default, BuiltinItem)]
+#[pin]
+pub struct Clip {
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub clip: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl_helper_item!(
+    Clip,
+    // The item covers its parent element, so that the children keep their position
+    geometry: |this| {
+        euclid::rect(
+            0.,
+            0.,
+            Self::FIELD_OFFSETS.width.apply_pin(this).get(),
+            Self::FIELD_OFFSETS.height.apply_pin(this).get(),
+        )
+    },
+    rendering_primitive: |this| {
+        if Self::FIELD_OFFSETS.clip.apply_pin(this).get() {
+            HighLevelRenderingPrimitive::ClipRect {
+                width: Self::FIELD_OFFSETS.width.apply_pin(this).get(),
+                height: Self::FIELD_OFFSETS.height.apply_pin(this).get(),
+            }
+        } else {
+            HighLevelRenderingPrimitive::NoContents
+        }
+    }
+);
+
+ItemVTable_static! {
+    /// The VTable for `Clip`
+    #[no_mangle]
+    pub static ClipVTable for Clip
+}
+
 /// The implementation of the `TextInput` element
 #[repr(C)]
 #[derive(FieldOffsets, Default, BuiltinItem)]
@@ -898,6 +1184,8 @@ pub struct TextInput {
     pub cursor_visible: Property<bool>,
     pub has_focus: Property<bool>,
     pub accepted: Signal<()>,
+    pub key_pressed: StringSignal,
+    pub key_released: StringSignal,
     pub pressed: std::cell::Cell<bool>,
     pub cached_rendering_data: CachedRenderingData,
 }
@@ -920,6 +1208,9 @@ impl Item for TextInput {
             text: Self::FIELD_OFFSETS.text.apply_pin(self).get(),
             font_family: Self::FIELD_OFFSETS.font_family.apply_pin(self).get(),
             font_size: TextInput::font_pixel_size(self, window),
+            font_properties: Default::default(),
+            right_to_left: false,
+            letter_spacing: 0.,
         }
     }
 
@@ -1034,6 +1325,18 @@ impl Item for TextInput {
 
     fn key_event(self: Pin<&Self>, event: &KeyEvent, window: &ComponentWindow) -> KeyEventResult {
         use std::convert::TryFrom;
+        // The name of the key is the one of its KeyCode variant, such as "Return" or "Space"
+        match event {
+            KeyEvent::KeyPressed { code, .. } => Self::FIELD_OFFSETS
+                .key_pressed
+                .apply_pin(self)
+                .emit(&(format!("{:?}", code).into(),)),
+            KeyEvent::KeyReleased { code, .. } => Self::FIELD_OFFSETS
+                .key_released
+                .apply_pin(self)
+                .emit(&(format!("{:?}", code).into(),)),
+            KeyEvent::CharacterInput { .. } => {}
+        }
         match event {
             KeyEvent::CharacterInput { unicode_scalar, .. } => {
                 self.delete_selection();
@@ -1302,7 +1605,7 @@ impl TextInput {
         let font_family = Self::FIELD_OFFSETS.font_family.apply_pin(self).get();
         let font_size = TextInput::font_pixel_size(self, window);
         crate::font::FONT_CACHE.with(|fc| {
-            let font = fc.find_font(&font_family, font_size);
+            let font = fc.find_font(&font_family, Default::default(), font_size);
             callback(&font)
         })
     }
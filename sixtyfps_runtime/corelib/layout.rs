@@ -195,53 +195,117 @@ pub struct GridLayoutCellData<'a> {
     pub y: Option<&'a Property<Coord>>,
     pub width: Option<&'a Property<Coord>>,
     pub height: Option<&'a Property<Coord>>,
+    /// When true, the cell is left out of the layout, as if it was not there: it does not take any
+    /// space, and the rows and columns that only contain collapsed cells are removed.
+    pub collapsed: bool,
 }
 
-/// FIXME: rename with sixstyfps prefix
-#[no_mangle]
-pub extern "C" fn solve_grid_layout(data: &GridLayoutData) {
-    let (mut num_col, mut num_row) = (0, 0);
-    for cell in data.cells.iter() {
-        num_row = num_row.max(cell.row + cell.rowspan);
-        num_col = num_col.max(cell.col + cell.colspan);
-    }
+/// The rows and columns of a grid layout, without the ones that only contain collapsed cells.
+struct GridLayoutLines {
+    rows: Vec<internal::LayoutData>,
+    cols: Vec<internal::LayoutData>,
+    /// The index in `rows` of each row of the grid, or None if the row was removed
+    row_index: Vec<Option<usize>>,
+    /// The index in `cols` of each column of the grid, or None if the column was removed
+    col_index: Vec<Option<usize>>,
+}
 
-    if num_col < 1 || num_row < 1 {
-        return;
-    }
+impl GridLayoutLines {
+    fn new(cells: &[GridLayoutCellData]) -> Self {
+        let (mut num_col, mut num_row) = (0, 0);
+        for cell in cells.iter() {
+            num_row = num_row.max(cell.row + cell.rowspan);
+            num_col = num_col.max(cell.col + cell.colspan);
+        }
 
-    let mut row_layout_data = vec![internal::LayoutData::default(); num_row as usize];
-    let mut col_layout_data = vec![internal::LayoutData::default(); num_col as usize];
-    for cell in data.cells.iter() {
-        let rdata = &mut row_layout_data[cell.row as usize];
-        let cdata = &mut col_layout_data[cell.col as usize];
-        rdata.max = rdata.max.min(cell.constraint.max_height);
-        cdata.max = cdata.max.min(cell.constraint.max_width);
-        rdata.min = rdata.min.max(cell.constraint.min_height);
-        cdata.min = cdata.min.max(cell.constraint.min_width);
-        rdata.pref = rdata.pref.max(cell.constraint.min_height);
-        cdata.pref = cdata.pref.max(cell.constraint.min_width);
+        // A row or column is removed if it has collapsed cells, but no other cell. The rows and
+        // columns without any cell are kept, like before collapsing existed.
+        let mut row_index = vec![Some(0); num_row as usize];
+        let mut col_index = vec![Some(0); num_col as usize];
+        for cell in cells.iter().filter(|cell| cell.collapsed) {
+            for row in cell.row..cell.row + cell.rowspan {
+                row_index[row as usize] = None;
+            }
+            for col in cell.col..cell.col + cell.colspan {
+                col_index[col as usize] = None;
+            }
+        }
+        for cell in cells.iter().filter(|cell| !cell.collapsed) {
+            for row in cell.row..cell.row + cell.rowspan {
+                row_index[row as usize] = Some(0);
+            }
+            for col in cell.col..cell.col + cell.colspan {
+                col_index[col as usize] = Some(0);
+            }
+        }
+        let number = |index: &mut Vec<Option<usize>>| {
+            let mut count = 0;
+            for i in index.iter_mut().filter(|i| i.is_some()) {
+                *i = Some(count);
+                count += 1;
+            }
+            count
+        };
+        let mut rows = vec![internal::LayoutData::default(); number(&mut row_index)];
+        let mut cols = vec![internal::LayoutData::default(); number(&mut col_index)];
+
+        for cell in cells.iter().filter(|cell| !cell.collapsed) {
+            let rdata = &mut rows[row_index[cell.row as usize].unwrap()];
+            let cdata = &mut cols[col_index[cell.col as usize].unwrap()];
+            rdata.max = rdata.max.min(cell.constraint.max_height);
+            cdata.max = cdata.max.min(cell.constraint.max_width);
+            rdata.min = rdata.min.max(cell.constraint.min_height);
+            cdata.min = cdata.min.max(cell.constraint.min_width);
+            rdata.pref = rdata.pref.max(cell.constraint.min_height);
+            cdata.pref = cdata.pref.max(cell.constraint.min_width);
+        }
+
+        Self { rows, cols, row_index, col_index }
     }
+}
 
-    internal::layout_items(
-        &mut row_layout_data,
-        data.y + data.padding.top,
-        data.height - (data.padding.top + data.padding.bottom),
-        data.spacing,
-    );
-    internal::layout_items(
-        &mut col_layout_data,
-        data.x + data.padding.left,
-        data.width - (data.padding.left + data.padding.right),
-        data.spacing,
-    );
+/// FIXME: rename with sixstyfps prefix
+#[no_mangle]
+pub extern "C" fn solve_grid_layout(data: &GridLayoutData) {
+    let GridLayoutLines {
+        rows: mut row_layout_data,
+        cols: mut col_layout_data,
+        row_index,
+        col_index,
+    } = GridLayoutLines::new(&data.cells);
+
+    if !row_layout_data.is_empty() && !col_layout_data.is_empty() {
+        internal::layout_items(
+            &mut row_layout_data,
+            data.y + data.padding.top,
+            data.height - (data.padding.top + data.padding.bottom),
+            data.spacing,
+        );
+        internal::layout_items(
+            &mut col_layout_data,
+            data.x + data.padding.left,
+            data.width - (data.padding.left + data.padding.right),
+            data.spacing,
+        );
+    }
     for cell in data.cells.iter() {
-        let rdata = &row_layout_data[cell.row as usize];
-        let cdata = &col_layout_data[cell.col as usize];
-        cell.x.map(|p| p.set(cdata.pos));
-        cell.width.map(|p| p.set(cdata.size));
-        cell.y.map(|p| p.set(rdata.pos));
-        cell.height.map(|p| p.set(rdata.size));
+        let rdata = row_index[cell.row as usize].map(|i| &row_layout_data[i]);
+        let cdata = col_index[cell.col as usize].map(|i| &col_layout_data[i]);
+        match (rdata, cdata) {
+            (Some(rdata), Some(cdata)) if !cell.collapsed => {
+                cell.x.map(|p| p.set(cdata.pos));
+                cell.width.map(|p| p.set(cdata.size));
+                cell.y.map(|p| p.set(rdata.pos));
+                cell.height.map(|p| p.set(rdata.size));
+            }
+            _ => {
+                // A collapsed cell is at the top left corner of the layout, without any size
+                cell.x.map(|p| p.set(data.x + data.padding.left));
+                cell.width.map(|p| p.set(0.));
+                cell.y.map(|p| p.set(data.y + data.padding.top));
+                cell.height.map(|p| p.set(0.));
+            }
+        }
     }
 }
 
@@ -251,31 +315,15 @@ pub extern "C" fn grid_layout_info<'a>(
     spacing: Coord,
     padding: &Padding,
 ) -> LayoutInfo {
-    let (mut num_col, mut num_row) = (0, 0);
-    for cell in cells.iter() {
-        num_row = num_row.max(cell.row + cell.rowspan);
-        num_col = num_col.max(cell.col + cell.colspan);
-    }
+    let GridLayoutLines { rows: row_layout_data, cols: col_layout_data, .. } =
+        GridLayoutLines::new(cells);
 
-    if num_col < 1 || num_row < 1 {
+    if row_layout_data.is_empty() || col_layout_data.is_empty() {
         return LayoutInfo { min_width: 0., max_width: 0., min_height: 0., max_height: 0. };
     };
 
-    let mut row_layout_data = vec![internal::LayoutData::default(); num_row as usize];
-    let mut col_layout_data = vec![internal::LayoutData::default(); num_col as usize];
-    for cell in cells.iter() {
-        let rdata = &mut row_layout_data[cell.row as usize];
-        let cdata = &mut col_layout_data[cell.col as usize];
-        rdata.max = rdata.max.min(cell.constraint.max_height);
-        cdata.max = cdata.max.min(cell.constraint.max_width);
-        rdata.min = rdata.min.max(cell.constraint.min_height);
-        cdata.min = cdata.min.max(cell.constraint.min_width);
-        rdata.pref = rdata.pref.max(cell.constraint.min_height);
-        cdata.pref = cdata.pref.max(cell.constraint.min_width);
-    }
-
-    let spacing_h = spacing * (num_row - 1) as Coord;
-    let spacing_w = spacing * (num_col - 1) as Coord;
+    let spacing_h = spacing * (row_layout_data.len() - 1) as Coord;
+    let spacing_w = spacing * (col_layout_data.len() - 1) as Coord;
 
     let min_height = row_layout_data.iter().map(|data| data.min).sum::<Coord>()
         + spacing_h
@@ -385,3 +433,42 @@ pub extern "C" fn solve_path_layout(data: &PathLayoutData) {
         }
     }
 }
+
+#[test]
+fn test_grid_layout_collapsed() {
+    let x: Vec<Property<Coord>> = (0..3).map(|_| Property::new(-1.)).collect();
+    let width: Vec<Property<Coord>> = (0..3).map(|_| Property::new(-1.)).collect();
+    let cell = |col: u16, collapsed: bool| GridLayoutCellData {
+        col,
+        row: 0,
+        colspan: 1,
+        rowspan: 1,
+        constraint: LayoutInfo { min_width: 10., max_width: 10., ..Default::default() },
+        x: Some(&x[col as usize]),
+        width: Some(&width[col as usize]),
+        collapsed,
+        ..Default::default()
+    };
+    let padding = Padding::default();
+    let get = |p: &Property<Coord>| unsafe { core::pin::Pin::new_unchecked(p) }.get();
+
+    let cells = [cell(0, false), cell(1, true), cell(2, false)];
+    let info = grid_layout_info(&Slice::from_slice(&cells), 5., &padding);
+    assert_eq!(info.min_width, 25.);
+    solve_grid_layout(&GridLayoutData {
+        width: 25.,
+        height: 10.,
+        x: 0.,
+        y: 0.,
+        spacing: 5.,
+        padding: &padding,
+        cells: Slice::from_slice(&cells),
+    });
+    assert_eq!((get(&x[0]), get(&width[0])), (0., 10.));
+    assert_eq!(get(&width[1]), 0.);
+    assert_eq!((get(&x[2]), get(&width[2])), (15., 10.));
+
+    let cells = [cell(0, false), cell(1, false), cell(2, false)];
+    let info = grid_layout_info(&Slice::from_slice(&cells), 5., &padding);
+    assert_eq!(info.min_width, 40.);
+}
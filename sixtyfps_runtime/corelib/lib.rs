@@ -65,6 +65,8 @@ pub use graphics::PathData;
 pub mod slice;
 
 pub mod eventloop;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
 pub mod item_rendering;
 pub mod tests;
 pub mod timers;
@@ -1139,33 +1139,65 @@ mod animation_tests {
 
 /// This structure allow to run a closure that queries properties, and can report
 /// if any property we accessed have become dirty
-pub struct PropertyTracker {
-    holder: BindingHolder<()>,
+///
+/// The `DirtyHandler` is notified when the tracker becomes dirty, see
+/// [`PropertyTracker::new_with_dirty_handler`]
+pub struct PropertyTracker<DirtyHandler = ()> {
+    holder: BindingHolder<DirtyHandler>,
 }
 
-impl Default for PropertyTracker {
+impl Default for PropertyTracker<()> {
     fn default() -> Self {
-        static VT: &'static BindingVTable = &BindingVTable {
-            drop: |_| (),
-            evaluate: |_, _| BindingResult::KeepBinding,
-            mark_dirty: |_| (),
-            intercept_set: |_, _| false,
-            intercept_set_binding: |_, _| false,
-        };
+        Self::new_with_dirty_handler(())
+    }
+}
+
+/// The handler of a [`PropertyTracker`] that is notified when one of the properties it depends on
+/// changed.
+pub trait PropertyDirtyHandler {
+    /// Called when one of the properties accessed during the last evaluation changed.
+    ///
+    /// This is called while the property is being changed, so the handler must not access any
+    /// property. It can for example schedule some work for later.
+    fn notify(&self);
+}
+
+impl PropertyDirtyHandler for () {
+    fn notify(&self) {}
+}
+
+impl<DirtyHandler: PropertyDirtyHandler> PropertyTracker<DirtyHandler> {
+    /// Create a PropertyTracker whose `handler` is notified when it becomes dirty.
+    pub fn new_with_dirty_handler(handler: DirtyHandler) -> Self {
+        /// Safety: _self must be a pointer to a `BindingHolder<DirtyHandler>`
+        unsafe fn mark_dirty<DirtyHandler: PropertyDirtyHandler>(_self: *const BindingHolder) {
+            (*(_self as *const BindingHolder<DirtyHandler>)).binding.notify()
+        }
+
+        trait HasTrackerVTable {
+            const VT: &'static BindingVTable;
+        }
+        impl<DirtyHandler: PropertyDirtyHandler> HasTrackerVTable for DirtyHandler {
+            const VT: &'static BindingVTable = &BindingVTable {
+                drop: |_| (),
+                evaluate: |_, _| BindingResult::KeepBinding,
+                mark_dirty: mark_dirty::<DirtyHandler>,
+                intercept_set: |_, _| false,
+                intercept_set_binding: |_, _| false,
+            };
+        }
 
         let holder = BindingHolder {
             dependencies: Cell::new(0),
             dep_nodes: Default::default(),
-            vtable: VT,
+            vtable: <DirtyHandler as HasTrackerVTable>::VT,
             dirty: Cell::new(true), // starts dirty so it evaluates the property when used
             pinned: PhantomPinned,
-            binding: (),
+            binding: handler,
         };
         Self { holder }
     }
-}
 
-impl PropertyTracker {
     /// Any of the properties accessed during the last evaluation of the closure called
     /// from the last call to evaluate is pottentially dirty.
     pub fn is_dirty(&self) -> bool {
@@ -1176,8 +1208,14 @@ impl PropertyTracker {
     pub fn evaluate<R>(self: Pin<&Self>, f: impl FnOnce() -> R) -> R {
         // clear all the nodes so that we can start from scratch
         *self.holder.dep_nodes.borrow_mut() = Default::default();
-        // Safety: it is safe to project the holder as we don't implement drop or unpin
-        let pinned_holder = unsafe { self.map_unchecked(|s| &s.holder) };
+        // Safety: it is safe to project the holder as we don't implement drop or unpin.
+        // The BindingHolder is repr(C) with the handler last, so it can be seen as a
+        // BindingHolder<()>
+        let pinned_holder = unsafe {
+            self.map_unchecked(|s| {
+                &*(&s.holder as *const BindingHolder<DirtyHandler> as *const BindingHolder)
+            })
+        };
         let r = CURRENT_BINDING.set(pinned_holder, f);
         self.holder.dirty.set(false);
         r
@@ -1210,6 +1248,36 @@ fn test_property_listener_scope() {
     assert!(!scope.is_dirty());
 }
 
+#[test]
+fn test_property_tracker_dirty_handler() {
+    struct Handler(Rc<Cell<usize>>);
+    impl PropertyDirtyHandler for Handler {
+        fn notify(&self) {
+            self.0.set(self.0.get() + 1)
+        }
+    }
+    let notified = Rc::new(Cell::new(0));
+    let tracker = Box::pin(PropertyTracker::new_with_dirty_handler(Handler(notified.clone())));
+    let prop1 = Rc::pin(Property::new(42));
+    let prop2 = Rc::pin(Property::new(0));
+    prop2.as_ref().set_binding({
+        let prop1 = prop1.clone();
+        move || prop1.as_ref().get() + 1
+    });
+
+    assert_eq!(tracker.as_ref().evaluate(|| prop2.as_ref().get()), 43);
+    assert_eq!(notified.get(), 0);
+    prop1.as_ref().set(88);
+    // notified through the binding of prop2
+    assert_eq!(notified.get(), 1);
+    assert!(tracker.is_dirty());
+    assert_eq!(tracker.as_ref().evaluate(|| prop2.as_ref().get()), 89);
+    assert!(!tracker.is_dirty());
+    drop(tracker);
+    prop1.as_ref().set(1);
+    assert_eq!(notified.get(), 1);
+}
+
 pub(crate) mod ffi {
     use super::*;
     use core::pin::Pin;
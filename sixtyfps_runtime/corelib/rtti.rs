@@ -35,8 +35,12 @@ declare_ValueType![
     crate::Color,
     crate::PathData,
     crate::animations::EasingCurve,
+    crate::items::ImageFit,
     crate::items::TextHorizontalAlignment,
-    crate::items::TextVerticalAlignment
+    crate::items::TextVerticalAlignment,
+    crate::items::TextDirection,
+    crate::items::TextDecoration,
+    crate::items::FontStyle
 ];
 
 pub trait PropertyInfo<Item, Value> {
@@ -198,12 +202,65 @@ where
     }
 }
 
+pub trait SignalInfo<Item, Value> {
+    /// Sets the handler of the signal, which is called with the arguments converted to `Value`
+    fn set_handler(&self, item: Pin<&Item>, handler: Box<dyn Fn(&[Value])>);
+    fn emit(&self, item: Pin<&Item>, args: &[Value]) -> Result<(), ()>;
+}
+
+impl<Item, Value> SignalInfo<Item, Value> for FieldOffset<Item, crate::Signal<()>> {
+    fn set_handler(&self, item: Pin<&Item>, handler: Box<dyn Fn(&[Value])>) {
+        self.apply_pin(item).set_handler(move |()| handler(&[]))
+    }
+    fn emit(&self, item: Pin<&Item>, _args: &[Value]) -> Result<(), ()> {
+        self.apply_pin(item).emit(&());
+        Ok(())
+    }
+}
+
+impl<Item, T, Value> SignalInfo<Item, Value> for FieldOffset<Item, crate::Signal<(T,)>>
+where
+    T: Clone + TryInto<Value> + 'static,
+    Value: Clone + TryInto<T>,
+{
+    fn set_handler(&self, item: Pin<&Item>, handler: Box<dyn Fn(&[Value])>) {
+        self.apply_pin(item).set_handler(move |(arg,)| match arg.clone().try_into() {
+            Ok(value) => handler(&[value]),
+            Err(_) => panic!("the argument of the signal cannot be converted"),
+        })
+    }
+    fn emit(&self, item: Pin<&Item>, args: &[Value]) -> Result<(), ()> {
+        let arg = args.first().ok_or(())?.clone().try_into().map_err(|_| ())?;
+        self.apply_pin(item).emit(&(arg,));
+        Ok(())
+    }
+}
+
+impl<Item, T, U, Value> SignalInfo<Item, Value> for FieldOffset<Item, crate::Signal<(T, U)>>
+where
+    T: Clone + TryInto<Value> + 'static,
+    U: Clone + TryInto<Value> + 'static,
+    Value: Clone + TryInto<T> + TryInto<U>,
+{
+    fn set_handler(&self, item: Pin<&Item>, handler: Box<dyn Fn(&[Value])>) {
+        self.apply_pin(item).set_handler(move |(a, b)| {
+            match (TryInto::<Value>::try_into(a.clone()), TryInto::<Value>::try_into(b.clone())) {
+                (Ok(a), Ok(b)) => handler(&[a, b]),
+                _ => panic!("the arguments of the signal cannot be converted"),
+            }
+        })
+    }
+    fn emit(&self, item: Pin<&Item>, args: &[Value]) -> Result<(), ()> {
+        let a: T = TryInto::<T>::try_into(args.get(0).ok_or(())?.clone()).map_err(|_| ())?;
+        let b: U = TryInto::<U>::try_into(args.get(1).ok_or(())?.clone()).map_err(|_| ())?;
+        self.apply_pin(item).emit(&(a, b));
+        Ok(())
+    }
+}
+
 pub trait BuiltinItem: Sized {
     fn name() -> &'static str;
     fn properties<Value: ValueType>() -> Vec<(&'static str, &'static dyn PropertyInfo<Self, Value>)>;
     fn fields<Value: ValueType>() -> Vec<(&'static str, &'static dyn FieldInfo<Self, Value>)>;
-    fn signals() -> Vec<(
-        &'static str,
-        const_field_offset::FieldOffset<Self, crate::Signal<()>, const_field_offset::AllowPin>,
-    )>;
+    fn signals<Value: ValueType>() -> Vec<(&'static str, &'static dyn SignalInfo<Self, Value>)>;
 }
@@ -246,6 +246,23 @@ where
 
 impl Eq for SharedString {}
 
+/// Strings are ordered lexicographically by their Unicode code points, which is
+/// also the order of their UTF-8 bytes. The ordering does not depend on the locale.
+impl<T> PartialOrd<T> for SharedString
+where
+    T: ?Sized + AsRef<str>,
+{
+    fn partial_cmp(&self, other: &T) -> Option<std::cmp::Ordering> {
+        self.as_str().partial_cmp(other.as_ref())
+    }
+}
+
+impl Ord for SharedString {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
 impl From<String> for SharedString {
     fn from(s: String) -> Self {
         s.as_str().into()
@@ -282,6 +299,19 @@ fn simple_test() {
     );
 }
 
+#[test]
+fn ordering_test() {
+    let a = SharedString::from("apple");
+    let b = SharedString::from("banana");
+    assert!(a < b);
+    assert!(b > a);
+    assert!(a <= a.clone());
+    assert!(SharedString::default() < a);
+    assert!(SharedString::from("Zebra") < "apple");
+    assert!(SharedString::from("z") < "é");
+    assert_eq!(a.cmp(&b), std::cmp::Ordering::Less);
+}
+
 pub(crate) mod ffi {
     use super::*;
 
@@ -333,6 +363,26 @@ pub(crate) mod ffi {
         let str = format!("{}", n);
         core::ptr::write(out, SharedString::from(str.as_str()));
     }
+
+    /// Create a copy of the string converted to upper case, using the Unicode default case mapping.
+    /// The resulting structure must be passed to sixtyfps_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_shared_string_to_uppercase(
+        out: *mut SharedString,
+        ss: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.to_uppercase().as_str()));
+    }
+
+    /// Create a copy of the string converted to lower case, using the Unicode default case mapping.
+    /// The resulting structure must be passed to sixtyfps_shared_string_drop
+    #[no_mangle]
+    pub unsafe extern "C" fn sixtyfps_shared_string_to_lowercase(
+        out: *mut SharedString,
+        ss: &SharedString,
+    ) {
+        core::ptr::write(out, SharedString::from(ss.to_lowercase().as_str()));
+    }
 }
 
 #[test]
@@ -19,7 +19,7 @@ use crate::input::{MouseEvent, MouseEventType};
 #[no_mangle]
 pub extern "C" fn sixtyfps_mock_elapsed_time(time_in_ms: u64) {
     crate::animations::CURRENT_ANIMATION_DRIVER.with(|driver| {
-        let mut tick = driver.current_tick();
+        let mut tick = driver.last_tick();
         tick += instant::Duration::from_millis(time_in_ms);
         driver.update_animations(tick)
     })
@@ -52,6 +52,122 @@ pub extern "C" fn sixtyfps_send_mouse_click(
     );
 }
 
+/// Simulate the mouse wheel being turned while the cursor is at `x`, `y`. The deltas are in
+/// logical pixels, positive values scroll towards the top left of the content.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_mouse_wheel(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    x: f32,
+    y: f32,
+    delta_x: f32,
+    delta_y: f32,
+    window: &crate::eventloop::ComponentWindow,
+) {
+    component.as_ref().input_event(
+        MouseEvent {
+            pos: euclid::point2(x, y),
+            what: MouseEventType::MouseWheel { delta_x, delta_y },
+        },
+        window,
+        &component,
+    );
+}
+
+/// Simulate a pinch gesture around `x`, `y`: a first finger is put on the screen 10 pixels to the
+/// left, a second finger 10 pixels to the right then moves 10 pixels further, and the fingers are
+/// lifted, the first one first. The touch events are converted to mouse events as in the event loop.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_pinch_gesture(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    x: f32,
+    y: f32,
+    window: &crate::eventloop::ComponentWindow,
+) {
+    let mut touch_points = crate::input::TouchPoints::default();
+    let first = euclid::point2(x - 10., y);
+    let second = euclid::point2(x + 10., y);
+    let moved = euclid::point2(x + 20., y);
+    let events = [
+        touch_points.touch_started(1, first),
+        touch_points.touch_started(2, second),
+        touch_points.touch_moved(2, moved),
+        touch_points.touch_ended(1, first),
+        touch_points.touch_ended(2, moved),
+    ];
+    for event in events.iter().flatten() {
+        component.as_ref().input_event(*event, window, &component);
+    }
+}
+
+/// Simulate the window being resized by the windowing system to `width` x `height`: the size
+/// of the root `Window` element is changed, the layout is recomputed and the `resized` signal
+/// of the root element is emitted.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_resize_event(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    width: f32,
+    height: f32,
+    window: &crate::eventloop::ComponentWindow,
+) {
+    window.set_size(width, height);
+    let (width, height) = window.size();
+    // When the window is mapped, the size of the root element is bound to the size of the window.
+    // That is not the case for the windows used in tests, so set it directly.
+    crate::item_tree::visit_items(
+        component,
+        crate::item_tree::TraversalOrder::BackToFront,
+        |_, item, _| {
+            if let Some(window_item) =
+                crate::items::ItemRef::downcast_pin::<crate::items::Window>(item)
+            {
+                crate::items::Window::FIELD_OFFSETS.width.apply_pin(window_item).set(width);
+                crate::items::Window::FIELD_OFFSETS.height.apply_pin(window_item).set(height);
+            }
+            crate::item_tree::ItemVisitorResult::Abort
+        },
+        (),
+    );
+    component.as_ref().compute_layout();
+    crate::eventloop::emit_resized(component);
+}
+
+/// Simulate the window being shown: its first frame is rendered with
+/// [`crate::eventloop::ComponentWindow::render_now`], and the `shown` signal of the root element
+/// is emitted as the event loop does after the first frame.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_show_event(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    window: &crate::eventloop::ComponentWindow,
+) {
+    window.render_now(component);
+    crate::eventloop::ShownTracker::default().frame_drawn(component);
+}
+
+/// Simulate files being dragged over the window and dropped on it: the events the windowing system
+/// sends for the drop are processed as in the event loop, so the `file_dropped` signal of the root
+/// element is emitted with the absolute path of each of the `paths`, followed by `files_dropped`.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_dropped_files(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    paths: &crate::slice::Slice<crate::SharedString>,
+    _window: &crate::eventloop::ComponentWindow,
+) {
+    use winit::event::{Event, WindowEvent};
+    let window_id = unsafe { winit::window::WindowId::dummy() };
+    let mut dropped_files = crate::eventloop::DroppedFiles::default();
+    let events = paths
+        .iter()
+        .map(|path| WindowEvent::HoveredFile(path.as_str().into()))
+        .chain(paths.iter().map(|path| WindowEvent::DroppedFile(path.as_str().into())))
+        .map(|event| Event::WindowEvent { window_id, event })
+        .chain(core::iter::once(Event::MainEventsCleared));
+    for event in events {
+        if let Some((_, files)) = dropped_files.process_event(&event) {
+            crate::eventloop::emit_files_dropped(component, &files);
+        }
+    }
+}
+
 /// Simulate a change in keyboard modifiers pressed.
 #[no_mangle]
 pub extern "C" fn sixtyfps_set_keyboard_modifiers(
@@ -86,6 +202,23 @@ pub extern "C" fn sixtyfps_send_key_clicks(
     }
 }
 
+/// Simulate the press (if `pressed` is true) or the release of a single key.
+#[no_mangle]
+pub extern "C" fn sixtyfps_send_key_event(
+    component: core::pin::Pin<crate::component::ComponentRef>,
+    key_code: crate::input::KeyCode,
+    pressed: bool,
+    window: &crate::eventloop::ComponentWindow,
+) {
+    let modifiers = window.current_keyboard_modifiers();
+    let event = if pressed {
+        crate::input::KeyEvent::KeyPressed { code: key_code, modifiers }
+    } else {
+        crate::input::KeyEvent::KeyReleased { code: key_code, modifiers }
+    };
+    window.process_key_input(&event, component);
+}
+
 /// Simulate a character input event.
 #[no_mangle]
 pub extern "C" fn send_keyboard_string_sequence(
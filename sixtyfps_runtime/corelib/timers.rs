@@ -82,9 +82,10 @@ impl Timer {
 impl Drop for Timer {
     fn drop(&mut self) {
         if let Some(id) = self.id.get() {
-            CURRENT_TIMERS.with(|timers| {
+            // A timer in a thread local may be dropped after the list of timers was destroyed
+            let _ = CURRENT_TIMERS.try_with(|timers| {
                 timers.borrow_mut().remove_timer(id);
-            })
+            });
         }
     }
 }
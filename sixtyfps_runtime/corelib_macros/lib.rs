@@ -60,8 +60,11 @@ pub fn builtin_item(input: TokenStream) -> TokenStream {
         .map(|f| (f.ident.as_ref().unwrap(), &f.ty))
         .unzip();
 
-    let signal_field_names =
-        fields.iter().filter(|f| is_signal(&f.ty)).map(|f| f.ident.as_ref().unwrap());
+    let (signal_field_names, signal_field_types): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .filter(|f| is_signal(&f.ty))
+        .map(|f| (f.ident.as_ref().unwrap(), &f.ty))
+        .unzip();
 
     let item_name = &input.ident;
 
@@ -85,10 +88,12 @@ pub fn builtin_item(input: TokenStream) -> TokenStream {
                     (stringify!(#plain_field_names), &O as &'static dyn FieldInfo<Self, Value> )
                 } ),*]
             }
-            fn signals() -> Vec<(&'static str, const_field_offset::FieldOffset<Self, Signal<()>, const_field_offset::AllowPin>)> {
-                vec![#(
-                    (stringify!(#signal_field_names),#item_name::FIELD_OFFSETS.#signal_field_names)
-                ),*]
+            fn signals<Value: ValueType>() -> Vec<(&'static str, &'static dyn SignalInfo<Self, Value>)> {
+                vec![#( {
+                    const O : const_field_offset::FieldOffset<#item_name, #signal_field_types, const_field_offset::AllowPin> =
+                        #item_name::FIELD_OFFSETS.#signal_field_names;
+                    (stringify!(#signal_field_names), &O as &'static dyn SignalInfo<Self, Value> )
+                } ),*]
             }
         }
     )
@@ -102,8 +107,10 @@ fn type_name(ty: &syn::Type) -> String {
 fn is_property(ty: &syn::Type) -> bool {
     type_name(ty).starts_with("Property <")
 }
+/// Aliases of signal types, such as `StringSignal`, must also end with `Signal`
 fn is_signal(ty: &syn::Type) -> bool {
-    type_name(ty).to_string().starts_with("Signal <")
+    let name = type_name(ty);
+    name.starts_with("Signal <") || name.ends_with("Signal")
 }
 
 #[proc_macro_derive(MappedKeyCode)]
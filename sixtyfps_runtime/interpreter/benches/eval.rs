@@ -0,0 +1,88 @@
+/* LICENSE BEGIN
+    This file is part of the SixtyFPS Project -- https://sixtyfps.io
+    Copyright (c) 2020 Olivier Goffart <olivier.goffart@sixtyfps.io>
+    Copyright (c) 2020 Simon Hausmann <simon.hausmann@sixtyfps.io>
+
+    SPDX-License-Identifier: GPL-3.0-only
+    This file is also available under commercial licensing terms.
+    Please contact info@sixtyfps.io for more information.
+LICENSE END */
+//! Benchmark of the evaluation of the bindings by the interpreter.
+//!
+//! Each iteration changes the `input` property, which marks the bindings that depend on it as
+//! dirty, and then reads one property so that its binding is evaluated again. No window is
+//! created. The `input` benchmark only sets and reads `input`, it is the baseline to subtract
+//! from the other ones.
+//!
+//! Run with `cargo bench -p sixtyfps-interpreter`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use sixtyfps_interpreter::Value;
+
+const SOURCE: &str = r#"
+Bench := Rectangle {
+    property<int> input;
+
+    property<float> arithmetic: (input * 3 + 7) / 2 - input * input + 1.5;
+
+    property<int> step1: input + 1;
+    property<int> step2: step1 * 2;
+    property<int> step3: step2 - step1;
+    property<int> property_references: step1 + step2 + step3;
+
+    property<{a: int, b: {c: float, d: string}}> object: { a: input, b: { c: input / 2, d: "x" } };
+    property<float> object_access: object.a + object.b.c;
+
+    property<float> function_call: Math.clamp(input, 10, 1000);
+    property<string> string_function_call: to_uppercase("value: \{input}");
+    property<string> string_literal: input > 0 ? "a string literal" : "another string literal";
+
+    property<float> cast_to_float: input;
+    property<string> cast_to_string: input + 0.5;
+    property<length> cast_to_length: input * 1px + 2px;
+    property<string> casts_in_conditional: input > 500 ? input : "small";
+
+    property<bool> comparisons: input > 10 && input < 1000 || input == 5 && input != 6;
+    property<bool> string_comparison: cast_to_string == "42.5";
+}
+"#;
+
+fn eval_bindings(c: &mut Criterion) {
+    let (component, diagnostics) = sixtyfps_interpreter::load(
+        SOURCE.into(),
+        std::path::Path::new("bench.60"),
+        &Default::default(),
+    );
+    let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+    let instance = component.clone().create();
+
+    let mut group = c.benchmark_group("eval");
+    for name in &[
+        "input",
+        "arithmetic",
+        "property_references",
+        "object_access",
+        "function_call",
+        "string_function_call",
+        "string_literal",
+        "cast_to_float",
+        "cast_to_string",
+        "cast_to_length",
+        "casts_in_conditional",
+        "comparisons",
+        "string_comparison",
+    ] {
+        let mut input = 0.;
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                input += 1.;
+                component.set_property(instance.borrow(), "input", Value::Number(input)).unwrap();
+                component.get_property(instance.borrow(), name).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, eval_bindings);
+criterion_main!(benches);
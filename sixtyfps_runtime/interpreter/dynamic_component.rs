@@ -72,6 +72,23 @@ impl<'id> ComponentBox<'id> {
         InstanceRef { instance: self.instance.as_pin_ref(), component_type: &self.component_type }
     }
 
+    /// Returns the size constraints of the root element, as computed from its layouts
+    pub fn layout_info(&self) -> LayoutInfo {
+        self.borrow().as_ref().layout_info()
+    }
+
+    /// The minimum size (width, height) of the component
+    pub fn min_size(&self) -> (f32, f32) {
+        let info = self.layout_info();
+        (info.min_width, info.min_height)
+    }
+
+    /// The maximum size (width, height) of the component
+    pub fn max_size(&self) -> (f32, f32) {
+        let info = self.layout_info();
+        (info.max_width, info.max_height)
+    }
+
     pub fn window(&self) -> sixtyfps_corelib::eventloop::ComponentWindow {
         self.component_type
             .extra_data_offset
@@ -125,6 +142,9 @@ pub(crate) struct RepeaterWithinComponent<'par_id, 'sub_id> {
     pub(crate) offset: FieldOffset<Instance<'par_id>, RepeaterVec<'sub_id>>,
     /// The model
     pub(crate) model: Expression,
+    /// Offset of the model set with `set_repeater_model`, which is used instead of the `model`
+    /// expression when it is not `Value::Void`
+    model_override: FieldOffset<Instance<'par_id>, Property<eval::Value>>,
     /// Offset of the PropertyTracker
     property_tracker: Option<FieldOffset<Instance<'par_id>, PropertyTracker>>,
 }
@@ -195,7 +215,8 @@ pub struct ComponentDescription<'id> {
     item_tree: Vec<ItemTreeNode<crate::dynamic_type::Instance<'id>>>,
     pub(crate) items: HashMap<String, ItemWithinComponent>,
     pub(crate) custom_properties: HashMap<String, PropertiesWithinComponent>,
-    pub(crate) custom_signals: HashMap<String, FieldOffset<Instance<'id>, Signal<[eval::Value]>>>,
+    pub(crate) custom_signals:
+        HashMap<String, FieldOffset<Instance<'id>, Signal<[eval::Value], eval::Value>>>,
     repeater: Vec<ErasedRepeaterWithinComponent<'id>>,
     /// Map the Element::id of the repeater to the index in the `repeater` vec
     pub repeater_names: HashMap<String, usize>,
@@ -206,6 +227,59 @@ pub struct ComponentDescription<'id> {
     pub(crate) extra_data_offset: FieldOffset<Instance<'id>, ComponentExtraData>,
     /// Keep the Rc alive
     pub(crate) original: Rc<object_tree::Component>,
+    /// The string literals of the expressions of this component, converted to SharedString once
+    /// when the component is generated. They are identified by the address of the literal in the
+    /// expression tree, which is kept alive by `original`, and sorted by it.
+    string_literals: Vec<(usize, SharedString)>,
+}
+
+impl<'id> ComponentDescription<'id> {
+    /// Returns the SharedString for a string literal of this component. Evaluating the same
+    /// literal again only increments the reference count, instead of hashing or allocating a
+    /// new string. Literals that are not part of the component's expressions are converted.
+    pub(crate) fn string_literal(&self, literal: &str) -> SharedString {
+        match self
+            .string_literals
+            .binary_search_by_key(&(literal.as_ptr() as usize), |(address, _)| *address)
+        {
+            Ok(index) => self.string_literals[index].1.clone(),
+            Err(_) => SharedString::from(literal),
+        }
+    }
+}
+
+/// Converts the string literals used in the expressions of the elements of the component, so that
+/// they are resolved once at load time instead of on every evaluation.
+fn collect_string_literals(root_component: &object_tree::Component) -> Vec<(usize, SharedString)> {
+    fn visit_expression(expr: &Expression, literals: &mut Vec<(usize, SharedString)>) {
+        if let Expression::StringLiteral(s) = expr {
+            // Empty strings don't own a buffer, so they don't have a unique address
+            if !s.is_empty() {
+                literals.push((s.as_ptr() as usize, SharedString::from(s.as_str())));
+            }
+        }
+        expr.visit(|sub| visit_expression(sub, literals));
+    }
+
+    let mut literals = Vec::new();
+    object_tree::recurse_elem(&root_component.root_element, &(), &mut |elem, _| {
+        object_tree::visit_element_expressions(elem, |expr, _| {
+            visit_expression(expr, &mut literals)
+        });
+    });
+    literals.sort_by_key(|(address, _)| *address);
+    literals
+}
+
+/// Returns a pointer to the expression of a binding, for the closures that evaluate it.
+///
+/// The expression is not cloned so that its string literals keep the address under which they are
+/// found in `ComponentDescription::string_literals`.
+///
+/// The pointer stays valid as long as the instance: the expression is owned by the `original`
+/// component of the ComponentDescription, and the elements are not modified after they are loaded.
+fn binding_expression(expr: &Expression) -> *const Expression {
+    expr
 }
 
 extern "C" fn visit_children_item(
@@ -231,30 +305,10 @@ extern "C" fn visit_children_item(
             if let Some(listener_offset) = rep_in_comp.property_tracker {
                 let listener = listener_offset.apply_pin(instance);
                 if listener.is_dirty() {
-                    match listener.evaluate(|| {
-                        eval::eval_expression(
-                            &rep_in_comp.model,
-                            InstanceRef { instance, component_type },
-                            &mut Default::default(),
-                        )
-                    }) {
-                        crate::Value::Number(count) => populate_model(
-                            &mut *vec,
-                            rep_in_comp,
-                            component,
-                            (0..count as i32).into_iter().map(|v| crate::Value::Number(v as f64)),
-                        ),
-                        crate::Value::Array(a) => {
-                            populate_model(&mut *vec, rep_in_comp, component, a.into_iter())
-                        }
-                        crate::Value::Bool(b) => populate_model(
-                            &mut *vec,
-                            rep_in_comp,
-                            component,
-                            (if b { Some(crate::Value::Void) } else { None }).into_iter(),
-                        ),
-                        _ => panic!("Unsupported model"),
-                    }
+                    let model = listener.evaluate(|| {
+                        evaluate_model(rep_in_comp, InstanceRef { instance, component_type })
+                    });
+                    populate_model_value(&mut *vec, rep_in_comp, component, model);
                 }
             }
             match order {
@@ -292,9 +346,7 @@ pub(crate) struct ItemRTTI {
     vtable: &'static ItemVTable,
     type_info: dynamic_type::StaticTypeInfo,
     pub(crate) properties: HashMap<&'static str, Box<dyn eval::ErasedPropertyInfo>>,
-    /// The uszie is an offset within this item to the Signal.
-    /// Ideally, we would need a vtable::VFieldOffset<ItemVTable, corelib::Signal<()>>
-    pub(crate) signals: HashMap<&'static str, usize>,
+    pub(crate) signals: HashMap<&'static str, Box<dyn eval::ErasedSignalInfo>>,
 }
 
 fn rtti_for<T: 'static + Default + rtti::BuiltinItem + vtable::HasStaticVTable<ItemVTable>>(
@@ -306,7 +358,10 @@ fn rtti_for<T: 'static + Default + rtti::BuiltinItem + vtable::HasStaticVTable<I
             .into_iter()
             .map(|(k, v)| (k, Box::new(v) as Box<dyn eval::ErasedPropertyInfo>))
             .collect(),
-        signals: T::signals().into_iter().map(|(k, v)| (k, v.get_byte_offset())).collect(),
+        signals: T::signals()
+            .into_iter()
+            .map(|(k, v)| (k, Box::new(v) as Box<dyn eval::ErasedSignalInfo>))
+            .collect(),
     };
     (T::name(), Rc::new(rtti))
 }
@@ -343,8 +398,8 @@ fn rtti_for_flickable() -> (&'static str, Rc<ItemRTTI>) {
             item: Pin<ItemRef>,
             binding: Box<dyn Fn() -> eval::Value>,
             animation: Option<PropertyAnimation>,
-        ) {
-            (*self.0).set_binding(viewport(item), binding, animation).unwrap();
+        ) -> Result<(), ()> {
+            (*self.0).set_binding(viewport(item), binding, animation)
         }
         fn offset(&self) -> usize {
             (*self.0).offset() + Flickable::FIELD_OFFSETS.viewport.get_byte_offset()
@@ -383,6 +438,17 @@ pub fn load<'id>(
         d.add(diag);
         return (Err(()), d);
     }
+    load_syntax_node(syntax_node, diag, compiler_config, guard)
+}
+
+/// Same as [`load`], from the syntax tree of a file that was already parsed without error
+pub fn load_syntax_node<'id>(
+    syntax_node: parser::SyntaxNodeWithSourceFile,
+    diag: sixtyfps_compilerlib::diagnostics::FileDiagnostics,
+    compiler_config: &CompilerConfiguration,
+    guard: generativity::Guard<'id>,
+) -> (Result<Rc<ComponentDescription<'id>>, ()>, sixtyfps_compilerlib::diagnostics::BuildDiagnostics)
+{
     let (doc, diag) = compile_syntax_node(syntax_node, diag, compiler_config);
     if diag.has_error() {
         return (Err(()), diag);
@@ -407,6 +473,9 @@ fn generate_component<'id>(
                 rtti_for::<Path>(),
                 rtti_for_flickable(),
                 rtti_for::<Window>(),
+                rtti_for::<Visibility>(),
+                rtti_for::<Opacity>(),
+                rtti_for::<Clip>(),
                 rtti_for::<TextInput>(),
             ]
             .iter()
@@ -462,6 +531,7 @@ fn generate_component<'id>(
                     component_to_repeat: generate_component(base_component, guard),
                     offset: builder.add_field_type::<RepeaterVec>(),
                     model: repeated.model.clone(),
+                    model_override: builder.add_field_type::<Property<eval::Value>>(),
                     property_tracker: if repeated.model.is_constant() {
                         None
                     } else {
@@ -539,8 +609,10 @@ fn generate_component<'id>(
             Type::Resource => property_info::<Resource>(),
             Type::Bool => property_info::<bool>(),
             Type::Signal { .. } => {
-                custom_signals
-                    .insert(name.clone(), builder.add_field_type::<Signal<[eval::Value]>>());
+                custom_signals.insert(
+                    name.clone(),
+                    builder.add_field_type::<Signal<[eval::Value], eval::Value>>(),
+                );
                 continue;
             }
             Type::Object(_) => property_info::<eval::Value>(),
@@ -583,10 +655,6 @@ fn generate_component<'id>(
 
     let extra_data_offset = builder.add_field_type::<ComponentExtraData>();
 
-    extern "C" fn layout_info(_: ComponentRefPin) -> LayoutInfo {
-        todo!()
-    }
-
     let t = ComponentVTable {
         visit_children_item,
         layout_info,
@@ -607,6 +675,7 @@ fn generate_component<'id>(
         repeater_names,
         parent_component_offset,
         extra_data_offset,
+        string_literals: collect_string_literals(root_component),
     };
 
     Rc::new(t)
@@ -635,6 +704,76 @@ fn animation_for_element_property(
     animation_for_property(component, &element.property_animations, property_name)
 }
 
+/// Returns the model set with `set_repeater_model`, or else the value of the model expression
+fn evaluate_model<'par_id, 'sub_id>(
+    rep_in_comp: &RepeaterWithinComponent<'par_id, 'sub_id>,
+    instance_ref: InstanceRef<'_, 'par_id>,
+) -> eval::Value {
+    match rep_in_comp.model_override.apply_pin(instance_ref.instance).get() {
+        eval::Value::Void => {
+            eval::eval_expression(&rep_in_comp.model, instance_ref, &mut Default::default())
+        }
+        model => model,
+    }
+}
+
+fn populate_model_value<'par_id, 'sub_id>(
+    vec: &mut Vec<ComponentBox<'sub_id>>,
+    rep_in_comp: &RepeaterWithinComponent<'par_id, 'sub_id>,
+    component: ComponentRefPin,
+    model: eval::Value,
+) {
+    match model {
+        crate::Value::Number(count) => populate_model(
+            vec,
+            rep_in_comp,
+            component,
+            (0..count as i32).into_iter().map(|v| crate::Value::Number(v as f64)),
+        ),
+        crate::Value::Array(a) => populate_model(vec, rep_in_comp, component, a.into_iter()),
+        crate::Value::Bool(b) => populate_model(
+            vec,
+            rep_in_comp,
+            component,
+            (if b { Some(crate::Value::Void) } else { None }).into_iter(),
+        ),
+        _ => panic!("Unsupported model"),
+    }
+}
+
+/// Returns the current model of the repeater whose element has the given id
+pub(crate) fn repeater_model(
+    component: ComponentRefPin,
+    repeater_id: &str,
+) -> Result<eval::Value, ()> {
+    generativity::make_guard!(guard);
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    let index = *instance_ref.component_type.repeater_names.get(repeater_id).ok_or(())?;
+    generativity::make_guard!(guard);
+    let rep_in_comp = instance_ref.component_type.repeater[index].unerase(guard);
+    Ok(evaluate_model(rep_in_comp, instance_ref))
+}
+
+/// Replace the model of the repeater whose element has the given id, and update the
+/// instances of the repeated component right away.
+pub(crate) fn set_repeater_model(
+    component: ComponentRefPin,
+    repeater_id: &str,
+    model: eval::Value,
+) -> Result<(), ()> {
+    generativity::make_guard!(guard);
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    let index = *instance_ref.component_type.repeater_names.get(repeater_id).ok_or(())?;
+    generativity::make_guard!(guard);
+    let rep_in_comp = instance_ref.component_type.repeater[index].unerase(guard);
+    // If the model is not constant, this also marks the property tracker as dirty, so that
+    // the model is evaluated again the next time the repeater is visited.
+    rep_in_comp.model_override.apply_pin(instance_ref.instance).set(model.clone());
+    let mut vec = rep_in_comp.offset.apply(instance_ref.as_ref()).borrow_mut();
+    populate_model_value(&mut *vec, rep_in_comp, component, model);
+    Ok(())
+}
+
 fn populate_model<'par_id, 'sub_id>(
     vec: &mut Vec<ComponentBox<'sub_id>>,
     rep_in_comp: &RepeaterWithinComponent<'par_id, 'sub_id>,
@@ -690,25 +829,29 @@ pub fn instantiate<'id>(
             for (prop, expr) in &elem.bindings {
                 let ty = elem.lookup_property(prop.as_str());
                 if let Type::Signal { .. } = ty {
-                    let expr = expr.clone();
+                    let expr = binding_expression(expr);
                     let component_type = component_type.clone();
                     let instance = component_box.instance.as_ptr();
                     let c = Pin::new_unchecked(vtable::VRef::from_raw(
                         NonNull::from(&component_type.ct).cast(),
                         instance.cast(),
                     ));
-                    if let Some(signal_offset) =
-                        item_within_component.rtti.signals.get(prop.as_str())
-                    {
-                        let signal = &*(item.as_ptr().add(*signal_offset) as *const Signal<()>);
-                        signal.set_handler(move |_: &()| {
-                            generativity::make_guard!(guard);
-                            eval::eval_expression(
-                                &expr,
-                                InstanceRef::from_pin_ref(c, guard),
-                                &mut Default::default(),
-                            );
-                        })
+                    if let Some(signal) = item_within_component.rtti.signals.get(prop.as_str()) {
+                        signal.set_handler(
+                            item,
+                            Box::new(move |args| {
+                                generativity::make_guard!(guard);
+                                let mut local_context =
+                                    eval::EvalLocalContext::from_function_arguments(
+                                        args.iter().cloned().collect(),
+                                    );
+                                eval::eval_expression(
+                                    &*expr,
+                                    InstanceRef::from_pin_ref(c, guard),
+                                    &mut local_context,
+                                );
+                            }),
+                        )
                     } else if let Some(signal_offset) =
                         component_type.custom_signals.get(prop.as_str())
                     {
@@ -719,10 +862,10 @@ pub fn instantiate<'id>(
                                 args.iter().cloned().collect(),
                             );
                             eval::eval_expression(
-                                &expr,
+                                &*expr,
                                 InstanceRef::from_pin_ref(c, guard),
                                 &mut local_context,
-                            );
+                            )
                         })
                     } else {
                         panic!("unkown signal {}", prop)
@@ -743,26 +886,33 @@ pub fn instantiate<'id>(
                                 maybe_animation,
                             );
                         } else {
-                            let expr = expr.clone();
+                            let expr = binding_expression(expr);
                             let component_type = component_type.clone();
                             let instance = component_box.instance.as_ptr();
                             let c = Pin::new_unchecked(vtable::VRef::from_raw(
                                 NonNull::from(&component_type.ct).cast(),
                                 instance.cast(),
                             ));
+                            let property = item.as_ptr().add(prop_rtti.offset());
+                            let element = item_within_component.elem.clone();
+                            let name: Rc<str> = prop.as_str().into();
 
-                            prop_rtti.set_binding(
-                                item,
-                                Box::new(move || {
-                                    generativity::make_guard!(guard);
-                                    eval::eval_expression(
-                                        &expr,
-                                        InstanceRef::from_pin_ref(c, guard),
-                                        &mut Default::default(),
-                                    )
-                                }),
-                                maybe_animation,
-                            );
+                            prop_rtti
+                                .set_binding(
+                                    item,
+                                    Box::new(move || {
+                                        eval::evaluate_binding(property, &element, &name, || {
+                                            generativity::make_guard!(guard);
+                                            eval::eval_expression(
+                                                &*expr,
+                                                InstanceRef::from_pin_ref(c, guard),
+                                                &mut Default::default(),
+                                            )
+                                        })
+                                    }),
+                                    maybe_animation,
+                                )
+                                .unwrap();
                         }
                     } else if let Some(PropertiesWithinComponent {
                         offset, prop: prop_info, ..
@@ -783,23 +933,28 @@ pub fn instantiate<'id>(
                                 eval::eval_expression(expr, instance_ref, &mut Default::default());
                             prop_info.set(item, v, None).unwrap();
                         } else {
-                            let expr = expr.clone();
+                            let expr = binding_expression(expr);
                             let component_type = component_type.clone();
                             let instance = component_box.instance.as_ptr();
                             let c = Pin::new_unchecked(vtable::VRef::from_raw(
                                 NonNull::from(&component_type.ct).cast(),
                                 instance.cast(),
                             ));
+                            let property = mem.add(*offset) as *const u8;
+                            let element = item_within_component.elem.clone();
+                            let name: Rc<str> = prop.as_str().into();
                             prop_info
                                 .set_binding(
                                     item,
                                     Box::new(move || {
-                                        generativity::make_guard!(guard);
-                                        eval::eval_expression(
-                                            &expr,
-                                            InstanceRef::from_pin_ref(c, guard),
-                                            &mut Default::default(),
-                                        )
+                                        eval::evaluate_binding(property, &element, &name, || {
+                                            generativity::make_guard!(guard);
+                                            eval::eval_expression(
+                                                &*expr,
+                                                InstanceRef::from_pin_ref(c, guard),
+                                                &mut Default::default(),
+                                            )
+                                        })
                                     }),
                                     maybe_animation,
                                 )
@@ -820,24 +975,8 @@ pub fn instantiate<'id>(
             continue;
         }
         let mut vec = rep_in_comp.offset.apply(instance_ref.as_ref()).borrow_mut();
-        match eval::eval_expression(&rep_in_comp.model, instance_ref, &mut Default::default()) {
-            crate::Value::Number(count) => populate_model(
-                &mut *vec,
-                rep_in_comp,
-                component_box.borrow(),
-                (0..count as i32).into_iter().map(|v| crate::Value::Number(v as f64)),
-            ),
-            crate::Value::Array(a) => {
-                populate_model(&mut *vec, rep_in_comp, component_box.borrow(), a.into_iter())
-            }
-            crate::Value::Bool(b) => populate_model(
-                &mut *vec,
-                rep_in_comp,
-                component_box.borrow(),
-                (if b { Some(crate::Value::Void) } else { None }).into_iter(),
-            ),
-            _ => panic!("Unsupported model"),
-        }
+        let model = evaluate_model(rep_in_comp, instance_ref);
+        populate_model_value(&mut *vec, rep_in_comp, component_box.borrow(), model);
     }
 
     component_box
@@ -888,7 +1027,8 @@ impl<'a> LayoutTreeItem<'a> {
                 grid_layout.spacing,
                 &grid_layout.padding,
             ),
-            LayoutTreeItem::PathLayout(_) => todo!(),
+            // The path layout does not constrain the size of its parent
+            LayoutTreeItem::PathLayout(_) => LayoutInfo::default(),
         }
     }
 }
@@ -1027,6 +1167,11 @@ fn collect_layouts_recursively<'a, 'b>(
                         colspan: cell.colspan,
                         rowspan: cell.rowspan,
                         constraint: layout_info,
+                        collapsed: cell.collapsed.as_ref().map_or(false, |e| {
+                            eval::eval_expression(e, component, &mut Default::default())
+                                .try_into()
+                                .unwrap()
+                        }),
                     }
                 })
                 .collect();
@@ -1275,6 +1420,38 @@ extern "C" fn compute_layout(component: ComponentRefPin) {
     }
 }
 
+extern "C" fn layout_info(component: ComponentRefPin) -> LayoutInfo {
+    generativity::make_guard!(guard);
+    // This is fine since we can only be called with a component that with our vtable which is a ComponentDescription
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    let window = eval::window_ref(instance_ref).unwrap();
+
+    let root_element = &instance_ref.component_type.original.root_element;
+    let root_item = &instance_ref.component_type.items[root_element.borrow().id.as_str()];
+    let mut layout_info = unsafe {
+        root_item.item_from_component(instance_ref.as_ptr()).as_ref().layouting_info(&window)
+    };
+
+    // Only the layouts that fill the root element constrain the size of the component
+    let is_root_layout = |layout: &Layout| layout.rect().fills_element(root_element);
+
+    instance_ref
+        .component_type
+        .original
+        .layout_constraints
+        .borrow()
+        .iter()
+        .filter(|l| is_root_layout(l))
+        .for_each(|layout| {
+            let mut inverse_layout_tree = Vec::new();
+            layout_info = layout
+                .get_layout_info(instance_ref, &mut inverse_layout_tree, &window)
+                .merge(&layout_info);
+        });
+
+    layout_info
+}
+
 /// Get the component description from a ComponentRef
 ///
 /// Safety: the component must have been created by the interpreter
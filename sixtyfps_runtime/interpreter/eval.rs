@@ -19,10 +19,12 @@ use sixtyfps_compilerlib::{object_tree::ElementRc, typeregister::Type};
 use sixtyfps_corelib as corelib;
 use sixtyfps_corelib::{
     graphics::PathElement, items::ItemRef, items::PropertyAnimation, Color, PathData, Resource,
-    SharedArray, SharedString, Signal,
+    SharedArray, SharedString,
 };
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub trait ErasedPropertyInfo {
     fn get(&self, item: Pin<ItemRef>) -> Value;
@@ -32,7 +34,7 @@ pub trait ErasedPropertyInfo {
         item: Pin<ItemRef>,
         binding: Box<dyn Fn() -> Value>,
         animation: Option<PropertyAnimation>,
-    );
+    ) -> Result<(), ()>;
     fn offset(&self) -> usize;
 
     unsafe fn link_two_ways(&self, item: Pin<ItemRef>, property2: *const ());
@@ -52,8 +54,8 @@ impl<Item: vtable::HasStaticVTable<corelib::items::ItemVTable>> ErasedPropertyIn
         item: Pin<ItemRef>,
         binding: Box<dyn Fn() -> Value>,
         animation: Option<PropertyAnimation>,
-    ) {
-        (*self).set_binding(ItemRef::downcast_pin(item).unwrap(), binding, animation).unwrap();
+    ) -> Result<(), ()> {
+        (*self).set_binding(ItemRef::downcast_pin(item).unwrap(), binding, animation)
     }
     fn offset(&self) -> usize {
         (*self).offset()
@@ -63,6 +65,22 @@ impl<Item: vtable::HasStaticVTable<corelib::items::ItemVTable>> ErasedPropertyIn
     }
 }
 
+pub trait ErasedSignalInfo {
+    fn emit(&self, item: Pin<ItemRef>, args: &[Value]);
+    fn set_handler(&self, item: Pin<ItemRef>, handler: Box<dyn Fn(&[Value])>);
+}
+
+impl<Item: vtable::HasStaticVTable<corelib::items::ItemVTable>> ErasedSignalInfo
+    for &'static dyn corelib::rtti::SignalInfo<Item, Value>
+{
+    fn emit(&self, item: Pin<ItemRef>, args: &[Value]) {
+        (*self).emit(ItemRef::downcast_pin(item).unwrap(), args).unwrap()
+    }
+    fn set_handler(&self, item: Pin<ItemRef>, handler: Box<dyn Fn(&[Value])>) {
+        (*self).set_handler(ItemRef::downcast_pin(item).unwrap(), handler)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 /// This is a dynamically typed Value used in the interpreter, it need to be able
 /// to be converted from and to anything that can be stored in a Property
@@ -80,8 +98,8 @@ pub enum Value {
     Resource(Resource),
     /// An Array
     Array(Vec<Value>),
-    /// An object
-    Object(HashMap<String, Value>),
+    /// An object, with its fields sorted by name
+    Object(BTreeMap<String, Value>),
     /// A color
     Color(Color),
     /// The elements of a path
@@ -132,7 +150,7 @@ declare_value_conversion!(Number => [u32, u64, i32, i64, f32, f64, usize, isize]
 declare_value_conversion!(String => [SharedString] );
 declare_value_conversion!(Bool => [bool] );
 declare_value_conversion!(Resource => [Resource] );
-declare_value_conversion!(Object => [HashMap<String, Value>] );
+declare_value_conversion!(Object => [BTreeMap<String, Value>] );
 declare_value_conversion!(Color => [Color] );
 declare_value_conversion!(PathElements => [PathData]);
 declare_value_conversion!(EasingCurve => [corelib::animations::EasingCurve]);
@@ -164,8 +182,12 @@ macro_rules! declare_value_enum_conversion {
     };
 }
 
+declare_value_enum_conversion!(corelib::items::ImageFit, ImageFit);
 declare_value_enum_conversion!(corelib::items::TextHorizontalAlignment, TextHorizontalAlignment);
 declare_value_enum_conversion!(corelib::items::TextVerticalAlignment, TextVerticalAlignment);
+declare_value_enum_conversion!(corelib::items::TextDirection, TextDirection);
+declare_value_enum_conversion!(corelib::items::TextDecoration, TextDecoration);
+declare_value_enum_conversion!(corelib::items::FontStyle, FontStyle);
 
 /// The local variable needed for binding evaluation
 #[derive(Default)]
@@ -191,9 +213,22 @@ pub fn eval_expression(
         Expression::Invalid => panic!("invalid expression while evaluating"),
         Expression::Uncompiled(_) => panic!("uncompiled expression while evaluating"),
         Expression::TwoWayBinding(_) => panic!("invalid expression while evaluating"),
-        Expression::StringLiteral(s) => Value::String(s.into()),
-        Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
-        Expression::BoolLiteral(b) => Value::Bool(*b),
+        Expression::StringLiteral(s) => Value::String(component.component_type.string_literal(s)),
+        Expression::StringInterpolation { parts } => {
+            let mut result = String::new();
+            for part in parts {
+                match eval_expression(part, component, local_context) {
+                    Value::String(s) => result.push_str(s.as_str()),
+                    v => panic!("Interpolated expression is not a string: {:?}", v),
+                }
+            }
+            Value::String(SharedString::from(result.as_str()))
+        }
+        Expression::NumberLiteral(..)
+        | Expression::BoolLiteral(_)
+        | Expression::ResourceReference { .. }
+        | Expression::EasingCurve(_)
+        | Expression::EnumerationValue(_) => eval_literal(e),
         Expression::SignalReference { .. } => panic!("signal in expression"),
         Expression::BuiltinFunctionReference(_) => panic!(
             "naked builtin function reference not allowed, should be handled by function call"
@@ -224,15 +259,7 @@ pub fn eval_expression(
             }
         }
         Expression::Cast { from, to } => {
-            let v = eval_expression(&*from, component, local_context);
-            match (v, to) {
-                (Value::Number(n), Type::Int32) => Value::Number(n.round()),
-                (Value::Number(n), Type::String) => {
-                    Value::String(SharedString::from(format!("{}", n).as_str()))
-                }
-                (Value::Number(n), Type::Color) => Value::Color(Color::from_argb_encoded(n as u32)),
-                (v, _) => v,
-            }
+            cast_value(eval_expression(&*from, component, local_context), to)
         }
         Expression::CodeBlock(sub) => {
             let mut v = Value::Void;
@@ -242,7 +269,7 @@ pub fn eval_expression(
             v
         }
         Expression::FunctionCall { function, arguments } => {
-            let a = arguments.iter().map(|e| eval_expression(e, component, local_context));
+            let mut a = arguments.iter().map(|e| eval_expression(e, component, local_context));
             if let Expression::SignalReference(NamedReference { element, name }) = &**function {
                 let element = element.upgrade().unwrap();
                 generativity::make_guard!(guard);
@@ -253,19 +280,16 @@ pub fn eval_expression(
                 let item_info = &component_type.items[element.borrow().id.as_str()];
                 let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
 
-                if let Some(signal_offset) = item_info.rtti.signals.get(name.as_str()) {
-                    let signal =
-                        unsafe { &*(item.as_ptr().add(*signal_offset) as *const Signal<()>) };
-                    signal.emit(&());
+                if let Some(signal) = item_info.rtti.signals.get(name.as_str()) {
+                    signal.emit(item, a.collect::<Vec<_>>().as_slice());
+                    Value::Void
                 } else if let Some(signal_offset) = component_type.custom_signals.get(name.as_str())
                 {
                     let signal = signal_offset.apply(&*enclosing_component.instance);
-                    signal.emit(a.collect::<Vec<_>>().as_slice())
+                    emit_custom_signal(signal, a.collect::<Vec<_>>().as_slice(), &function.ty())
                 } else {
                     panic!("unkown signal {}", name)
                 }
-
-                Value::Void
             } else if let Expression::BuiltinFunctionReference(funcref) = &**function {
                 match funcref {
                     BuiltinFunction::GetWindowScaleFactor => {
@@ -275,6 +299,39 @@ pub fn eval_expression(
                         println!("{:?}", a);
                         Value::Void
                     }
+                    BuiltinFunction::StringToUppercase | BuiltinFunction::StringToLowercase => {
+                        let s: SharedString = match a.next() {
+                            Some(Value::String(s)) => s,
+                            _ => panic!("{:?} expects a string argument", funcref),
+                        };
+                        let converted = if matches!(funcref, BuiltinFunction::StringToUppercase) {
+                            s.to_uppercase()
+                        } else {
+                            s.to_lowercase()
+                        };
+                        Value::String(converted.as_str().into())
+                    }
+                    BuiltinFunction::ArrayLength => match a.next() {
+                        Some(Value::Array(a)) => Value::Number(a.len() as f64),
+                        // A model given as a number of rows
+                        Some(Value::Number(n)) => Value::Number(n.max(0.).floor()),
+                        _ => Value::Number(0.),
+                    },
+                    BuiltinFunction::Clamp => match (a.next(), a.next(), a.next()) {
+                        (
+                            Some(Value::Number(v)),
+                            Some(Value::Number(min)),
+                            Some(Value::Number(max)),
+                        ) => Value::Number(if v.is_nan() || v < min || min > max {
+                            min
+                        } else {
+                            v.min(max)
+                        }),
+                        _ => panic!("Math.clamp expects three numbers"),
+                    },
+                    BuiltinFunction::AnimationTick => {
+                        Value::Number(corelib::animations::animation_tick() as f64)
+                    }
                 }
             } else {
                 panic!("call of something not a signal")
@@ -307,7 +364,9 @@ pub fn eval_expression(
                         unsafe {
                             let p =
                                 Pin::new_unchecked(&*enclosing_component.as_ptr().add(x.offset));
-                            x.prop.set(p, eval(x.prop.get(p).unwrap()), None).unwrap();
+                            let value = eval(x.prop.get(p).unwrap());
+                            trace_property_write(&element, name, &value);
+                            x.prop.set(p, value, None).unwrap();
                         }
                         return Value::Void;
                     }
@@ -316,11 +375,25 @@ pub fn eval_expression(
                     &enclosing_component.component_type.items[element.borrow().id.as_str()];
                 let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
                 let p = &item_info.rtti.properties[name.as_str()];
-                p.set(item, eval(p.get(item)), None);
+                let value = eval(p.get(item));
+                trace_property_write(&element, name, &value);
+                p.set(item, value, None);
                 Value::Void
             }
             _ => panic!("typechecking should make sure this was a PropertyReference"),
         },
+        Expression::BinaryExpression { lhs, rhs, op: op @ '&' }
+        | Expression::BinaryExpression { lhs, rhs, op: op @ '|' } => {
+            // Short-circuit: the right hand side is only evaluated if the left one is not enough
+            match eval_expression(&**lhs, component, local_context) {
+                Value::Bool(lhs) if lhs == (*op == '|') => Value::Bool(lhs),
+                Value::Bool(_) => match eval_expression(&**rhs, component, local_context) {
+                    Value::Bool(rhs) => Value::Bool(rhs),
+                    rhs => panic!("unsupported {} {:?}", op, rhs),
+                },
+                lhs => panic!("unsupported {:?} {}", lhs, op),
+            }
+        }
         Expression::BinaryExpression { lhs, rhs, op } => {
             let lhs = eval_expression(&**lhs, component, local_context);
             let rhs = eval_expression(&**rhs, component, local_context);
@@ -334,10 +407,12 @@ pub fn eval_expression(
                 ('>', Value::Number(a), Value::Number(b)) => Value::Bool(a > b),
                 ('≤', Value::Number(a), Value::Number(b)) => Value::Bool(a <= b),
                 ('≥', Value::Number(a), Value::Number(b)) => Value::Bool(a >= b),
+                ('<', Value::String(a), Value::String(b)) => Value::Bool(a < b),
+                ('>', Value::String(a), Value::String(b)) => Value::Bool(a > b),
+                ('≤', Value::String(a), Value::String(b)) => Value::Bool(a <= b),
+                ('≥', Value::String(a), Value::String(b)) => Value::Bool(a >= b),
                 ('=', a, b) => Value::Bool(a == b),
                 ('!', a, b) => Value::Bool(a != b),
-                ('&', Value::Bool(a), Value::Bool(b)) => Value::Bool(a && b),
-                ('|', Value::Bool(a), Value::Bool(b)) => Value::Bool(a || b),
                 (op, lhs, rhs) => panic!("unsupported {:?} {} {:?}", lhs, op, rhs),
             }
         }
@@ -350,9 +425,6 @@ pub fn eval_expression(
                 (sub, op) => panic!("unsupported {} {:?}", op, sub),
             }
         }
-        Expression::ResourceReference { absolute_source_path } => {
-            Value::Resource(Resource::AbsoluteFilePath(absolute_source_path.into()))
-        }
         Expression::Condition { condition, true_expr, false_expr } => {
             match eval_expression(&**condition, component, local_context).try_into()
                 as Result<bool, _>
@@ -382,6 +454,42 @@ pub fn eval_expression(
         Expression::ReadLocalVariable { name, .. } => {
             local_context.local_variables.get(name).unwrap().clone()
         }
+    }
+}
+
+fn cast_value(v: Value, to: &Type) -> Value {
+    match (v, to) {
+        (Value::Number(n), Type::Int32) => Value::Number(n.round()),
+        (Value::Color(c), Type::Int32) => Value::Number(c.as_argb_encoded() as i32 as f64),
+        (Value::Number(n), Type::String) => {
+            Value::String(SharedString::from(format!("{}", n).as_str()))
+        }
+        (Value::Number(n), Type::Color) => Value::Color(Color::from_argb_encoded(n as i64 as u32)),
+        (v, _) => v,
+    }
+}
+
+/// Evaluate an expression made only of literals, which doesn't need a component.
+/// `Expression::Invalid` evaluates to `Value::Void`.
+fn eval_literal(e: &Expression) -> Value {
+    match e {
+        Expression::Invalid => Value::Void,
+        Expression::StringLiteral(s) => Value::String(s.as_str().into()),
+        Expression::NumberLiteral(n, unit) => Value::Number(unit.normalize(*n)),
+        Expression::BoolLiteral(b) => Value::Bool(*b),
+        Expression::Cast { from, to } => cast_value(eval_literal(from), to),
+        Expression::ResourceReference { absolute_source_path } => {
+            Value::Resource(Resource::AbsoluteFilePath(absolute_source_path.into()))
+        }
+        Expression::Array { values, .. } => Value::Array(values.iter().map(eval_literal).collect()),
+        Expression::Object { values, .. } => {
+            Value::Object(values.iter().map(|(k, v)| (k.clone(), eval_literal(v))).collect())
+        }
+        Expression::PathElements { elements: ExprPath::Elements(elements) }
+            if elements.is_empty() =>
+        {
+            Value::PathElements(PathData::Elements(SharedArray::default()))
+        }
         Expression::EasingCurve(curve) => Value::EasingCurve(match curve {
             EasingCurve::Linear => corelib::animations::EasingCurve::Linear,
             EasingCurve::CubicBezier(a, b, c, d) => {
@@ -391,6 +499,100 @@ pub fn eval_expression(
         Expression::EnumerationValue(value) => {
             Value::EnumerationValue(value.enumeration.name.clone(), value.to_string())
         }
+        _ => panic!("not a literal: {:?}", e),
+    }
+}
+
+/// Returns the value of `Expression::default_value_for_type(ty)`. It is `Value::Void` for the
+/// types that have no default value, such as signals.
+pub fn default_value_for_type(ty: &Type) -> Value {
+    eval_literal(&Expression::default_value_for_type(ty))
+}
+
+/// Emits a signal declared in a .60 file. If it has no handler, the default value of its
+/// return type is returned.
+pub(crate) fn emit_custom_signal(
+    signal: &corelib::Signal<[Value], Value>,
+    args: &[Value],
+    signal_ty: &Type,
+) -> Value {
+    match (signal.emit(args), signal_ty) {
+        (Value::Void, Type::Signal { return_type: Some(return_type), .. }) => {
+            default_value_for_type(return_type)
+        }
+        (value, _) => value,
+    }
+}
+
+/// Callback invoked with the element id, the property name and the new value
+/// each time the interpreter writes a property.
+pub type PropertyTraceHandler = Box<dyn Fn(&str, &str, &Value)>;
+
+/// Set when a trace handler is installed, so that writing a property does not
+/// need to access the thread local when tracing is disabled.
+static PROPERTY_TRACE_ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local!(static PROPERTY_TRACE_HANDLER: RefCell<Option<PropertyTraceHandler>> = Default::default());
+
+pub fn set_property_trace_handler(handler: Option<PropertyTraceHandler>) {
+    PROPERTY_TRACE_ENABLED.store(handler.is_some(), Ordering::Relaxed);
+    PROPERTY_TRACE_HANDLER.with(|h| *h.borrow_mut() = handler);
+}
+
+#[inline]
+fn trace_property_write(element: &ElementRc, name: &str, value: &Value) {
+    if PROPERTY_TRACE_ENABLED.load(Ordering::Relaxed) {
+        PROPERTY_TRACE_HANDLER.with(|h| {
+            if let Some(handler) = &*h.borrow() {
+                handler(&element.borrow().id, name, value)
+            }
+        })
+    }
+}
+
+thread_local!(
+    /// The address, the element and the name of the properties whose binding is being evaluated,
+    /// innermost last.
+    static BINDING_EVALUATION_STACK: RefCell<Vec<(*const u8, ElementRc, Rc<str>)>> =
+        Default::default()
+);
+
+/// Evaluates the binding of the property at the address `property`, named `name` in `element`,
+/// while keeping track of it so that `load_property` can report binding loops.
+pub(crate) fn evaluate_binding(
+    property: *const u8,
+    element: &ElementRc,
+    name: &Rc<str>,
+    f: impl FnOnce() -> Value,
+) -> Value {
+    struct PopOnDrop;
+    impl Drop for PopOnDrop {
+        fn drop(&mut self) {
+            BINDING_EVALUATION_STACK.with(|stack| stack.borrow_mut().pop());
+        }
+    }
+    BINDING_EVALUATION_STACK
+        .with(|stack| stack.borrow_mut().push((property, element.clone(), name.clone())));
+    let _pop = PopOnDrop;
+    f()
+}
+
+/// Panics with the chain of the properties involved if the binding of the property at the
+/// address `property` is being evaluated: reading it would recurse forever.
+fn check_binding_loop(property: *const u8, element: &str, name: &str) {
+    let chain = BINDING_EVALUATION_STACK.with(|stack| {
+        let stack = stack.borrow();
+        stack.iter().position(|(p, _, _)| *p == property).map(|pos| {
+            stack[pos..]
+                .iter()
+                .map(|(_, e, n)| format!("{}.{}", e.borrow().id, n))
+                .chain(std::iter::once(format!("{}.{}", element, name)))
+                .collect::<Vec<_>>()
+        })
+    });
+    // The stack must not be borrowed while panicking, as it is popped while unwinding
+    if let Some(chain) = chain {
+        panic!("Binding loop detected: {}", chain.join(" -> "));
     }
 }
 
@@ -400,9 +602,9 @@ pub fn load_property(component: InstanceRef, element: &ElementRc, name: &str) ->
     let element = element.borrow();
     if element.id == element.enclosing_component.upgrade().unwrap().root_element.borrow().id {
         if let Some(x) = enclosing_component.component_type.custom_properties.get(name) {
-            return unsafe {
-                x.prop.get(Pin::new_unchecked(&*enclosing_component.as_ptr().add(x.offset)))
-            };
+            let property = unsafe { enclosing_component.as_ptr().add(x.offset) };
+            check_binding_loop(property, &element.id, name);
+            return unsafe { x.prop.get(Pin::new_unchecked(&*property)) };
         }
     };
     let item_info = enclosing_component
@@ -410,9 +612,23 @@ pub fn load_property(component: InstanceRef, element: &ElementRc, name: &str) ->
         .items
         .get(element.id.as_str())
         .unwrap_or_else(|| panic!("Unkown element for {}.{}", element.id, name));
-    core::mem::drop(element);
     let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
-    Ok(item_info.rtti.properties.get(name).ok_or(())?.get(item))
+    let prop_info = item_info.rtti.properties.get(name).ok_or(())?;
+    check_binding_loop(unsafe { item.as_ptr().add(prop_info.offset()) }, &element.id, name);
+    core::mem::drop(element);
+    Ok(prop_info.get(item))
+}
+
+/// Coerce a value written to a property into the declared type of that property.
+///
+/// Numbers stored into an `int` property are rounded to the nearest integer, with halfway cases
+/// rounded away from zero. This is the same rule as the conversion from `float` to `int` in
+/// expressions.
+fn coerce_to_property_type(value: Value, property_type: &Type) -> Value {
+    match (value, property_type) {
+        (Value::Number(n), Type::Int32) => Value::Number(n.round()),
+        (value, _) => value,
+    }
 }
 
 pub fn store_property(
@@ -423,11 +639,48 @@ pub fn store_property(
 ) -> Result<(), ()> {
     generativity::make_guard!(guard);
     let enclosing_component = enclosing_component_for_element(&element, component_instance, guard);
+    store_property_in_enclosing_component(enclosing_component, element, name, value)
+}
+
+/// Same as [`store_property`], but the change of value is animated with the given `animation`
+/// instead of the animation declared for the property, if any.
+pub fn store_property_with_animation(
+    component_instance: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    value: Value,
+    animation: PropertyAnimation,
+) -> Result<(), ()> {
+    generativity::make_guard!(guard);
+    let enclosing_component = enclosing_component_for_element(&element, component_instance, guard);
+    store_animated_property(enclosing_component, element, name, value, Some(animation))
+}
+
+/// Same as [`store_property`], when the `enclosing_component` of the `element` is already known.
+pub fn store_property_in_enclosing_component(
+    enclosing_component: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    value: Value,
+) -> Result<(), ()> {
     let maybe_animation = crate::dynamic_component::animation_for_property(
         enclosing_component,
         &element.borrow().property_animations,
         name,
     );
+    store_animated_property(enclosing_component, element, name, value, maybe_animation)
+}
+
+fn store_animated_property(
+    enclosing_component: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    value: Value,
+    maybe_animation: Option<PropertyAnimation>,
+) -> Result<(), ()> {
+    let value = coerce_to_property_type(value, &element.borrow().lookup_property(name));
+
+    trace_property_write(element, name, &value);
 
     let component = element.borrow().enclosing_component.upgrade().unwrap();
     if element.borrow().id == component.root_element.borrow().id {
@@ -445,6 +698,44 @@ pub fn store_property(
     Ok(())
 }
 
+/// Install a binding on the property `name` of the `element`. The binding is re-evaluated
+/// whenever the properties it depends on change.
+pub fn store_property_binding(
+    component_instance: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    binding: Box<dyn Fn() -> Value>,
+) -> Result<(), ()> {
+    generativity::make_guard!(guard);
+    let enclosing_component = enclosing_component_for_element(&element, component_instance, guard);
+    let maybe_animation = crate::dynamic_component::animation_for_property(
+        enclosing_component,
+        &element.borrow().property_animations,
+        name,
+    );
+
+    let property_type = element.borrow().lookup_property(name);
+    let binding: Box<dyn Fn() -> Value> = if property_type == Type::Int32 {
+        Box::new(move || coerce_to_property_type(binding(), &Type::Int32))
+    } else {
+        binding
+    };
+
+    let component = element.borrow().enclosing_component.upgrade().unwrap();
+    if element.borrow().id == component.root_element.borrow().id {
+        if let Some(x) = enclosing_component.component_type.custom_properties.get(name) {
+            unsafe {
+                let p = Pin::new_unchecked(&*enclosing_component.as_ptr().add(x.offset));
+                return x.prop.set_binding(p, binding, maybe_animation);
+            }
+        }
+    };
+    let item_info = &enclosing_component.component_type.items[element.borrow().id.as_str()];
+    let item = unsafe { item_info.item_from_component(enclosing_component.as_ptr()) };
+    let p = &item_info.rtti.properties.get(name).ok_or(())?;
+    p.set_binding(item, binding, maybe_animation)
+}
+
 pub fn window_ref(component: InstanceRef) -> Option<sixtyfps_corelib::eventloop::ComponentWindow> {
     if let Some(parent_offset) = component.component_type.parent_component_offset {
         let parent_component =
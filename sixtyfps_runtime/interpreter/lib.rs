@@ -19,9 +19,11 @@ mod dynamic_component;
 mod dynamic_type;
 mod eval;
 
-pub use eval::Value;
+pub use eval::{default_value_for_type, PropertyTraceHandler, Value};
 
 use dynamic_component::InstanceRef;
+use sixtyfps_compilerlib::object_tree::ElementRc;
+use sixtyfps_compilerlib::parser::{SyntaxKind, SyntaxNodeEx};
 use sixtyfps_corelib::component::{ComponentRef, ComponentRefPin};
 use std::{collections::HashMap, pin::Pin, rc::Rc};
 
@@ -42,6 +44,36 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
             .collect()
     }
 
+    /// List of the properties that can be accessed on the root element, sorted by name.
+    ///
+    /// In addition to the declared properties returned by [`Self::properties`], this includes the
+    /// properties inherited from the components and the builtin element the component is based
+    /// on (for example `color` for a Rectangle). Signals are not included.
+    pub fn property_descriptors(&self) -> Vec<PropertyDescriptor> {
+        let mut result = vec![];
+        collect_property_descriptors(&self.original.root_element.borrow(), &mut result);
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+        result
+    }
+
+    /// The absolute paths of the image files referenced by this component, including the ones
+    /// referenced by the components it uses.
+    pub fn referenced_file_resources(&self) -> Vec<String> {
+        let resources = self.original.referenced_file_resources.borrow();
+        let mut resources = resources.iter().collect::<Vec<_>>();
+        resources.sort_by_key(|(_, id)| **id);
+        resources.into_iter().map(|(path, _)| path.clone()).collect()
+    }
+
+    /// The tree of elements of this component, starting with its root element.
+    ///
+    /// This is the structure of the component after the compiler transformed it: the elements
+    /// of the sub-components it uses are inlined, and the elements generated by the compiler
+    /// (for example for the layouts) are included.
+    pub fn element_tree(&self) -> ElementInfo {
+        ElementInfo::from_element(&self.original.root_element)
+    }
+
     /// Instantiate a runtime component from this ComponentDescription
     pub fn create(
         self: Rc<Self>,
@@ -70,7 +102,35 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
         }
         generativity::make_guard!(guard);
         let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
-        if let Some(alias) = self
+        let (element, name) = self.resolve_root_property(name);
+        eval::store_property(c, &element, &name, value)
+    }
+
+    /// Set a value to property, animating the change with the given `animation`. It replaces the
+    /// animation declared for this property, if any, and also works for a property without one.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if the property with this name does not exist in this component
+    pub fn set_property_with_animation(
+        &self,
+        component: ComponentRefPin,
+        name: &str,
+        value: Value,
+        animation: sixtyfps_corelib::items::PropertyAnimation,
+    ) -> Result<(), ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let (element, name) = self.resolve_root_property(name);
+        eval::store_property_with_animation(c, &element, &name, value, animation)
+    }
+
+    /// Returns the element and the name of the property that the property `name` of the root
+    /// element refers to, which is another element's property if it is an alias.
+    fn resolve_root_property(&self, name: &str) -> (ElementRc, String) {
+        match self
             .original
             .root_element
             .borrow()
@@ -78,42 +138,100 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
             .get(name)
             .and_then(|d| d.is_alias.as_ref())
         {
-            eval::store_property(c, &alias.element.upgrade().unwrap(), &alias.name, value)
-        } else {
-            eval::store_property(c, &self.original.root_element, name, value)
+            Some(alias) => (alias.element.upgrade().unwrap(), alias.name.clone()),
+            None => (self.original.root_element.clone(), name.to_owned()),
         }
     }
 
+    /// Set the values of several properties at once. This is faster than calling
+    /// [`Self::set_property`] for each of them, as the component is only looked up once.
+    /// The animations of the properties still apply to each of them individually.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if one of the properties does not exist in this component. The other properties are still set.
+    pub fn set_properties(
+        &self,
+        component: ComponentRefPin,
+        values: impl IntoIterator<Item = (impl AsRef<str>, Value)>,
+    ) -> Result<(), ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let root_element = self.original.root_element.borrow();
+        let mut result = Ok(());
+        for (name, value) in values {
+            let name = name.as_ref();
+            let r = match root_element
+                .property_declarations
+                .get(name)
+                .and_then(|d| d.is_alias.as_ref())
+            {
+                Some(alias) => {
+                    eval::store_property(c, &alias.element.upgrade().unwrap(), &alias.name, value)
+                }
+                None => eval::store_property_in_enclosing_component(
+                    c,
+                    &self.original.root_element,
+                    name,
+                    value,
+                ),
+            };
+            result = result.and(r);
+        }
+        result
+    }
+
     /// Set a binding to a property
     ///
+    /// The binding is re-evaluated whenever any property it reads changes.
+    ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
     /// or if the property with this name does not exist in this component
     pub fn set_binding(
         &self,
-        component: ComponentRef,
+        component: ComponentRefPin,
         name: &str,
         binding: Box<dyn Fn() -> Value>,
     ) -> Result<(), ()> {
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
         }
-        let x = self.custom_properties.get(name).ok_or(())?;
-        unsafe {
-            x.prop
-                .set_binding(Pin::new_unchecked(&*component.as_ptr().add(x.offset)), binding, None)
-                .unwrap()
-        };
-        Ok(())
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        if let Some(alias) = self
+            .original
+            .root_element
+            .borrow()
+            .property_declarations
+            .get(name)
+            .and_then(|d| d.is_alias.as_ref())
+        {
+            eval::store_property_binding(c, &alias.element.upgrade().unwrap(), &alias.name, binding)
+        } else {
+            eval::store_property_binding(c, &self.original.root_element, name, binding)
+        }
     }
 
     /// Return the value of a property
     ///
+    /// The name can also be qualified with the id of an element, such as `"label.text"`, to read
+    /// a property of that element. Like for [`Self::item_geometry`], only the elements declared
+    /// directly in this component can be looked up.
+    ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
-    /// or if a signal with this name does not exist in this component
+    /// or if a property with this name does not exist in this component
     pub fn get_property(&self, component: ComponentRefPin, name: &str) -> Result<Value, ()> {
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
         }
+        if let Some(dot) = name.find('.') {
+            let (element, name) = self.lookup_element_property(&name[..dot], &name[dot + 1..])?;
+            generativity::make_guard!(guard);
+            let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+            return eval::load_property(c, &element, &name);
+        }
         if let Some(alias) = self
             .original
             .root_element
@@ -133,13 +251,16 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
 
     /// Sets an handler for a signal
     ///
+    /// The value returned by the handler is the value returned by the signal. For signals
+    /// declared without a return type, the handler should return `Value::Void`.
+    ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
     /// or if the property with this name does not exist in this component
     pub fn set_signal_handler(
         &self,
         component: Pin<ComponentRef>,
         name: &str,
-        handler: Box<dyn Fn(&[Value])>,
+        handler: Box<dyn Fn(&[Value]) -> Value>,
     ) -> Result<(), ()> {
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
@@ -150,7 +271,7 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
         Ok(())
     }
 
-    /// Emits the specified signal
+    /// Emits the specified signal, and returns the value returned by its handler
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
     /// or if the signal with this name does not exist in this component
@@ -159,15 +280,511 @@ impl<'id> dynamic_component::ComponentDescription<'id> {
         component: ComponentRefPin,
         name: &str,
         args: &[Value],
-    ) -> Result<(), ()> {
+    ) -> Result<Value, ()> {
         if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
             return Err(());
         }
         let x = self.custom_signals.get(name).ok_or(())?;
         let sig = x.apply(unsafe { &*(component.as_ptr() as *const dynamic_type::Instance) });
-        sig.emit(args);
+        let ty = self.original.root_element.borrow().lookup_property(name);
+        Ok(eval::emit_custom_signal(sig, args, &ty))
+    }
+
+    /// Returns the geometry of the element with the given id, after the layouts were computed.
+    ///
+    /// The rectangle is in logical pixels, and its origin is the top-left corner of the
+    /// component. Only the elements declared directly in this component can be looked up,
+    /// not the ones inside a `for` or an `if`, nor the ones of the sub-components it uses.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if there is no element with this id
+    pub fn item_geometry(
+        &self,
+        component: ComponentRefPin,
+        id: &str,
+    ) -> Result<sixtyfps_corelib::graphics::Rect, ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        let root_node = self.original.root_element.borrow().node.as_ref().map(|n| n.0.node.clone());
+        let mut path = vec![];
+        if !find_element_path(&self.original.root_element, id, &root_node, &mut path) {
+            return Err(());
+        }
+        // The position of the root element is the position of the window, it is not included.
+        self.geometry_in_path(component, &path, 1)
+    }
+
+    /// Returns the geometry of the element with the id `element_id`, relative to the element
+    /// with the id `ancestor_id`, after the layouts were computed.
+    ///
+    /// This is like [`Self::item_geometry`], but the origin of the rectangle is the top-left corner
+    /// of the ancestor instead of the one of the component. This allows for example to scroll
+    /// an element into the view of a Flickable.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// if there is no element with one of these ids, or if the element with the id `ancestor_id`
+    /// is not an ancestor of the element with the id `element_id`.
+    pub fn element_geometry_in(
+        &self,
+        component: ComponentRefPin,
+        ancestor_id: &str,
+        element_id: &str,
+    ) -> Result<sixtyfps_corelib::graphics::Rect, ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        let root_node = self.original.root_element.borrow().node.as_ref().map(|n| n.0.node.clone());
+        let mut ancestor_path = vec![];
+        if !find_element_path(
+            &self.original.root_element,
+            ancestor_id,
+            &root_node,
+            &mut ancestor_path,
+        ) {
+            return Err(());
+        }
+        let mut path = vec![];
+        if !find_element_path(&self.original.root_element, element_id, &root_node, &mut path) {
+            return Err(());
+        }
+        let is_ancestor = path.len() > ancestor_path.len()
+            && path.iter().zip(ancestor_path.iter()).all(|(a, b)| Rc::ptr_eq(a, b));
+        if !is_ancestor {
+            return Err(());
+        }
+        self.geometry_in_path(component, &path, ancestor_path.len())
+    }
+
+    /// Replaces the model of the `for` element declared with the given id, as in
+    /// `for item in model : id := Rectangle { ... }`.
+    ///
+    /// The model must be a `Value::Array`, or a `Value::Number` for the number of repetitions.
+    /// It replaces the model expression of the `for` element for the lifetime of the component.
+    /// The repeated elements are updated right away: the instances of the items that are still
+    /// in the model are kept, so their state (for example the position of a Flickable within them)
+    /// is preserved, and only the instances at the end are created or destroyed when the model grows
+    /// or shrinks.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// if there is no `for` element with this id declared directly in this component, or if the model
+    /// is neither an array nor a number.
+    pub fn set_model(&self, component: ComponentRefPin, id: &str, model: Value) -> Result<(), ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        if !matches!(model, Value::Array(_) | Value::Number(_)) {
+            return Err(());
+        }
+        let elem = self.find_repeater(id).ok_or(())?;
+        let unique_id = elem.borrow().id.clone();
+        dynamic_component::set_repeater_model(component, &unique_id, model)
+    }
+
+    /// Returns the current model of the `for` element declared with the given id: the model set
+    /// with [`Self::set_model`], or else the value of its model expression.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// or if there is no `for` element with this id declared directly in this component.
+    pub fn get_model(&self, component: ComponentRefPin, id: &str) -> Result<Value, ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        let elem = self.find_repeater(id).ok_or(())?;
+        let unique_id = elem.borrow().id.clone();
+        dynamic_component::repeater_model(component, &unique_id)
+    }
+
+    /// Returns the type of the model of the `for` element declared with the given id.
+    /// This is an array type, or a number type when the element is repeated a number of times.
+    pub fn model_type(&self, id: &str) -> Option<sixtyfps_compilerlib::typeregister::Type> {
+        use sixtyfps_compilerlib::{expression_tree::Expression, typeregister::Type};
+        let elem = self.find_repeater(id)?;
+        let elem = elem.borrow();
+        let model = &elem.repeated.as_ref()?.model;
+        Some(match model {
+            Expression::Cast { from, to: Type::Model } => from.ty(),
+            _ => model.ty(),
+        })
+    }
+
+    /// Returns the `for` element declared with the given id. `if` elements are not included.
+    fn find_repeater(&self, id: &str) -> Option<ElementRc> {
+        let root_node = self.original.root_element.borrow().node.as_ref().map(|n| n.0.node.clone());
+        find_repeater(&self.original.root_element, id, &root_node)
+            .filter(|e| !e.borrow().repeated.as_ref().map_or(true, |r| r.is_conditional_element))
+    }
+
+    /// Returns the geometry of the last element of `path`, which is the path returned by
+    /// `find_element_path`, relative to the element at the index `origin_depth - 1`.
+    fn geometry_in_path(
+        &self,
+        component: ComponentRefPin,
+        path: &[ElementRc],
+        origin_depth: usize,
+    ) -> Result<sixtyfps_corelib::graphics::Rect, ()> {
+        // Make sure the geometry is up to date with the current value of the properties
+        component.as_ref().compute_layout();
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let length = |elem: &ElementRc, name: &str| match eval::load_property(c, elem, name)? {
+            Value::Number(n) => Ok(n as f32),
+            _ => Err(()),
+        };
+        let (mut x, mut y) = (0., 0.);
+        // The Visibility, Opacity and Clip items inserted by the compiler do not move their children.
+        for elem in path.iter().skip(origin_depth).filter(|e| {
+            !matches!(
+                e.borrow().base_type.as_native().class_name.as_str(),
+                "Visibility" | "Opacity" | "Clip"
+            )
+        }) {
+            x += length(elem, "x")?;
+            y += length(elem, "y")?;
+        }
+        let elem = path.last().unwrap();
+        let (width, height) = (length(elem, "width")?, length(elem, "height")?);
+        let scale_factor = eval::window_ref(c).map_or(1., |w| w.scale_factor());
+        Ok(sixtyfps_corelib::graphics::Rect::new(
+            sixtyfps_corelib::graphics::Point::new(x, y) / scale_factor,
+            sixtyfps_corelib::graphics::Size::new(width, height) / scale_factor,
+        ))
+    }
+
+    /// Gives the keyboard focus to the element with the given id.
+    ///
+    /// Like for [`Self::item_geometry`], only the elements declared directly in this component
+    /// can be looked up.
+    ///
+    /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
+    /// if there is no element with this id, or if this element cannot have the focus
+    pub fn set_focus_item(&self, component: ComponentRefPin, id: &str) -> Result<(), ()> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            return Err(());
+        }
+        let root_node = self.original.root_element.borrow().node.as_ref().map(|n| n.0.node.clone());
+        let mut path = vec![];
+        if !find_element_path(&self.original.root_element, id, &root_node, &mut path) {
+            return Err(());
+        }
+        let elem = path.last().unwrap().borrow();
+        let item_within_component = self.items.get(elem.id.as_str()).ok_or(())?;
+        // Only the items that handle the focus have a has_focus property
+        if !item_within_component.rtti.properties.contains_key("has_focus") {
+            return Err(());
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let item = unsafe { item_within_component.item_from_component(c.as_ptr()) };
+        eval::window_ref(c).ok_or(())?.set_focus_item(component, item);
         Ok(())
     }
+
+    /// Find the element and the name of the property `name` of the element declared with the
+    /// given id, once the declarations were moved to the root element by the compiler.
+    fn lookup_element_property(&self, id: &str, name: &str) -> Result<(ElementRc, String), ()> {
+        let root_node = self.original.root_element.borrow().node.as_ref().map(|n| n.0.node.clone());
+        let mut path = vec![];
+        if !find_element_path(&self.original.root_element, id, &root_node, &mut path) {
+            return Err(());
+        }
+        let element = path.pop().unwrap();
+        // The properties declared in an element (or in the component it is an instance of)
+        // were moved to the root element, prefixed with the id of the element.
+        let moved_name = format!("{}_{}", element.borrow().id, name);
+        let root_element = self.original.root_element.borrow();
+        match root_element.property_declarations.get(&moved_name) {
+            Some(d) => match &d.is_alias {
+                Some(alias) => Ok((alias.element.upgrade().unwrap(), alias.name.clone())),
+                None => Ok((self.original.root_element.clone(), moved_name)),
+            },
+            // Elements that were optimized away have no item to read from
+            None if self.items.contains_key(element.borrow().id.as_str()) => {
+                Ok((element, name.into()))
+            }
+            None => Err(()),
+        }
+    }
+}
+
+/// An element of a component, as returned by `ComponentDescription::element_tree`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementInfo {
+    /// The id of the element. The compiler makes the ids unique by appending a number to the
+    /// id written in the .60 file, or to `item` for the elements that have no id.
+    pub id: String,
+    /// The name of the builtin element, for example `Rectangle`
+    pub type_name: String,
+    /// True if the element is instantiated by a `for` or an `if`
+    pub repeated: bool,
+    /// The children of the element, in the order they are declared
+    pub children: Vec<ElementInfo>,
+}
+
+impl ElementInfo {
+    fn from_element(elem: &ElementRc) -> Self {
+        let e = elem.borrow();
+        if e.repeated.is_some() {
+            // The repeated element is the root element of a sub-component
+            if let sixtyfps_compilerlib::typeregister::Type::Component(c) = &e.base_type {
+                return Self { repeated: true, ..Self::from_element(&c.root_element) };
+            }
+        }
+        Self {
+            id: e.id.clone(),
+            type_name: e.base_type.to_string(),
+            repeated: false,
+            children: e.children.iter().map(Self::from_element).collect(),
+        }
+    }
+}
+
+/// A property of the root element of a component, as returned by
+/// `ComponentDescription::property_descriptors`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyDescriptor {
+    /// The name of the property
+    pub name: String,
+    /// The type of the property
+    pub ty: sixtyfps_compilerlib::typeregister::Type,
+    /// True if the property is only written by the runtime, for example `pressed` of a
+    /// TouchArea. Setting it is allowed, but the value is overwritten on the next input event.
+    pub read_only: bool,
+}
+
+/// Add to `result` the properties of `element` and of its base types, unless a property with the
+/// same name is already in it
+fn collect_property_descriptors(
+    element: &sixtyfps_compilerlib::object_tree::Element,
+    result: &mut Vec<PropertyDescriptor>,
+) {
+    use sixtyfps_compilerlib::typeregister::Type;
+    fn add(result: &mut Vec<PropertyDescriptor>, name: &String, ty: &Type, read_only: bool) {
+        if ty.is_property_type() && !result.iter().any(|p| &p.name == name) {
+            result.push(PropertyDescriptor { name: name.clone(), ty: ty.clone(), read_only });
+        }
+    }
+    for (name, decl) in &element.property_declarations {
+        add(result, name, &decl.property_type, false);
+    }
+    let native_class = match &element.base_type {
+        Type::Component(base) => {
+            return collect_property_descriptors(&base.root_element.borrow(), result);
+        }
+        Type::Builtin(builtin) => builtin.native_class.clone(),
+        Type::Native(native_class) => native_class.clone(),
+        _ => return,
+    };
+    native_class.visit_class_hierarchy(|class| {
+        for (name, ty) in class.local_property_iter() {
+            add(result, name, ty, class.output_properties.contains(name));
+        }
+    });
+}
+
+/// Find the element that was declared with the given id in the component, and push it in `path`,
+/// preceded by all its parents starting from the root element.
+///
+/// The compiler made the ids unique, so the id is compared to the one written in the source.
+/// The elements coming from other components are ignored because they were not declared within
+/// `root_node`.
+/// Returns true if the element was declared with the given id in the source, within `root_node`
+fn has_declared_id(
+    elem: &ElementRc,
+    id: &str,
+    root_node: &Option<sixtyfps_compilerlib::parser::SyntaxNode>,
+) -> bool {
+    if let (Some(n), Some(root_node)) = (&elem.borrow().node, root_node) {
+        let declared_id = n.0.node.parent().and_then(|p| {
+            if p.kind() == SyntaxKind::SubElement {
+                p.child_text(SyntaxKind::Identifier)
+            } else {
+                None
+            }
+        });
+        declared_id.as_deref() == Some(id) && n.0.node.ancestors().any(|a| a == *root_node)
+    } else {
+        false
+    }
+}
+
+fn find_element_path(
+    elem: &ElementRc,
+    id: &str,
+    root_node: &Option<sixtyfps_compilerlib::parser::SyntaxNode>,
+    path: &mut Vec<ElementRc>,
+) -> bool {
+    let e = elem.borrow();
+    if e.repeated.is_some() {
+        return false;
+    }
+    path.push(elem.clone());
+    if has_declared_id(elem, id, root_node) {
+        return true;
+    }
+    for child in &e.children {
+        if find_element_path(child, id, root_node, path) {
+            return true;
+        }
+    }
+    path.pop();
+    false
+}
+
+/// Find the repeated element with the given declared id, without looking inside the repeated elements
+fn find_repeater(
+    elem: &ElementRc,
+    id: &str,
+    root_node: &Option<sixtyfps_compilerlib::parser::SyntaxNode>,
+) -> Option<ElementRc> {
+    for child in &elem.borrow().children {
+        if child.borrow().repeated.is_some() {
+            if has_declared_id(child, id, root_node) {
+                return Some(child.clone());
+            }
+        } else if let Some(found) = find_repeater(child, id, root_node) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// Install a handler that is called for every property written by the interpreter,
+/// with the id of the element, the name of the property and the new value.
+/// Pass `None` to disable the tracing again.
+pub fn set_property_trace_handler(handler: Option<eval::PropertyTraceHandler>) {
+    eval::set_property_trace_handler(handler)
+}
+
+/// Enable or disable the printing of every property written by the interpreter.
+/// This is meant as a debugging aid to follow the data flow.
+pub fn set_trace_property_changes(enabled: bool) {
+    set_property_trace_handler(if enabled {
+        Some(Box::new(|element, name, value| println!("{}.{} = {:?}", element, name, value)))
+    } else {
+        None
+    })
+}
+
+/// Register a callback that is called for every animation frame, with the time elapsed since
+/// the previous frame, while there are active animations.
+/// The callback can return true to keep the animations active and get called for the next frame,
+/// which allows to drive properties from custom animations, in sync with the rendering.
+pub fn on_animation_tick(callback: impl FnMut(std::time::Duration) -> bool + 'static) {
+    sixtyfps_corelib::animations::on_animation_tick(callback)
+}
+
+/// Calls a callback with the new value of a property of a component each time that value
+/// changed, until it is dropped.
+///
+/// The callback is not called while the property is being changed, but later from the event loop,
+/// or when [`PropertyChangeListener::process`] is called. If the value changes several times in
+/// between, the callback is only called with the last value.
+///
+/// The listener does not keep the component alive. Its clones share the same state: the callback
+/// is called until all of them are dropped.
+#[derive(Clone)]
+pub struct PropertyChangeListener {
+    state: Rc<ListenerState>,
+}
+
+struct ListenerState {
+    tracker: Pin<Box<sixtyfps_corelib::properties::PropertyTracker<ListenerDirtyHandler>>>,
+    component: std::rc::Weak<ComponentBox>,
+    name: String,
+    last_value: std::cell::RefCell<Value>,
+    callback: Box<dyn Fn(Value)>,
+}
+
+/// Schedules the processing of the listeners from the event loop when a property they depend on
+/// changed
+struct ListenerDirtyHandler;
+
+impl sixtyfps_corelib::properties::PropertyDirtyHandler for ListenerDirtyHandler {
+    fn notify(&self) {
+        LISTENERS_TIMER.with(|timer| {
+            timer.start(
+                sixtyfps_corelib::timers::TimerMode::SingleShot,
+                Default::default(),
+                Box::new(process_property_change_listeners),
+            )
+        })
+    }
+}
+
+thread_local! {
+    static LISTENERS: std::cell::RefCell<Vec<std::rc::Weak<ListenerState>>> = Default::default();
+    static LISTENERS_TIMER: sixtyfps_corelib::timers::Timer = Default::default();
+}
+
+fn process_property_change_listeners() {
+    let listeners = LISTENERS.with(|listeners| {
+        let mut listeners = listeners.borrow_mut();
+        listeners.retain(|state| state.strong_count() > 0);
+        listeners.clone()
+    });
+    for state in listeners.iter().filter_map(|state| state.upgrade()) {
+        state.process();
+    }
+}
+
+impl ListenerState {
+    fn process(&self) {
+        if !self.tracker.is_dirty() {
+            return;
+        }
+        let component = match self.component.upgrade() {
+            Some(component) => component,
+            None => return,
+        };
+        let value = self
+            .tracker
+            .as_ref()
+            .evaluate(|| component.description().get_property(component.borrow(), &self.name));
+        if let Ok(value) = value {
+            if *self.last_value.borrow() != value {
+                *self.last_value.borrow_mut() = value.clone();
+                // Called outside of the evaluation of the tracker so that the properties read by
+                // the callback are not tracked
+                (self.callback)(value);
+            }
+        }
+    }
+}
+
+impl PropertyChangeListener {
+    /// Start listening to the changes of the property `name` of the `component`. The name can
+    /// be qualified with the id of an element, like for [`ComponentDescription::get_property`].
+    ///
+    /// Returns an error if the property does not exist
+    pub fn new(
+        component: &Rc<ComponentBox>,
+        name: &str,
+        callback: impl Fn(Value) + 'static,
+    ) -> Result<Self, ()> {
+        let tracker =
+            Box::pin(sixtyfps_corelib::properties::PropertyTracker::new_with_dirty_handler(
+                ListenerDirtyHandler,
+            ));
+        let initial_value = tracker
+            .as_ref()
+            .evaluate(|| component.description().get_property(component.borrow(), name))?;
+        let state = Rc::new(ListenerState {
+            tracker,
+            component: Rc::downgrade(component),
+            name: name.into(),
+            last_value: std::cell::RefCell::new(initial_value),
+            callback: Box::new(callback),
+        });
+        LISTENERS.with(|listeners| listeners.borrow_mut().push(Rc::downgrade(&state)));
+        Ok(Self { state })
+    }
+
+    /// Call the callback now if the value of the property changed since it was last called,
+    /// instead of waiting for the event loop.
+    pub fn process(&self) {
+        self.state.process()
+    }
 }
 
 pub type ComponentDescription = dynamic_component::ComponentDescription<'static>;
@@ -181,3 +798,539 @@ pub fn load(
         generativity::Guard::new(generativity::Id::new())
     })
 }
+
+/// What a component loaded with [`load_cached`] was compiled from. The cached component is only
+/// used if this is still the same.
+#[derive(PartialEq)]
+struct CacheKey {
+    compiler_version: &'static str,
+    include_paths: Vec<std::path::PathBuf>,
+    style: Option<String>,
+    embed_resources: bool,
+    features: Vec<String>,
+    /// The hash of the content of the file and of every file it imports, or None for the files
+    /// that are not on the file system, such as the builtin widgets.
+    file_hashes: Vec<(std::path::PathBuf, Option<u64>)>,
+}
+
+impl CacheKey {
+    fn new(
+        compiler_config: &sixtyfps_compilerlib::CompilerConfiguration,
+        files: impl Iterator<Item = std::path::PathBuf>,
+    ) -> Self {
+        Self {
+            compiler_version: env!("CARGO_PKG_VERSION"),
+            include_paths: compiler_config.include_paths.to_vec(),
+            style: compiler_config.style.map(String::from),
+            embed_resources: compiler_config.embed_resources,
+            features: compiler_config.features.to_vec(),
+            file_hashes: files.map(|path| (path.clone(), hash_file(&path))).collect(),
+        }
+    }
+}
+
+fn hash_file(path: &std::path::Path) -> Option<u64> {
+    use std::hash::{Hash, Hasher};
+    let content = std::fs::read(path).ok()?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// The maximum number of components kept by [`load_cached`]. When it is reached, the
+/// component that was used the longest time ago is dropped from the cache.
+const COMPONENT_CACHE_SIZE: usize = 16;
+
+thread_local!(
+    /// The cached components, the most recently used first.
+    static COMPONENT_CACHE: std::cell::RefCell<
+        Vec<(std::path::PathBuf, CacheKey, Rc<ComponentDescription>)>,
+    > = Default::default()
+);
+
+/// The version of the format of the syntax tree files written by [`load_cached`]. It must be
+/// increased when the format of `parser::write_syntax_tree` changes.
+const SYNTAX_CACHE_FORMAT_VERSION: u32 = 1;
+
+/// The directory where [`load_cached`] saves the syntax trees: `SIXTYFPS_CACHE_DIR` if it is set,
+/// otherwise a `sixtyfps-cache` directory in the temporary directory.
+fn syntax_cache_dir() -> std::path::PathBuf {
+    std::env::var_os("SIXTYFPS_CACHE_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join("sixtyfps-cache"))
+}
+
+/// The file in which [`load_cached`] saves the syntax tree of the .60 file at `path`
+fn syntax_cache_file(path: &std::path::Path) -> std::path::PathBuf {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+    syntax_cache_dir()
+        .join(format!("{:016x}.60cache", stable_hash(path.to_string_lossy().as_bytes())))
+}
+
+/// The header of the syntax tree file of `source`. The syntax tree is only read back if the file
+/// starts with the same header, so it is invalidated when the source, the format or the version
+/// of the compiler change.
+fn syntax_cache_header(source: &str) -> Vec<u8> {
+    format!(
+        "sixtyfps syntax tree {}\n{}\n{:016x} {}\n",
+        SYNTAX_CACHE_FORMAT_VERSION,
+        env!("CARGO_PKG_VERSION"),
+        stable_hash(source.as_bytes()),
+        source.len()
+    )
+    .into_bytes()
+}
+
+/// The FNV-1a hash of `data`. Unlike the hasher of the standard library, it doesn't change from
+/// one build to another, so it can be saved in files.
+fn stable_hash(data: &[u8]) -> u64 {
+    data.iter()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3))
+}
+
+fn read_cached_syntax_tree(
+    cache_file: &std::path::Path,
+    header: &[u8],
+) -> Option<sixtyfps_compilerlib::parser::SyntaxNode> {
+    let data = std::fs::read(cache_file).ok()?;
+    if !data.starts_with(header) {
+        return None;
+    }
+    sixtyfps_compilerlib::parser::read_syntax_tree(&data[header.len()..])
+}
+
+fn write_cached_syntax_tree(
+    cache_file: &std::path::Path,
+    header: &[u8],
+    node: &sixtyfps_compilerlib::parser::SyntaxNode,
+) {
+    let mut data = header.to_vec();
+    sixtyfps_compilerlib::parser::write_syntax_tree(node, &mut data);
+    // Write to a temporary file first, so that another process never reads a partial file.
+    // The cache is only an optimization, so failing to write it is not an error.
+    let tmp_file = cache_file.with_extension(format!("{}.tmp", std::process::id()));
+    let _ = std::fs::create_dir_all(syntax_cache_dir())
+        .and_then(|()| std::fs::write(&tmp_file, data))
+        .and_then(|()| std::fs::rename(&tmp_file, cache_file));
+}
+
+/// Load the .60 file at `path`, like [`load`], but return the component of a previous call
+/// if neither the file, nor the files it imports, nor the configuration changed since.
+///
+/// This is meant for tools that load the same files repeatedly. The compiled components are
+/// kept in memory, and at most [`COMPONENT_CACHE_SIZE`] of them. In addition, the syntax tree of
+/// the file is saved in the directory given by the `SIXTYFPS_CACHE_DIR` environment variable, or
+/// in the temporary directory, so that the file is not parsed again by the next process that
+/// loads it while it is unchanged. The passes of the compiler still run in that case, and the
+/// imported files are still parsed. The warnings are only returned when the file is compiled.
+pub fn load_cached(
+    path: &std::path::Path,
+    compiler_config: &sixtyfps_compilerlib::CompilerConfiguration,
+) -> (Result<Rc<ComponentDescription>, ()>, sixtyfps_compilerlib::diagnostics::BuildDiagnostics) {
+    let cached = COMPONENT_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let index = cache.iter().position(|(p, _, _)| p == path)?;
+        let entry = cache.remove(index);
+        let current_key =
+            CacheKey::new(compiler_config, entry.1.file_hashes.iter().map(|(p, _)| p.clone()));
+        if current_key == entry.1 {
+            let component = entry.2.clone();
+            cache.insert(0, entry);
+            Some(component)
+        } else {
+            None
+        }
+    });
+    if let Some(component) = cached {
+        return (Ok(component), Default::default());
+    }
+
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) => {
+            let mut file_diagnostics = sixtyfps_compilerlib::diagnostics::FileDiagnostics {
+                current_path: Rc::new(path.into()),
+                ..Default::default()
+            };
+            file_diagnostics.inner.push(err.into());
+            let mut diagnostics = sixtyfps_compilerlib::diagnostics::BuildDiagnostics::default();
+            diagnostics.add(file_diagnostics);
+            return (Err(()), diagnostics);
+        }
+    };
+    let cache_file = syntax_cache_file(path);
+    let header = syntax_cache_header(&source);
+    let (syntax_node, file_diagnostics) = match read_cached_syntax_tree(&cache_file, &header) {
+        Some(node) => {
+            let current_path = Rc::new(path.to_owned());
+            (
+                sixtyfps_compilerlib::parser::SyntaxNodeWithSourceFile {
+                    node,
+                    source_file: Some(current_path.clone()),
+                },
+                sixtyfps_compilerlib::diagnostics::FileDiagnostics {
+                    current_path,
+                    source: Some(source),
+                    ..Default::default()
+                },
+            )
+        }
+        None => {
+            let (syntax_node, file_diagnostics) =
+                sixtyfps_compilerlib::parser::parse(source, Some(path));
+            if file_diagnostics.has_error() {
+                let mut diagnostics =
+                    sixtyfps_compilerlib::diagnostics::BuildDiagnostics::default();
+                diagnostics.add(file_diagnostics);
+                return (Err(()), diagnostics);
+            }
+            write_cached_syntax_tree(&cache_file, &header, &syntax_node.node);
+            (syntax_node, file_diagnostics)
+        }
+    };
+    let (result, diagnostics) = dynamic_component::load_syntax_node(
+        syntax_node,
+        file_diagnostics,
+        compiler_config,
+        unsafe { generativity::Guard::new(generativity::Id::new()) },
+    );
+    if let Ok(component) = &result {
+        // The diagnostics have an entry for each file that was loaded
+        let key = CacheKey::new(
+            compiler_config,
+            diagnostics.iter().map(|d| d.current_path.as_ref().clone()),
+        );
+        COMPONENT_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            cache.insert(0, (path.to_owned(), key, component.clone()));
+            cache.truncate(COMPONENT_CACHE_SIZE);
+        });
+    }
+    (result, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[should_panic(expected = "Binding loop detected: root_1.a -> root_1.b -> root_1.a")]
+    fn binding_loop() {
+        let source = r#"
+TestCase := Rectangle {
+    property<int> a: b;
+    property<int> b: a + 1;
+}
+        "#;
+        let (component, diagnostics) = crate::load(
+            source.into(),
+            std::path::Path::new("binding_loop.60"),
+            &Default::default(),
+        );
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let instance = component.clone().create();
+        let _ = component.get_property(instance.borrow(), "a");
+    }
+
+    #[test]
+    fn property_change_listener() {
+        use std::{cell::RefCell, rc::Rc};
+        let source = r#"
+TestCase := Rectangle {
+    property<int> a: 1;
+    property<int> b: a * 2;
+}
+        "#;
+        let (component, diagnostics) = crate::load(
+            source.into(),
+            std::path::Path::new("property_change_listener.60"),
+            &Default::default(),
+        );
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let instance = Rc::new(component.clone().create());
+        let changes = Rc::new(RefCell::new(Vec::new()));
+        assert!(crate::PropertyChangeListener::new(&instance, "unknown", |_| ()).is_err());
+        let listener = crate::PropertyChangeListener::new(&instance, "b", {
+            let changes = changes.clone();
+            move |value| changes.borrow_mut().push(value)
+        })
+        .unwrap();
+
+        component.set_property(instance.borrow(), "a", crate::Value::Number(2.)).unwrap();
+        component.set_property(instance.borrow(), "a", crate::Value::Number(3.)).unwrap();
+        assert!(changes.borrow().is_empty());
+        listener.process();
+        assert_eq!(*changes.borrow(), vec![crate::Value::Number(6.)]);
+
+        // The callback is also called from the event loop
+        component.set_property(instance.borrow(), "a", crate::Value::Number(4.)).unwrap();
+        sixtyfps_corelib::timers::TimerList::maybe_activate_timers();
+        assert_eq!(changes.borrow().last(), Some(&crate::Value::Number(8.)));
+        assert_eq!(changes.borrow().len(), 2);
+
+        drop(listener);
+        component.set_property(instance.borrow(), "a", crate::Value::Number(5.)).unwrap();
+        sixtyfps_corelib::timers::TimerList::maybe_activate_timers();
+        assert_eq!(changes.borrow().len(), 2);
+    }
+
+    #[test]
+    fn property_trace_handler() {
+        use std::{cell::RefCell, rc::Rc};
+        let source = r#"
+TestCase := Rectangle {
+    property<int> a;
+    signal update;
+    update => {
+        a = 42;
+        label.text = "hello";
+    }
+    label := Text {}
+}
+        "#;
+        let (component, diagnostics) = crate::load(
+            source.into(),
+            std::path::Path::new("property_trace_handler.60"),
+            &Default::default(),
+        );
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let instance = component.clone().create();
+
+        let traces = Rc::new(RefCell::new(Vec::new()));
+        crate::set_property_trace_handler(Some(Box::new({
+            let traces = traces.clone();
+            move |element, name, value| {
+                traces.borrow_mut().push((element.to_owned(), name.to_owned(), value.clone()))
+            }
+        })));
+        component.emit_signal(instance.borrow(), "update", &[]).unwrap();
+        crate::set_property_trace_handler(None);
+
+        let traces = traces.borrow();
+        assert_eq!(traces.len(), 2);
+        assert_eq!((traces[0].1.as_str(), &traces[0].2), ("a", &crate::Value::Number(42.)));
+        assert_eq!(
+            traces[1],
+            ("label".to_owned(), "text".to_owned(), crate::Value::String("hello".into()))
+        );
+
+        // Nothing is traced once the handler is removed
+        component.emit_signal(instance.borrow(), "update", &[]).unwrap();
+        assert_eq!(traces.len(), 2);
+    }
+
+    #[test]
+    fn set_binding() {
+        let source = r#"
+TestCase := Rectangle {
+    property<int> a: 1;
+    property<string> label_text <=> label.text;
+    label := Text {}
+}
+        "#;
+        let (component, diagnostics) =
+            crate::load(source.into(), std::path::Path::new("set_binding.60"), &Default::default());
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let instance = component.clone().create();
+
+        component
+            .set_binding(instance.borrow(), "a", Box::new(|| crate::Value::Number(42.)))
+            .unwrap();
+        assert_eq!(component.get_property(instance.borrow(), "a"), Ok(crate::Value::Number(42.)));
+        component
+            .set_binding(
+                instance.borrow(),
+                "label_text",
+                Box::new(|| crate::Value::String("hello".into())),
+            )
+            .unwrap();
+        assert_eq!(
+            component.get_property(instance.borrow(), "label_text"),
+            Ok(crate::Value::String("hello".into()))
+        );
+
+        // Errors are returned instead of being ignored
+        assert!(component
+            .set_binding(instance.borrow(), "unknown", Box::new(|| crate::Value::Number(0.)))
+            .is_err());
+        let (other, _) = crate::load(
+            "Other := Rectangle {}".into(),
+            std::path::Path::new("set_binding_other.60"),
+            &Default::default(),
+        );
+        let other_instance = other.unwrap().create();
+        assert!(component
+            .set_binding(other_instance.borrow(), "a", Box::new(|| crate::Value::Number(0.)))
+            .is_err());
+    }
+
+    #[test]
+    fn load_cached() {
+        let path = std::env::temp_dir().join(format!("load_cached_{}.60", std::process::id()));
+        std::fs::write(&path, "TestCase := Rectangle { property<int> a: 42; }").unwrap();
+        let (first, _) = crate::load_cached(&path, &Default::default());
+        let (second, _) = crate::load_cached(&path, &Default::default());
+        assert!(std::rc::Rc::ptr_eq(&first.unwrap(), second.as_ref().unwrap()));
+
+        std::fs::write(&path, "TestCase := Rectangle { property<int> a: 43; }").unwrap();
+        let (third, diagnostics) = crate::load_cached(&path, &Default::default());
+        std::fs::remove_file(crate::syntax_cache_file(&path)).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let third = third.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        assert!(!std::rc::Rc::ptr_eq(&third, &second.unwrap()));
+        let instance = third.clone().create();
+        assert_eq!(third.get_property(instance.borrow(), "a"), Ok(crate::Value::Number(43.)));
+        assert_eq!(crate::COMPONENT_CACHE.with(|cache| cache.borrow().len()), 1);
+    }
+
+    #[test]
+    fn load_cached_size() {
+        let paths = (0..=crate::COMPONENT_CACHE_SIZE)
+            .map(|i| {
+                let path = std::env::temp_dir().join(format!(
+                    "load_cached_size_{}_{}.60",
+                    std::process::id(),
+                    i
+                ));
+                std::fs::write(&path, "TestCase := Rectangle { }").unwrap();
+                path
+            })
+            .collect::<Vec<_>>();
+        let (first, _) = crate::load_cached(&paths[0], &Default::default());
+        for path in &paths[1..] {
+            // Keep the first one in use, so that the second is the least recently used one
+            let (again, _) = crate::load_cached(&paths[0], &Default::default());
+            assert!(std::rc::Rc::ptr_eq(first.as_ref().unwrap(), again.as_ref().unwrap()));
+            crate::load_cached(path, &Default::default()).0.unwrap();
+        }
+        crate::COMPONENT_CACHE.with(|cache| {
+            let cache = cache.borrow();
+            assert_eq!(cache.len(), crate::COMPONENT_CACHE_SIZE);
+            assert!(cache.iter().any(|(p, _, _)| *p == paths[0]));
+            assert!(!cache.iter().any(|(p, _, _)| *p == paths[1]));
+        });
+        for path in &paths {
+            std::fs::remove_file(crate::syntax_cache_file(path)).unwrap();
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn load_cached_syntax_tree() {
+        let path =
+            std::env::temp_dir().join(format!("load_cached_syntax_tree_{}.60", std::process::id()));
+        let source = "TestCase := Rectangle { property<int> a: 42; }";
+        std::fs::write(&path, source).unwrap();
+        crate::load_cached(&path, &Default::default()).0.unwrap();
+        let cache_file = crate::syntax_cache_file(&path);
+        let header = crate::syntax_cache_header(source);
+        assert!(std::fs::read(&cache_file).unwrap().starts_with(&header));
+
+        // Replace the saved syntax tree with the one of another source, to see that it is used
+        // instead of parsing the file
+        let (other, _) = sixtyfps_compilerlib::parser::parse(
+            "TestCase := Rectangle { property<int> a: 43; }".into(),
+            None,
+        );
+        crate::write_cached_syntax_tree(&cache_file, &header, &other.node);
+        let get_a = || {
+            crate::COMPONENT_CACHE.with(|cache| cache.borrow_mut().clear());
+            let (component, diagnostics) = crate::load_cached(&path, &Default::default());
+            let component =
+                component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+            let instance = component.clone().create();
+            component.get_property(instance.borrow(), "a")
+        };
+        assert_eq!(get_a(), Ok(crate::Value::Number(43.)));
+
+        // The saved syntax tree is not used once the file changed
+        std::fs::write(&path, "TestCase := Rectangle { property<int> a: 44; }").unwrap();
+        assert_eq!(get_a(), Ok(crate::Value::Number(44.)));
+        // An invalid file is ignored
+        std::fs::write(&cache_file, "garbage").unwrap();
+        assert_eq!(get_a(), Ok(crate::Value::Number(44.)));
+
+        std::fs::remove_file(&cache_file).unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn features() {
+        let source = r#"
+TestCase := Rectangle {
+    property<bool> debug: feature("debug");
+    property<bool> release: feature("release");
+}
+        "#;
+        let features = ["debug".to_owned()];
+        let config = sixtyfps_compilerlib::CompilerConfiguration {
+            features: &features,
+            ..Default::default()
+        };
+        let (component, diagnostics) =
+            crate::load(source.into(), std::path::Path::new("features.60"), &config);
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let instance = component.clone().create();
+        assert_eq!(
+            component.get_property(instance.borrow(), "debug"),
+            Ok(crate::Value::Bool(true))
+        );
+        assert_eq!(
+            component.get_property(instance.borrow(), "release"),
+            Ok(crate::Value::Bool(false))
+        );
+    }
+
+    #[test]
+    fn property_descriptors() {
+        use sixtyfps_compilerlib::typeregister::Type;
+        let source = r#"
+TestCase := TouchArea {
+    property<int> counter;
+    signal activated;
+}
+        "#;
+        let (component, diagnostics) =
+            crate::load(source.into(), std::path::Path::new("descriptors.60"), &Default::default());
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let descriptors = component.property_descriptors();
+        let find = |name: &str| descriptors.iter().find(|p| p.name == name).cloned();
+        let counter = find("counter").unwrap();
+        assert_eq!(counter.ty, Type::Int32);
+        assert!(!counter.read_only);
+        let width = find("width").unwrap();
+        assert_eq!(width.ty, Type::Length);
+        assert!(!width.read_only);
+        assert!(find("pressed").unwrap().read_only);
+        assert!(find("activated").is_none());
+        assert!(find("clicked").is_none());
+        let instance = component.clone().create();
+        component.set_property(instance.borrow(), "width", crate::Value::Number(42.)).unwrap();
+        assert_eq!(
+            component.get_property(instance.borrow(), "width"),
+            Ok(crate::Value::Number(42.))
+        );
+    }
+
+    #[test]
+    fn property_descriptors_of_base_component() {
+        let source = r#"
+Base := TouchArea {
+    property<string> label;
+}
+TestCase := Base {
+    property<int> counter;
+}
+        "#;
+        let (component, diagnostics) = crate::load(
+            source.into(),
+            std::path::Path::new("descriptors_base.60"),
+            &Default::default(),
+        );
+        let component = component.unwrap_or_else(|()| panic!("{:?}", diagnostics.to_string_vec()));
+        let descriptors = component.property_descriptors();
+        let find = |name: &str| descriptors.iter().find(|p| p.name == name).cloned();
+        assert!(!find("counter").unwrap().read_only);
+        assert!(!find("label").unwrap().read_only);
+        assert!(!find("width").unwrap().read_only);
+        assert!(find("pressed").unwrap().read_only);
+    }
+}
@@ -21,12 +21,13 @@ cfg_if::cfg_if! {
 }
 
 pub fn create_window() -> ComponentWindow {
-    #[cfg(any(
-        feature = "sixtyfps-rendering-backend-qt",
-        feature = "sixtyfps-rendering-backend-gl"
-    ))]
     let backend_config = std::env::var("SIXTYFPS_BACKEND").unwrap_or_default();
 
+    #[cfg(not(target_arch = "wasm32"))]
+    if backend_config == "Headless" {
+        return sixtyfps_corelib::headless::create_headless_window(800, 600);
+    }
+
     #[cfg(feature = "sixtyfps-rendering-backend-qt")]
     if backend_config == "Qt" {
         return sixtyfps_rendering_backend_qt::create_gl_window();
@@ -36,10 +37,6 @@ pub fn create_window() -> ComponentWindow {
         return sixtyfps_rendering_backend_gl::create_gl_window();
     }
 
-    #[cfg(any(
-        feature = "sixtyfps-rendering-backend-qt",
-        feature = "sixtyfps-rendering-backend-gl"
-    ))]
     if !backend_config.is_empty() {
         eprintln!("Could not load rendering backend {}, fallback to default", backend_config)
     }
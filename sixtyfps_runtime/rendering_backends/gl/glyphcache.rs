@@ -8,28 +8,40 @@
     Please contact info@sixtyfps.io for more information.
 LICENSE END */
 use super::buffers::GLArrayBuffer;
-use super::texture::{AtlasAllocation, TextureAtlas};
+use super::texture::{AtlasAllocation, GLTexture, TextureAtlas};
 use super::Vertex;
 use collections::hash_map::HashMap;
 use itertools::Itertools;
+use sixtyfps_corelib::font::FaceProperties;
 use sixtyfps_corelib::font::Font;
 use sixtyfps_corelib::font::FontHandle;
-use std::cell::RefCell;
+use sixtyfps_corelib::font::GlyphMetrics;
+use std::cell::{Cell, RefCell};
 use std::{collections, rc::Rc};
 
 type GlyphsByPixelSize = Vec<Rc<RefCell<CachedFontGlyphs>>>;
 
 use super::GlyphRun;
 
+/// Number of frames after which a glyph that was not laid out, and that is not used by any
+/// glyph run, is removed from the cache, so that its space in the atlas can be reused.
+const MAX_UNUSED_FRAMES: u64 = 120;
+
 #[derive(Default)]
 pub(crate) struct GlyphCache {
     glyphs_by_font: RefCell<HashMap<FontHandle, GlyphsByPixelSize>>,
+    current_frame: Rc<Cell<u64>>,
 }
 
 impl GlyphCache {
-    pub fn find_font(&self, font_family: &str, pixel_size: f32) -> Rc<RefCell<CachedFontGlyphs>> {
-        let font =
-            sixtyfps_corelib::font::FONT_CACHE.with(|fc| fc.find_font(font_family, pixel_size));
+    pub fn find_font(
+        &self,
+        font_family: &str,
+        font_properties: FaceProperties,
+        pixel_size: f32,
+    ) -> Rc<RefCell<CachedFontGlyphs>> {
+        let font = sixtyfps_corelib::font::FONT_CACHE
+            .with(|fc| fc.find_font(font_family, font_properties, pixel_size));
 
         let font_handle = font.handle();
 
@@ -47,47 +59,88 @@ impl GlyphCache {
                 }
             })
             .unwrap_or_else(|| {
-                let fnt = Rc::new(RefCell::new(CachedFontGlyphs::new(font.clone())));
+                let fnt = Rc::new(RefCell::new(CachedFontGlyphs::new(
+                    font.clone(),
+                    self.current_frame.clone(),
+                )));
                 glyphs_by_pixel_size.push(fnt.clone());
                 fnt
             })
     }
+
+    /// Must be called at the end of every frame. Evicts the glyphs that were not used for
+    /// `MAX_UNUSED_FRAMES` frames. A glyph is still in use as long as a glyph run refers to it,
+    /// since the glyph runs are kept across frames for the texts that don't change.
+    pub fn end_frame(&self) {
+        let current_frame = self.current_frame.get() + 1;
+        self.current_frame.set(current_frame);
+
+        for gl_font in self.glyphs_by_font.borrow().values().flatten() {
+            evict_unused_glyphs(&mut gl_font.borrow_mut().glyphs, current_frame);
+        }
+    }
+}
+
+fn evict_unused_glyphs<Texture>(
+    glyphs: &mut HashMap<u32, PreRenderedGlyph<Texture>>,
+    current_frame: u64,
+) {
+    glyphs.retain(|_, glyph| {
+        current_frame - glyph.last_used_frame <= MAX_UNUSED_FRAMES
+            || glyph
+                .glyph_allocation
+                .as_ref()
+                .map_or(false, |allocation| Rc::strong_count(allocation) > 1)
+    });
 }
 
-pub struct PreRenderedGlyph {
-    pub glyph_allocation: Option<AtlasAllocation>,
-    pub advance: f32,
+pub struct PreRenderedGlyph<Texture = GLTexture> {
+    pub glyph_allocation: Option<Rc<AtlasAllocation<Texture>>>,
+    pub metrics: GlyphMetrics,
+    last_used_frame: u64,
 }
 
+/// The glyphs are cached by glyph id only: the atlas stores their coverage and the color of
+/// the text is applied by the glyph shader, so changing the color doesn't rasterize them again.
 pub struct CachedFontGlyphs {
     pub font: Rc<Font>,
     glyphs: HashMap<u32, PreRenderedGlyph>,
+    current_frame: Rc<Cell<u64>>,
 }
 
 impl CachedFontGlyphs {
-    pub fn new(font: Rc<Font>) -> Self {
+    fn new(font: Rc<Font>, current_frame: Rc<Cell<u64>>) -> Self {
         let glyphs = HashMap::new();
-        Self { font, glyphs }
+        Self { font, glyphs, current_frame }
     }
 
+    /// Returns the glyphs of the text in the order in which they are laid out from left to
+    /// right. When `right_to_left` is true, that is the reverse order of the characters. There
+    /// is no support for bidirectional text or shaping.
     pub fn layout_glyphs<'a>(
         &'a mut self,
         gl: &'a Rc<glow::Context>,
         atlas: &'a mut TextureAtlas,
         text: &'a str,
+        right_to_left: bool,
     ) -> impl Iterator<Item = &PreRenderedGlyph> + 'a {
-        let glyphs = self
+        let mut glyphs = self
             .font
             .clone()
             .string_to_glyphs(text)
             .map(|(_, ch, glyph_id)| (ch, glyph_id))
             .collect::<smallvec::SmallVec<[(_, _); 32]>>();
+        if right_to_left {
+            glyphs.reverse();
+        }
 
+        let current_frame = self.current_frame.get();
         glyphs.iter().for_each(|(ch, glyph)| {
             if !self.glyphs.contains_key(&glyph) {
                 // ensure the glyph is cached
                 self.glyphs.insert(*glyph, self.render_glyph(gl, atlas, *ch, *glyph));
             }
+            self.glyphs.get_mut(glyph).unwrap().last_used_frame = current_frame;
         });
 
         GlyphIter { gl_font: self, glyph_it: glyphs.into_iter().map(|(_, g)| g) }
@@ -100,27 +153,27 @@ impl CachedFontGlyphs {
         ch: char,
         glyph_id: u32,
     ) -> PreRenderedGlyph {
-        let advance = self.font.glyph_metrics(glyph_id).advance;
+        let metrics = self.font.glyph_metrics(glyph_id);
 
         let glyph_allocation = if !ch.is_whitespace() {
             let glyph_image = self.font.rasterize_glyph(glyph_id);
 
-            Some(
+            Some(Rc::new(
                 atlas.allocate_image_in_atlas(
                     gl,
-                    image::ImageBuffer::<_, &[u8]>::from_raw(
+                    image::ImageBuffer::<image::Luma<u8>, &[u8]>::from_raw(
                         glyph_image.width(),
                         glyph_image.height(),
                         &glyph_image,
                     )
                     .unwrap(),
                 ),
-            )
+            ))
         } else {
             None
         };
 
-        PreRenderedGlyph { glyph_allocation, advance }
+        PreRenderedGlyph { glyph_allocation, metrics, last_used_frame: self.current_frame.get() }
     }
 
     pub fn render_glyphs(
@@ -128,13 +181,15 @@ impl CachedFontGlyphs {
         context: &Rc<glow::Context>,
         texture_atlas: &mut TextureAtlas,
         text: &str,
+        right_to_left: bool,
+        letter_spacing: f32,
     ) -> Vec<GlyphRun> {
         let mut x = 0.;
 
-        self.layout_glyphs(&context, texture_atlas, text)
+        self.layout_glyphs(&context, texture_atlas, text, right_to_left)
             .filter_map(|cached_glyph| {
                 let glyph_x = x;
-                x += cached_glyph.advance;
+                x += cached_glyph.metrics.advance_with_letter_spacing(letter_spacing);
 
                 if let Some(glyph_allocation) = &cached_glyph.glyph_allocation {
                     let glyph_width = glyph_allocation.texture_coordinates.width() as f32;
@@ -159,12 +214,12 @@ impl CachedFontGlyphs {
                 let glyph_count = glyph_it.size_hint().0;
                 let mut vertices: Vec<Vertex> = Vec::with_capacity(glyph_count * 6);
                 let mut texture_vertices: Vec<Vertex> = Vec::with_capacity(glyph_count * 6);
+                let mut glyph_allocations = Vec::with_capacity(glyph_count);
 
-                for (glyph_vertices, glyph_texture_vertices) in
-                    glyph_it.map(|(vertices, texture_vertices, _)| (vertices, texture_vertices))
-                {
+                for (glyph_vertices, glyph_texture_vertices, glyph_allocation) in glyph_it {
                     vertices.extend(&glyph_vertices);
                     texture_vertices.extend(&glyph_texture_vertices);
+                    glyph_allocations.push(glyph_allocation);
                 }
 
                 let vertex_count = vertices.len() as i32;
@@ -173,6 +228,7 @@ impl CachedFontGlyphs {
                     texture_vertices: GLArrayBuffer::new(&context, &texture_vertices),
                     texture,
                     vertex_count,
+                    _glyph_allocations: glyph_allocations,
                 }
             })
             .collect()
@@ -197,3 +253,46 @@ where
         }
     }
 }
+
+#[test]
+fn glyph_eviction() {
+    use super::texture::TextureFormat;
+
+    // The tests have no GL context, so the textures only record their size
+    let mut atlas = TextureAtlas::<(i32, i32)>::new(TextureFormat::Alpha);
+    let mut glyph = |last_used_frame, has_allocation| PreRenderedGlyph {
+        glyph_allocation: if has_allocation {
+            Some(Rc::new(
+                atlas.allocate_region(10, 10, 4096, |width, height| (width, height)).unwrap(),
+            ))
+        } else {
+            None
+        },
+        metrics: GlyphMetrics { advance: 10., ink_right: 10. },
+        last_used_frame,
+    };
+
+    let mut glyphs = HashMap::new();
+    glyphs.insert(1, glyph(0, false));
+    glyphs.insert(2, glyph(0, true));
+    glyphs.insert(3, glyph(0, true));
+    glyphs.insert(4, glyph(MAX_UNUSED_FRAMES, true));
+    // A glyph run still refers to the glyph 3
+    let glyph_run_allocation = glyphs[&3].glyph_allocation.clone().unwrap();
+
+    evict_unused_glyphs(&mut glyphs, MAX_UNUSED_FRAMES);
+    assert_eq!(glyphs.len(), 4);
+
+    // One more frame makes the oldest glyphs go over the limit
+    evict_unused_glyphs(&mut glyphs, MAX_UNUSED_FRAMES + 1);
+    assert!(!glyphs.contains_key(&1));
+    assert!(!glyphs.contains_key(&2));
+    assert!(glyphs.contains_key(&3));
+    assert!(glyphs.contains_key(&4));
+
+    // Once the glyph run is gone, the glyph can be evicted too
+    drop(glyph_run_allocation);
+    evict_unused_glyphs(&mut glyphs, MAX_UNUSED_FRAMES + 1);
+    assert!(!glyphs.contains_key(&3));
+    assert!(glyphs.contains_key(&4));
+}
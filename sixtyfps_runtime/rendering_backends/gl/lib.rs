@@ -16,11 +16,13 @@ use lyon::tessellation::{
 };
 use sixtyfps_corelib::eventloop::ComponentWindow;
 use sixtyfps_corelib::{
+    font::FaceProperties,
     graphics::{
         ARGBColor, Color, Frame as GraphicsFrame, GraphicsBackend, GraphicsWindow,
         HighLevelRenderingPrimitive, Point, Rect, RenderingPrimitivesBuilder, RenderingVariable,
         Resource, Size,
     },
+    items::ImageFit,
     SharedArray,
 };
 use smallvec::{smallvec, SmallVec};
@@ -30,7 +32,7 @@ extern crate alloc;
 use alloc::rc::Rc;
 
 mod texture;
-use texture::{GLTexture, TextureAtlas};
+use texture::{GLTexture, TextureAtlas, TextureFormat};
 
 mod shader;
 use shader::{ImageShader, PathShader};
@@ -46,9 +48,20 @@ mod glyphcache;
 use glyphcache::GlyphCache;
 
 #[cfg(not(target_arch = "wasm32"))]
-#[derive(Default)]
 struct PlatformData {
     glyph_cache: GlyphCache,
+    /// The glyphs are stored separately from the images, as coverage only.
+    glyph_atlas: RefCell<TextureAtlas>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for PlatformData {
+    fn default() -> Self {
+        Self {
+            glyph_cache: GlyphCache::default(),
+            glyph_atlas: RefCell::new(TextureAtlas::new(TextureFormat::Alpha)),
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -61,6 +74,8 @@ pub struct GlyphRun {
     pub(crate) texture_vertices: GLArrayBuffer<Vertex>,
     pub(crate) texture: Rc<GLTexture>,
     pub(crate) vertex_count: i32,
+    /// Keeps the space of the glyphs in the atlas from being reused while the run is alive.
+    pub(crate) _glyph_allocations: Vec<Rc<texture::AtlasAllocation>>,
 }
 
 enum GLRenderingPrimitive {
@@ -73,6 +88,10 @@ enum GLRenderingPrimitive {
         texture_vertices: GLArrayBuffer<Vertex>,
         texture: texture::AtlasAllocation,
         image_size: Size,
+        image_fit: ImageFit,
+        /// The texture coordinates of the part of the image that is shown when it is cropped,
+        /// for the size of the box they were computed for.
+        cropped_texture_vertices: RefCell<Option<(Size, GLArrayBuffer<Vertex>)>>,
     },
     GlyphRuns {
         glyph_runs: Vec<GlyphRun>,
@@ -85,6 +104,8 @@ enum GLRenderingPrimitive {
         vertices: Rc<GLArrayBuffer<Vertex>>,
         indices: Rc<GLIndexBuffer<u16>>,
     },
+    /// Draws nothing but consumes the rendering variable of a primitive that produced no geometry.
+    SkipVariable,
 }
 
 struct TextCursor {
@@ -250,7 +271,7 @@ impl GLRenderer {
             glyph_shader,
             #[cfg(not(target_arch = "wasm32"))]
             platform_data,
-            texture_atlas: Rc::new(RefCell::new(TextureAtlas::new())),
+            texture_atlas: Rc::new(RefCell::new(TextureAtlas::new(TextureFormat::Rgba))),
             #[cfg(target_arch = "wasm32")]
             window,
             #[cfg(not(target_arch = "wasm32"))]
@@ -306,6 +327,8 @@ impl GraphicsBackend for GLRenderer {
         unsafe {
             self.context.viewport(0, 0, width as i32, height as i32);
 
+            // All the shaders output colors with premultiplied alpha, and the textures are
+            // premultiplied when uploaded.
             self.context.enable(glow::BLEND);
             self.context.blend_func(glow::ONE, glow::ONE_MINUS_SRC_ALPHA);
 
@@ -347,6 +370,8 @@ impl GraphicsBackend for GLRenderer {
                 Some(unsafe { frame.windowed_context.make_not_current().unwrap() });
         }
         self.text_cursor_rect = frame.text_cursor_rect.take();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.platform_data.glyph_cache.end_frame();
     }
     fn window(&self) -> &winit::window::Window {
         #[cfg(not(target_arch = "wasm32"))]
@@ -397,7 +422,7 @@ impl RenderingPrimitivesBuilder for GLRenderingPrimitivesBuilder {
 
                     primitives
                 }
-                HighLevelRenderingPrimitive::Image { source } => {
+                HighLevelRenderingPrimitive::Image { source, image_fit } => {
                     match source {
                         Resource::AbsoluteFilePath(path) => {
                             let mut image_path = std::env::current_exe().unwrap();
@@ -410,7 +435,7 @@ impl RenderingPrimitivesBuilder for GLRenderingPrimitivesBuilder {
                                 &image,
                             )
                             .unwrap();
-                            smallvec![self.create_image(image)]
+                            smallvec![self.create_image(image, *image_fit)]
                         }
                         Resource::EmbeddedData(slice) => {
                             let image_slice = slice.as_slice();
@@ -421,7 +446,7 @@ impl RenderingPrimitivesBuilder for GLRenderingPrimitivesBuilder {
                                 &image,
                             )
                             .unwrap();
-                            smallvec![self.create_image(image)]
+                            smallvec![self.create_image(image, *image_fit)]
                         }
                         Resource::EmbeddedRgbaImage { width, height, data } => {
                             let image = image::ImageBuffer::<image::Rgba<u8>, &[u8]>::from_raw(
@@ -430,12 +455,19 @@ impl RenderingPrimitivesBuilder for GLRenderingPrimitivesBuilder {
                                 data.as_slice(),
                             )
                             .unwrap();
-                            smallvec![self.create_image(image)]
+                            smallvec![self.create_image(image, *image_fit)]
                         }
                         Resource::None => SmallVec::new(),
                     }
                 }
-                HighLevelRenderingPrimitive::Text { text, font_family, font_size } => {
+                HighLevelRenderingPrimitive::Text {
+                    text,
+                    font_family,
+                    font_size,
+                    font_properties,
+                    right_to_left,
+                    letter_spacing,
+                } => {
                     if self.text_cursor_rect.is_none() {
                         let rect = Rect::new(Point::default(), Size::new(1., 1.));
                         self.text_cursor_rect = Some(TextCursor::from_primitive(
@@ -443,17 +475,33 @@ impl RenderingPrimitivesBuilder for GLRenderingPrimitivesBuilder {
                         ));
                     }
 
-                    smallvec![self.create_glyph_runs(text, font_family, *font_size)]
+                    smallvec![self.create_glyph_runs(
+                        text,
+                        font_family,
+                        *font_properties,
+                        *font_size,
+                        *right_to_left,
+                        *letter_spacing
+                    )]
                 }
                 HighLevelRenderingPrimitive::Path { width, height, elements, stroke_width } => {
                     let mut primitives = SmallVec::new();
 
                     let path_iter = elements.iter_fitted(*width, *height);
 
-                    primitives.extend(self.fill_path(path_iter.iter()).into_iter());
+                    let fill = self.fill_path(path_iter.iter());
+                    let stroke = if *stroke_width > 0. {
+                        self.stroke_path(path_iter.iter(), *stroke_width)
+                    } else {
+                        None
+                    };
 
-                    primitives
-                        .extend(self.stroke_path(path_iter.iter(), *stroke_width).into_iter());
+                    // The stroke must be drawn with the second color, even if the fill produced no geometry
+                    if fill.is_none() && stroke.is_some() {
+                        primitives.push(GLRenderingPrimitive::SkipVariable);
+                    }
+                    primitives.extend(fill.into_iter());
+                    primitives.extend(stroke.into_iter());
 
                     primitives
                 }
@@ -611,6 +659,7 @@ impl GLRenderingPrimitivesBuilder {
     fn create_image(
         &mut self,
         image: image::ImageBuffer<image::Rgba<u8>, &[u8]>,
+        image_fit: ImageFit,
     ) -> GLRenderingPrimitive {
         let image_size = Size::new(image.width() as _, image.height() as _);
         let source_size = image.dimensions();
@@ -637,6 +686,8 @@ impl GLRenderingPrimitivesBuilder {
             texture_vertices,
             texture: atlas_allocation,
             image_size,
+            image_fit,
+            cropped_texture_vertices: Default::default(),
         }
     }
 
@@ -645,24 +696,40 @@ impl GLRenderingPrimitivesBuilder {
         &mut self,
         text: &str,
         font_family: &str,
+        font_properties: FaceProperties,
         pixel_size: f32,
+        right_to_left: bool,
+        letter_spacing: f32,
     ) -> GLRenderingPrimitive {
-        let cached_glyphs = self.platform_data.glyph_cache.find_font(font_family, pixel_size);
+        let cached_glyphs =
+            self.platform_data.glyph_cache.find_font(font_family, font_properties, pixel_size);
         let mut cached_glyphs = cached_glyphs.borrow_mut();
-        let mut atlas = self.texture_atlas.borrow_mut();
-        let glyphs_runs = cached_glyphs.render_glyphs(&self.context, &mut atlas, text);
+        let mut atlas = self.platform_data.glyph_atlas.borrow_mut();
+        let glyphs_runs = cached_glyphs.render_glyphs(
+            &self.context,
+            &mut atlas,
+            text,
+            right_to_left,
+            letter_spacing,
+        );
         GLRenderingPrimitive::GlyphRuns { glyph_runs: glyphs_runs }
     }
 
+    /// The browser applies the unicode bidirectional algorithm when rendering the text into the
+    /// canvas, so there is nothing to do for right-to-left text. The letter spacing is not
+    /// supported yet.
     #[cfg(target_arch = "wasm32")]
     fn create_glyph_runs(
         &mut self,
         text: &str,
         font_family: &str,
+        font_properties: FaceProperties,
         pixel_size: f32,
+        _right_to_left: bool,
+        _letter_spacing: f32,
     ) -> GLRenderingPrimitive {
-        let font =
-            sixtyfps_corelib::font::FONT_CACHE.with(|fc| fc.find_font(font_family, pixel_size));
+        let font = sixtyfps_corelib::font::FONT_CACHE
+            .with(|fc| fc.find_font(font_family, font_properties, pixel_size));
         let text_canvas = font.render_text(text);
 
         let texture = Rc::new(GLTexture::new_from_canvas(&self.context, &text_canvas));
@@ -692,7 +759,13 @@ impl GLRenderingPrimitivesBuilder {
         let texture_vertices = GLArrayBuffer::new(&self.context, &normalized_coordinates);
         let vertex_count = 6;
 
-        let glyph_runs = vec![GlyphRun { vertices, texture_vertices, texture, vertex_count }];
+        let glyph_runs = vec![GlyphRun {
+            vertices,
+            texture_vertices,
+            texture,
+            vertex_count,
+            _glyph_allocations: Vec::new(),
+        }];
 
         GLRenderingPrimitive::GlyphRuns { glyph_runs }
     }
@@ -749,43 +822,78 @@ impl GraphicsFrame for GLFrame {
                     self.fill_path(&matrix, vertices, indices, col);
                     None
                 }
+                GLRenderingPrimitive::SkipVariable => {
+                    rendering_var.next();
+                    None
+                }
                 GLRenderingPrimitive::Texture {
                     vertices,
                     texture_vertices,
                     texture,
                     image_size,
+                    image_fit,
+                    cropped_texture_vertices,
                 } => {
-                    let matrix = if let Some(scaled_width) = rendering_var.next() {
-                        matrix
-                            * Matrix4::from_nonuniform_scale(
-                                scaled_width.as_scaled_width() / image_size.width,
-                                1.,
-                                1.,
-                            )
-                    } else {
-                        matrix
+                    let box_width = match rendering_var.peek() {
+                        Some(RenderingVariable::ScaledWidth(width)) => {
+                            rendering_var.next();
+                            *width
+                        }
+                        _ => image_size.width,
                     };
-
-                    let matrix = if let Some(scaled_height) = rendering_var.next() {
-                        matrix
-                            * Matrix4::from_nonuniform_scale(
-                                1.,
-                                scaled_height.as_scaled_height() / image_size.height,
-                                1.,
-                            )
-                    } else {
-                        matrix
+                    let box_height = match rendering_var.peek() {
+                        Some(RenderingVariable::ScaledHeight(height)) => {
+                            rendering_var.next();
+                            *height
+                        }
+                        _ => image_size.height,
                     };
 
-                    self.render_texture(&matrix, vertices, texture_vertices, texture);
+                    let box_size = Size::new(box_width, box_height);
+                    let (source, target) =
+                        sixtyfps_corelib::graphics::fit_image(*image_fit, *image_size, box_size);
+
+                    // The vertices cover the whole image, map them to the target rectangle
+                    let matrix = matrix
+                        * Matrix4::from_translation(cgmath::Vector3::new(
+                            target.origin.x,
+                            target.origin.y,
+                            0.,
+                        ))
+                        * Matrix4::from_nonuniform_scale(
+                            target.size.width / image_size.width,
+                            target.size.height / image_size.height,
+                            1.,
+                        );
+
+                    if source.size == *image_size {
+                        self.render_texture(&matrix, vertices, texture_vertices, texture);
+                    } else {
+                        // The cropped part only changes with the size of the box
+                        let mut cropped_texture_vertices = cropped_texture_vertices.borrow_mut();
+                        if !matches!(&*cropped_texture_vertices, Some((size, _)) if *size == box_size)
+                        {
+                            *cropped_texture_vertices = Some((
+                                box_size,
+                                GLArrayBuffer::new(
+                                    &self.context,
+                                    &texture.normalized_texture_coordinates_of(&source),
+                                ),
+                            ));
+                        }
+                        let (_, cropped_texture_vertices) =
+                            cropped_texture_vertices.as_ref().unwrap();
+                        self.render_texture(&matrix, vertices, cropped_texture_vertices, texture);
+                    }
                     None
                 }
                 GLRenderingPrimitive::GlyphRuns { glyph_runs } => {
                     let col: ARGBColor<f32> = (*rendering_var.next().unwrap().as_color()).into();
 
                     let render_glyphs = |text_color| {
-                        for GlyphRun { vertices, texture_vertices, texture, vertex_count } in
-                            glyph_runs
+                        for GlyphRun {
+                            vertices, texture_vertices, texture, vertex_count, ..
+                        } in glyph_runs
                         {
                             self.render_glyph_run(
                                 &matrix,
@@ -893,6 +1001,27 @@ impl GraphicsFrame for GLFrame {
                         }
                     }
 
+                    match (rendering_var.peek(), &self.text_cursor_rect) {
+                        (
+                            Some(RenderingVariable::TextDecoration(y, width, thickness)),
+                            Some(text_cursor),
+                        ) => {
+                            let matrix = matrix
+                                * Matrix4::from_translation(cgmath::Vector3::new(0., *y, 0.))
+                                * Matrix4::from_nonuniform_scale(*width, *thickness, 1.);
+
+                            self.fill_path(
+                                &matrix,
+                                &text_cursor.vertices,
+                                &text_cursor.indices,
+                                col,
+                            );
+
+                            rendering_var.next();
+                        }
+                        _ => {}
+                    }
+
                     match (rendering_var.peek(), &self.text_cursor_rect) {
                         (
                             Some(RenderingVariable::TextCursor(x, width, height)),
@@ -99,7 +99,7 @@ impl PathShader {
         precision mediump float;
         varying lowp vec4 fragcolor;
         void main() {
-            gl_FragColor = fragcolor;
+            gl_FragColor = vec4(fragcolor.rgb * fragcolor.a, fragcolor.a);
         }"#;
 
         let inner = Rc::new(Shader::new(&gl, PATH_VERTEX_SHADER, PATH_FRAGMENT_SHADER));
@@ -243,7 +243,8 @@ impl GlyphShader {
         varying lowp vec4 fragcolor;
         uniform sampler2D tex;
         void main() {
-            gl_FragColor = fragcolor * texture2D(tex, frag_tex_pos).a;
+            // The glyphs are only used for their coverage, stored in the alpha channel
+            gl_FragColor = vec4(fragcolor.rgb * fragcolor.a, fragcolor.a) * texture2D(tex, frag_tex_pos).a;
         }"#;
 
         let inner = Rc::new(Shader::new(&gl, GLYPH_VERTEX_SHADER, GLYPH_FRAGMENT_SHADER));
@@ -12,11 +12,41 @@ use glow::HasContext;
 use pathfinder_geometry::{rect::RectI, vector::Vector2I};
 use std::{cell::RefCell, rc::Rc};
 
+/// The layout of the pixels stored in a texture.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TextureFormat {
+    /// Four channels per pixel, with the colors premultiplied by the alpha channel.
+    Rgba,
+    /// A single channel of coverage per pixel. This is used for the glyphs, which are
+    /// tinted with the color of the text at draw time, so that the same rasterized glyph
+    /// can be used for any color.
+    Alpha,
+}
+
+impl TextureFormat {
+    fn gl_format(self) -> u32 {
+        match self {
+            TextureFormat::Rgba => glow::RGBA,
+            TextureFormat::Alpha => glow::ALPHA,
+        }
+    }
+
+    fn channel_count(self) -> u8 {
+        match self {
+            TextureFormat::Rgba => 4,
+            TextureFormat::Alpha => 1,
+        }
+    }
+}
+
+/// Textures store colors with premultiplied alpha, like the output of all the shaders, because
+/// that is what the blend function of the renderer expects (see `GLRenderer::new_frame`).
+/// This also avoids dark fringes when the linear filtering interpolates between a transparent
+/// and an opaque pixel, such as at the antialiased edges of the glyphs.
 pub struct GLTexture {
     texture_id: <GLContext as HasContext>::Texture,
     context: Rc<glow::Context>,
-    width: i32,
-    height: i32,
+    format: TextureFormat,
 }
 
 impl PartialEq for GLTexture {
@@ -28,6 +58,7 @@ impl PartialEq for GLTexture {
 impl GLTexture {
     fn new_with_size_and_data(
         gl: &Rc<glow::Context>,
+        format: TextureFormat,
         width: i32,
         height: i32,
         data: Option<&[u8]>,
@@ -52,19 +83,21 @@ impl GLTexture {
             gl.tex_image_2d(
                 glow::TEXTURE_2D,
                 0,
-                glow::RGBA as i32,
+                format.gl_format() as i32,
                 width,
                 height,
                 0,
-                glow::RGBA,
+                format.gl_format(),
                 glow::UNSIGNED_BYTE,
                 data,
             )
         }
 
-        Self { texture_id, context: gl.clone(), width, height }
+        Self { texture_id, context: gl.clone(), format }
     }
 
+    /// Only the alpha channel of the canvas is used by the glyph shader, so the colors are
+    /// uploaded as they are, without being premultiplied.
     #[cfg(target_arch = "wasm32")]
     pub fn new_from_canvas(gl: &Rc<glow::Context>, canvas: &web_sys::HtmlCanvasElement) -> Self {
         let texture_id = unsafe { gl.create_texture().unwrap() };
@@ -94,33 +127,40 @@ impl GLTexture {
             )
         }
 
-        Self {
-            texture_id,
-            context: gl.clone(),
-            width: canvas.width() as _,
-            height: canvas.height() as _,
-        }
+        Self { texture_id, context: gl.clone(), format: TextureFormat::Rgba }
     }
 
-    fn set_sub_image<Container: core::ops::Deref<Target = [u8]>>(
+    fn set_sub_image<
+        PixelType: image::Pixel<Subpixel = u8> + 'static,
+        Container: core::ops::Deref<Target = [u8]>,
+    >(
         &self,
         x: i32,
         y: i32,
-        image: image::ImageBuffer<image::Rgba<u8>, Container>,
+        image: image::ImageBuffer<PixelType, Container>,
     ) {
+        assert_eq!(PixelType::CHANNEL_COUNT, self.format.channel_count());
+        let (width, height) = (image.width() as i32, image.height() as i32);
+        let mut pixels = image.into_raw().to_vec();
+        if self.format == TextureFormat::Rgba {
+            premultiply_alpha(&mut pixels);
+        }
         unsafe {
             self.context.bind_texture(glow::TEXTURE_2D, Some(self.texture_id));
+            // Rows of single channel images are not necessarily aligned to 4 bytes
+            self.context.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
             self.context.tex_sub_image_2d(
                 glow::TEXTURE_2D,
                 0,
                 x,
                 y,
-                image.width() as i32,
-                image.height() as i32,
-                glow::RGBA,
+                width,
+                height,
+                self.format.gl_format(),
                 glow::UNSIGNED_BYTE,
-                glow::PixelUnpackData::Slice(&image.into_raw()),
+                glow::PixelUnpackData::Slice(&pixels),
             );
+            self.context.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
         }
     }
 
@@ -136,6 +176,33 @@ impl GLTexture {
     }
 }
 
+/// Multiplies the color channels of RGBA pixels by their alpha channel.
+fn premultiply_alpha(pixels: &mut [u8]) {
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u32;
+        for channel in &mut pixel[..3] {
+            *channel = ((*channel as u32 * alpha + 127) / 255) as u8;
+        }
+    }
+}
+
+#[test]
+fn premultiplied_blending() {
+    let mut pixels = [255, 255, 255, 255, 255, 128, 0, 128, 200, 100, 50, 0];
+    premultiply_alpha(&mut pixels);
+    assert_eq!(pixels, [255, 255, 255, 255, 128, 64, 0, 128, 0, 0, 0, 0]);
+
+    // Blending with ONE, ONE_MINUS_SRC_ALPHA gives the same result as the usual "over"
+    // operator on non-premultiplied colors
+    let straight = [255u32, 128, 0, 128];
+    let background = [0u32, 0, 255];
+    for i in 0..3 {
+        let blended = pixels[4 + i] as u32 + background[i] * (255 - pixels[7] as u32) / 255;
+        let over = (straight[i] * straight[3] + background[i] * (255 - straight[3])) / 255;
+        assert!((blended as i32 - over as i32).abs() <= 1);
+    }
+}
+
 impl Drop for GLTexture {
     fn drop(&mut self) {
         unsafe {
@@ -144,35 +211,54 @@ impl Drop for GLTexture {
     }
 }
 
-pub(crate) struct GLAtlasTexture {
-    pub(crate) texture: Rc<GLTexture>,
+/// A texture of the atlas and the allocator of its regions. The texture is a `GLTexture`, except
+/// in the tests that don't have a GL context.
+pub(crate) struct GLAtlasTexture<Texture = GLTexture> {
+    pub(crate) texture: Rc<Texture>,
     allocator: RefCell<guillotiere::AtlasAllocator>,
 }
 
-pub struct AtlasAllocation {
+pub struct AtlasAllocation<Texture = GLTexture> {
     pub texture_coordinates: RectI, // excludes padding
+    /// The factor by which the image was scaled down to fit in the largest texture of the GPU
+    image_scale: f32,
     allocation_id: guillotiere::AllocId,
-    pub(crate) atlas: Rc<GLAtlasTexture>,
+    pub(crate) atlas: Rc<GLAtlasTexture<Texture>>,
 }
 
-impl Drop for AtlasAllocation {
+impl<Texture> Drop for AtlasAllocation<Texture> {
     fn drop(&mut self) {
         self.atlas.allocator.borrow_mut().deallocate(self.allocation_id)
     }
 }
 
-impl AtlasAllocation {
+impl<Texture> AtlasAllocation<Texture> {
     pub(crate) fn normalized_texture_coordinates(&self) -> [Vertex; 6] {
-        let atlas_width = self.atlas.texture.width as f32;
-        let atlas_height = self.atlas.texture.height as f32;
-        let origin = self.texture_coordinates.origin();
         let size = self.texture_coordinates.size();
-        let texture_coordinates = RectI::new(origin, size);
+        self.normalized_texture_coordinates_of(&sixtyfps_corelib::graphics::Rect::new(
+            Default::default(),
+            sixtyfps_corelib::graphics::Size::new(
+                size.x() as f32 / self.image_scale,
+                size.y() as f32 / self.image_scale,
+            ),
+        ))
+    }
+
+    /// Returns the texture coordinates of the `source` rectangle, in pixels of the image that was
+    /// allocated.
+    pub(crate) fn normalized_texture_coordinates_of(
+        &self,
+        source: &sixtyfps_corelib::graphics::Rect,
+    ) -> [Vertex; 6] {
+        let atlas_size = self.atlas.allocator.borrow().size();
+        let (atlas_width, atlas_height) = (atlas_size.width as f32, atlas_size.height as f32);
+        let origin = self.texture_coordinates.origin();
+        let (origin_x, origin_y) = (origin.x() as f32, origin.y() as f32);
 
-        let tex_left = (texture_coordinates.min_x() as f32) / atlas_width;
-        let tex_top = (texture_coordinates.min_y() as f32) / atlas_height;
-        let tex_right = (texture_coordinates.max_x() as f32) / atlas_width;
-        let tex_bottom = (texture_coordinates.max_y() as f32) / atlas_height;
+        let tex_left = (origin_x + source.min_x() * self.image_scale) / atlas_width;
+        let tex_top = (origin_y + source.min_y() * self.image_scale) / atlas_height;
+        let tex_right = (origin_x + source.max_x() * self.image_scale) / atlas_width;
+        let tex_bottom = (origin_y + source.max_y() * self.image_scale) / atlas_height;
 
         let tex_vertex1 = Vertex { _pos: [tex_left, tex_top] };
         let tex_vertex2 = Vertex { _pos: [tex_right, tex_top] };
@@ -183,15 +269,35 @@ impl AtlasAllocation {
     }
 }
 
-impl GLAtlasTexture {
-    fn new(gl: &Rc<glow::Context>) -> Self {
-        let allocator = guillotiere::AtlasAllocator::new(guillotiere::Size::new(2048, 2048));
-        let texture = Rc::new(GLTexture::new_with_size_and_data(
-            gl,
-            allocator.size().width,
-            allocator.size().height,
-            None,
-        ));
+/// The size of the textures of the atlas, unless an image doesn't fit in it or the GPU doesn't
+/// support textures that large.
+const ATLAS_SIZE: i32 = 2048;
+
+/// Returns the size of a new atlas texture that can hold an image of the requested size, or None
+/// if the image is larger than the largest texture of the GPU.
+fn atlas_size_for(
+    requested_width: i32,
+    requested_height: i32,
+    max_texture_size: i32,
+) -> Option<guillotiere::Size> {
+    if requested_width > max_texture_size || requested_height > max_texture_size {
+        return None;
+    }
+    let regular_size = ATLAS_SIZE.min(max_texture_size);
+    Some(guillotiere::Size::new(
+        requested_width.max(regular_size),
+        requested_height.max(regular_size),
+    ))
+}
+
+fn max_texture_size(gl: &glow::Context) -> i32 {
+    unsafe { gl.get_parameter_i32(glow::MAX_TEXTURE_SIZE) }
+}
+
+impl<Texture> GLAtlasTexture<Texture> {
+    fn new(size: guillotiere::Size, new_texture: impl FnOnce(i32, i32) -> Texture) -> Self {
+        let allocator = guillotiere::AtlasAllocator::new(size);
+        let texture = Rc::new(new_texture(allocator.size().width, allocator.size().height));
         Self { texture, allocator: RefCell::new(allocator) }
     }
 
@@ -199,7 +305,7 @@ impl GLAtlasTexture {
         self: Rc<Self>,
         requested_width: i32,
         requested_height: i32,
-    ) -> Option<AtlasAllocation> {
+    ) -> Option<AtlasAllocation<Texture>> {
         self.allocator
             .borrow_mut()
             .allocate(guillotiere::Size::new(requested_width, requested_height))
@@ -212,6 +318,7 @@ impl GLAtlasTexture {
 
                 AtlasAllocation {
                     texture_coordinates,
+                    image_scale: 1.,
                     allocation_id: guillotiere_alloc.id,
                     atlas: self.clone(),
                 }
@@ -219,48 +326,90 @@ impl GLAtlasTexture {
     }
 }
 
-pub struct TextureAtlas {
-    atlases: Vec<Rc<GLAtlasTexture>>,
+/// All the textures of an atlas have the same format, so the images allocated in it must
+/// have the matching number of channels.
+pub struct TextureAtlas<Texture = GLTexture> {
+    atlases: Vec<Rc<GLAtlasTexture<Texture>>>,
+    format: TextureFormat,
 }
 
-impl TextureAtlas {
-    pub fn new() -> Self {
-        Self { atlases: vec![] }
+impl<Texture> TextureAtlas<Texture> {
+    pub fn new(format: TextureFormat) -> Self {
+        Self { atlases: vec![], format }
     }
 
-    fn allocate_region(
+    /// Returns None if the requested size is larger than `max_texture_size`. `new_texture` creates
+    /// the texture of a new atlas of the given width and height.
+    pub(crate) fn allocate_region(
         &mut self,
-        gl: &Rc<glow::Context>,
         requested_width: i32,
         requested_height: i32,
-    ) -> AtlasAllocation {
-        self.atlases
+        max_texture_size: i32,
+        new_texture: impl FnOnce(i32, i32) -> Texture,
+    ) -> Option<AtlasAllocation<Texture>> {
+        if let Some(allocation) = self
+            .atlases
             .iter()
             .find_map(|atlas| atlas.clone().allocate(requested_width, requested_height))
-            .unwrap_or_else(|| {
-                let new_atlas = Rc::new(GLAtlasTexture::new(&gl));
-                let atlas_allocation =
-                    new_atlas.clone().allocate(requested_width, requested_height).unwrap();
-                self.atlases.push(new_atlas);
-                atlas_allocation
-            })
+        {
+            return Some(allocation);
+        }
+
+        // All the atlases are full, so add one that is large enough for this request.
+        let size = atlas_size_for(requested_width, requested_height, max_texture_size)?;
+        let new_atlas = Rc::new(GLAtlasTexture::new(size, new_texture));
+        let atlas_allocation = new_atlas.clone().allocate(requested_width, requested_height)?;
+        // An image bigger than the regular atlas size gets a texture of its own, that
+        // is released together with the allocation.
+        let regular_size = ATLAS_SIZE.min(max_texture_size);
+        if size.width == regular_size && size.height == regular_size {
+            self.atlases.push(new_atlas);
+        }
+        Some(atlas_allocation)
     }
+}
 
-    pub fn allocate_image_in_atlas(
+impl TextureAtlas<GLTexture> {
+    pub fn allocate_image_in_atlas<PixelType: image::Pixel<Subpixel = u8> + 'static>(
         &mut self,
         gl: &Rc<glow::Context>,
-        image: image::ImageBuffer<image::Rgba<u8>, &[u8]>,
+        image: image::ImageBuffer<PixelType, &[u8]>,
     ) -> AtlasAllocation {
         use image::GenericImage;
         use image::GenericImageView;
 
+        let max_texture_size = max_texture_size(gl);
+
+        // Images that are larger than the largest texture, minus the padding, are scaled down
+        let max_image_size = (max_texture_size - 2) as u32;
+        let image_scale =
+            (max_image_size as f32 / image.width().max(image.height()) as f32).min(1.);
+        let scaled_image;
+        let image = if image_scale < 1. {
+            scaled_image = image::imageops::resize(
+                &image,
+                ((image.width() as f32 * image_scale) as u32).max(1),
+                ((image.height() as f32 * image_scale) as u32).max(1),
+                image::imageops::FilterType::Triangle,
+            );
+            image::ImageBuffer::<PixelType, &[u8]>::from_raw(
+                scaled_image.width(),
+                scaled_image.height(),
+                scaled_image.as_raw(),
+            )
+            .unwrap()
+        } else {
+            image
+        };
+
         // To avoid pixels leaking from adjacent textures in the atlas when scaling, add a one-pixel
         // padding.
 
         let requested_width = image.width() + 2;
         let requested_height = image.height() + 2;
 
-        let mut padded_image = image::ImageBuffer::new(requested_width, requested_height);
+        let mut padded_image =
+            image::ImageBuffer::<PixelType, Vec<u8>>::new(requested_width, requested_height);
 
         let mut blit = |target_x, target_y, source_x, source_y, source_width, source_height| {
             padded_image
@@ -307,7 +456,15 @@ impl TextureAtlas {
             1,
         );
 
-        let mut allocation = self.allocate_region(gl, requested_width as _, requested_height as _);
+        let format = self.format;
+        let mut allocation = self
+            .allocate_region(
+                requested_width as _,
+                requested_height as _,
+                max_texture_size,
+                |width, height| GLTexture::new_with_size_and_data(gl, format, width, height, None),
+            )
+            .expect("the scaled image always fits in a texture");
 
         allocation.atlas.texture.set_sub_image(
             allocation.texture_coordinates.origin_x(),
@@ -318,7 +475,45 @@ impl TextureAtlas {
         // Remove the padding from the coordinates we use for sampling
         allocation.texture_coordinates =
             allocation.texture_coordinates.contract(Vector2I::new(1, 1));
+        allocation.image_scale = image_scale;
 
         allocation
     }
 }
+
+#[test]
+fn atlas_fallback() {
+    // The tests have no GL context, so the textures only record their size
+    let mut atlas = TextureAtlas::<(i32, i32)>::new(TextureFormat::Alpha);
+    let max_texture_size = 4096;
+    let mut allocate = |width, height| {
+        atlas.allocate_region(width, height, max_texture_size, |width, height| (width, height))
+    };
+
+    // Many unique glyphs fill more than one atlas of the regular size
+    let glyphs: Vec<_> =
+        (0..20000).map(|i| allocate(10 + i % 50, 20 + i % 30).expect("glyph fits")).collect();
+    let first_atlas = &glyphs.first().unwrap().atlas;
+    let last_atlas = &glyphs.last().unwrap().atlas;
+    assert!(!Rc::ptr_eq(first_atlas, last_atlas));
+    assert_eq!(*first_atlas.texture, (ATLAS_SIZE, ATLAS_SIZE));
+    assert_eq!(*last_atlas.texture, (ATLAS_SIZE, ATLAS_SIZE));
+
+    // Images larger than the regular atlas size get a texture of their own
+    let wide_image = allocate(ATLAS_SIZE + 500, 100).expect("wide image fits");
+    assert_eq!(*wide_image.atlas.texture, (ATLAS_SIZE + 500, ATLAS_SIZE));
+    let large_image = allocate(max_texture_size, max_texture_size).expect("large image fits");
+    assert_eq!(*large_image.atlas.texture, (max_texture_size, max_texture_size));
+
+    // ... unless they don't fit in the largest texture of the GPU
+    assert!(allocate(max_texture_size + 1, 10).is_none());
+    assert!(atlas.atlases.iter().all(|atlas| *atlas.texture == (ATLAS_SIZE, ATLAS_SIZE)));
+
+    // The regular atlas size is clamped to the largest texture of the GPU
+    let mut small_gpu_atlas = TextureAtlas::<(i32, i32)>::new(TextureFormat::Alpha);
+    let small_glyph =
+        small_gpu_atlas.allocate_region(10, 10, 1024, |width, height| (width, height)).unwrap();
+    assert_eq!(*small_glyph.atlas.texture, (1024, 1024));
+    assert_eq!(small_gpu_atlas.atlases.len(), 1);
+    assert!(small_gpu_atlas.allocate_region(1025, 10, 1024, |_, _| unreachable!()).is_none());
+}
@@ -18,7 +18,7 @@ use sixtyfps_corelib::input::{
     FocusEvent, InputEventResult, KeyEvent, KeyEventResult, MouseEvent, MouseEventType,
 };
 use sixtyfps_corelib::item_rendering::CachedRenderingData;
-use sixtyfps_corelib::items::{Item, ItemConsts, ItemVTable};
+use sixtyfps_corelib::items::{ImageFit, Item, ItemConsts, ItemVTable};
 use sixtyfps_corelib::layout::LayoutInfo;
 use sixtyfps_corelib::rtti::*;
 use sixtyfps_corelib::{ItemVTable_static, Property, SharedArray, SharedString, Signal};
@@ -137,7 +137,10 @@ impl Item for NativeButton {
             qApp->style()->drawControl(QStyle::CE_PushButton, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -183,6 +186,9 @@ impl Item for NativeButton {
                     InputEventResult::EventIgnored
                 }
             }
+            MouseEventType::MouseWheel { .. } | MouseEventType::PinchGesture { .. } => {
+                return InputEventResult::EventIgnored
+            }
         });
         if matches!(event.what, MouseEventType::MouseReleased) {
             Self::FIELD_OFFSETS.clicked.apply_pin(self).emit(&());
@@ -253,7 +259,10 @@ impl Item for NativeCheckBox {
             qApp->style()->drawControl(QStyle::CE_CheckBox, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -393,7 +402,10 @@ impl Item for NativeSpinBox {
             p.drawText(text_rect, QString::number(value));
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -481,7 +493,9 @@ impl Item for NativeSpinBox {
                     }
                     true
                 }
-                MouseEventType::MouseMoved => false,
+                MouseEventType::MouseMoved
+                | MouseEventType::MouseWheel { .. }
+                | MouseEventType::PinchGesture { .. } => false,
             };
         data.active_controls = new_control;
         if changed {
@@ -584,7 +598,10 @@ impl Item for NativeSlider {
             style->drawComplexControl(QStyle::CC_Slider, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -679,6 +696,9 @@ impl Item for NativeSlider {
                     InputEventResult::EventIgnored
                 }
             }
+            MouseEventType::MouseWheel { .. } | MouseEventType::PinchGesture { .. } => {
+                InputEventResult::EventIgnored
+            }
         };
         data.active_controls = new_control;
 
@@ -755,7 +775,10 @@ impl Item for NativeGroupBox {
             qApp->style()->drawComplexControl(QStyle::CC_GroupBox, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -884,7 +907,10 @@ impl Item for NativeLineEdit {
             qApp->style()->drawPrimitive(QStyle::PE_PanelLineEdit, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -1016,7 +1042,10 @@ impl Item for NativeScrollBar {
             style->drawComplexControl(QStyle::CC_Slider, &option, &p, global_widget());
             return img;
         });
-        return HighLevelRenderingPrimitive::Image { source: to_resource(img) };
+        return HighLevelRenderingPrimitive::Image {
+            source: to_resource(img),
+            image_fit: ImageFit::fill,
+        };
     }
 
     fn rendering_variables(
@@ -1157,6 +1186,13 @@ impl Item for NativeScrollBar {
                     InputEventResult::EventAccepted
                 }
             }
+            MouseEventType::MouseWheel { delta_x, delta_y } => {
+                let delta = if horizontal { delta_x } else { delta_y };
+                let max = max as f32;
+                self.value.set((value as f32 - delta).max(0.).min(max));
+                InputEventResult::EventAccepted
+            }
+            MouseEventType::PinchGesture { .. } => InputEventResult::EventIgnored,
         };
         self.data.set(data);
         result
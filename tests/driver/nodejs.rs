@@ -24,7 +24,8 @@ pub fn test(testcase: &test_driver_lib::TestCase) -> Result<(), Box<dyn Error>>
         r#"
                 const assert = require('assert').strict;
                 let sixtyfpslib = require(String.raw`{sixtyfpspath}`);
-                let sixtyfps = require(String.raw`{path}`);
+                let testcase_path = String.raw`{path}`;
+                let sixtyfps = require(testcase_path);
         "#,
         sixtyfpspath = sixtyfpspath.to_string_lossy(),
         path = testcase.absolute_path.to_string_lossy()
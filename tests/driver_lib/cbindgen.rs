@@ -54,10 +54,17 @@ fn gen_corelib(include_dir: &Path) -> anyhow::Result<()> {
         "ComponentWindowOpaque",
         "PropertyAnimation",
         "EasingCurve",
+        "ImageFit",
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
+        "TextDirection",
+        "TextDecoration",
+        "FontStyle",
         "Window",
         "TextInput",
+        "Visibility",
+        "Opacity",
+        "Clip",
     ]
     .iter()
     .map(|x| x.to_string())
@@ -80,6 +87,8 @@ fn gen_corelib(include_dir: &Path) -> anyhow::Result<()> {
         "sixtyfps_property_listener_scope_is_dirty",
         "PropertyTrackerOpaque",
         "SignalOpaque",
+        "StringSignal",
+        "SizeSignal",
         "ComponentWindow",
     ]
     .iter()
@@ -207,7 +216,7 @@ fn gen_corelib(include_dir: &Path) -> anyhow::Result<()> {
         .with_include("sixtyfps_color.h")
         .with_include("sixtyfps_pathdata.h")
         .with_after_include(format!(
-            "namespace sixtyfps {{ namespace private_api {{ enum class VersionCheck {{ Major = {}, Minor = {}, Patch = {} }}; struct ComponentWindow; }} namespace cbindgen_private {{ using sixtyfps::private_api::ComponentWindow; }} }}",
+            "namespace sixtyfps {{ namespace private_api {{ enum class VersionCheck {{ Major = {}, Minor = {}, Patch = {} }}; struct ComponentWindow; }} namespace cbindgen_private {{ using sixtyfps::private_api::ComponentWindow; using StringSignal = sixtyfps::Signal<sixtyfps::SharedString>; using SizeSignal = sixtyfps::Signal<float, float>; }} }}",
             0, 0, 1,
         ))
         .generate()
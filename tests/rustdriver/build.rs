@@ -50,7 +50,7 @@ fn main() -> std::io::Result<()> {
         }
 
         output.write_all(b"\n")?;
-        output.write_all(source.as_bytes())?;
+        output.write_all(raw_string_templates(&source).as_bytes())?;
         output.write_all(b"}\n")?;
 
         for (i, x) in test_driver_lib::extract_test_functions(&source)
@@ -72,3 +72,42 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// The Rust tokenizer does not accept the `\{` of the string interpolations in the string literals
+/// given to the `sixtyfps!` macro, so these strings are written as raw strings, which the macro
+/// lexes as .60 strings.
+fn raw_string_templates(source: &str) -> String {
+    use sixtyfps_compilerlib::{lexer, parser::SyntaxKind};
+    let mut result = String::with_capacity(source.len());
+    // The string template being written, including the nested ones, and its nesting depth.
+    let mut template = String::new();
+    let mut depth = 0;
+    for token in lexer::lex(source) {
+        let text = token.text.as_str();
+        if token.kind == SyntaxKind::StringLiteral {
+            if lexer::string_literal_continues(text) {
+                if text.starts_with('"') {
+                    depth += 1;
+                }
+            } else if text.starts_with('}') {
+                depth -= 1;
+                if depth == 0 {
+                    template.push_str(text);
+                    let mut hashes = String::from("#");
+                    while template.contains(&format!("\"{}", hashes)) {
+                        hashes.push('#');
+                    }
+                    result.push_str(&format!("r{}{}{}", hashes, template, hashes));
+                    template.clear();
+                    continue;
+                }
+            }
+        }
+        if depth > 0 {
+            template.push_str(text);
+        } else {
+            result.push_str(text);
+        }
+    }
+    result
+}